@@ -1,5 +1,5 @@
 use serde::{Serialize, Deserialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
 
@@ -32,6 +32,80 @@ pub struct StackBaseline {
     /// Wall-clock time at which this baseline was saved/promoted.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub promoted_at: Option<String>,
+
+    /// How `expected_duration` was derived.
+    /// None for baselines created before this field was introduced (pinned run).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_method: Option<DurationMethod>,
+
+    /// SHA-256 of each service's Compose definition in the pinned run.
+    /// Empty for baselines pinned before hashes were recorded.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub service_hashes: BTreeMap<String, String>,
+
+    /// Deltas within these bounds are run-to-run jitter, not drift.
+    #[serde(flatten, default)]
+    pub tolerance: DriftTolerance,
+}
+
+/// Tolerances applied when a baseline leaves one unset. Normal runs
+/// jitter by a few points and seconds; this much is not drift.
+pub const DEFAULT_CONFIDENCE_TOLERANCE: u32 = 5;
+pub const DEFAULT_READINESS_TOLERANCE: u32 = 5;
+pub const DEFAULT_DURATION_TOLERANCE_PCT: u32 = 20;
+
+/// Per-baseline drift tolerances. Unset fields use the defaults above;
+/// set one to 0 to treat any change as drift.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DriftTolerance {
+    /// Confidence points the run may fall below the baseline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence_tolerance: Option<u32>,
+    /// Readiness points the run may fall below the baseline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readiness_tolerance: Option<u32>,
+    /// Slowdown, in percent of the expected duration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_tolerance_pct: Option<u32>,
+}
+
+impl DriftTolerance {
+    pub fn confidence(&self) -> u32 {
+        self.confidence_tolerance.unwrap_or(DEFAULT_CONFIDENCE_TOLERANCE)
+    }
+
+    pub fn readiness(&self) -> u32 {
+        self.readiness_tolerance.unwrap_or(DEFAULT_READINESS_TOLERANCE)
+    }
+
+    pub fn duration_pct(&self) -> u32 {
+        self.duration_tolerance_pct.unwrap_or(DEFAULT_DURATION_TOLERANCE_PCT)
+    }
+}
+
+/// How a baseline's expected duration was derived.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "snake_case", tag = "method")]
+pub enum DurationMethod {
+    /// Duration of the single pinned run.
+    PinnedRun,
+    /// Nearest-rank percentile over the most recent runs.
+    Percentile {
+        percentile: u32,
+        window:     usize,
+        /// Number of runs the percentile was actually computed over.
+        samples:    usize,
+    },
+}
+
+impl std::fmt::Display for DurationMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DurationMethod::PinnedRun => write!(f, "pinned run"),
+            DurationMethod::Percentile { percentile, samples, .. } =>
+                write!(f, "p{} over {} run{}", percentile, samples, if *samples == 1 { "" } else { "s" }),
+        }
+    }
 }
 
 /// A single entry in the per-stack baseline history log.
@@ -46,6 +120,26 @@ pub struct BaselineHistoryEntry {
     pub expected_duration: u64,
     pub expected_services: Vec<String>,
     pub service_scores: HashMap<String, u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_method: Option<DurationMethod>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub service_hashes: BTreeMap<String, String>,
+}
+
+impl From<&StackBaseline> for BaselineHistoryEntry {
+    fn from(b: &StackBaseline) -> Self {
+        BaselineHistoryEntry {
+            promoted_at:         b.promoted_at.clone().unwrap_or_default(),
+            pinned_at:           b.pinned_at.clone(),
+            expected_confidence: b.expected_confidence,
+            expected_readiness:  b.expected_readiness,
+            expected_duration:   b.expected_duration,
+            expected_services:   b.expected_services.clone(),
+            service_scores:      b.service_scores.clone(),
+            duration_method:     b.duration_method.clone(),
+            service_hashes:      b.service_hashes.clone(),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -56,6 +150,68 @@ pub struct BaselineDrift {
     pub confidence_delta: i32,
     pub readiness_delta: Option<i32>,
     pub duration_delta_percent: Option<i32>,
+
+    /// Tolerances of the baseline compared against.
+    pub tolerance: DriftTolerance,
+}
+
+/// A confidence or readiness drop below baseline larger than this
+/// (in percentage points) is Major drift.
+pub const MAJOR_DRIFT_DROP: i32 = 10;
+
+/// How far a run has drifted from its baseline contract.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum DriftSeverity {
+    /// Matches the baseline exactly.
+    None,
+    /// A score drop or slowdown beyond tolerance — nothing structural,
+    /// no large drop.
+    Minor,
+    /// Services added or missing, or a large confidence/readiness drop.
+    Major,
+}
+
+impl std::fmt::Display for DriftSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DriftSeverity::None  => write!(f, "NONE"),
+            DriftSeverity::Minor => write!(f, "MINOR"),
+            DriftSeverity::Major => write!(f, "MAJOR"),
+        }
+    }
+}
+
+impl BaselineDrift {
+    /// Only regressions count: a score drop or slowdown beyond the
+    /// baseline's tolerances, or a change in the service set. Higher
+    /// scores and faster runs are never drift. A drop only counts as
+    /// large once it also exceeds the tolerance.
+    pub fn severity(&self) -> DriftSeverity {
+        let t = &self.tolerance;
+        let conf_tol      = t.confidence() as i32;
+        let readiness_tol = t.readiness() as i32;
+        let duration_tol  = t.duration_pct() as i32;
+
+        let confidence = self.confidence_delta;
+        let readiness  = self.readiness_delta.unwrap_or(0);
+        let duration   = self.duration_delta_percent.unwrap_or(0);
+
+        let structural = !self.new_services.is_empty() || !self.missing_services.is_empty();
+        let large_drop = confidence < -MAJOR_DRIFT_DROP.max(conf_tol)
+            || readiness < -MAJOR_DRIFT_DROP.max(readiness_tol);
+
+        if structural || large_drop {
+            DriftSeverity::Major
+        } else if confidence < -conf_tol
+            || readiness < -readiness_tol
+            || duration > duration_tol
+        {
+            DriftSeverity::Minor
+        } else {
+            DriftSeverity::None
+        }
+    }
 }
 
 // ======================================================
@@ -84,10 +240,7 @@ fn baseline_history_dir(stack: &str) -> Result<PathBuf, String> {
 // SAVE
 // ======================================================
 
-pub fn save_baseline(
-    stack: &str,
-    baseline: &StackBaseline,
-) -> Result<(), String> {
+fn write_baseline_file(stack: &str, baseline: &StackBaseline) -> Result<(), String> {
 
     let dir = baseline_dir()?;
 
@@ -100,7 +253,15 @@ pub fn save_baseline(
         .map_err(|e| format!("Failed to serialize baseline: {}", e))?;
 
     fs::write(path, json)
-        .map_err(|e| format!("Failed to write baseline file: {}", e))?;
+        .map_err(|e| format!("Failed to write baseline file: {}", e))
+}
+
+pub fn save_baseline(
+    stack: &str,
+    baseline: &StackBaseline,
+) -> Result<(), String> {
+
+    write_baseline_file(stack, baseline)?;
 
     // --------------------------------------------------
     // Append to baseline history log
@@ -111,13 +272,8 @@ pub fn save_baseline(
         .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
 
     let entry = BaselineHistoryEntry {
-        promoted_at:         promoted_at.clone(),
-        pinned_at:           baseline.pinned_at.clone(),
-        expected_confidence: baseline.expected_confidence,
-        expected_readiness:  baseline.expected_readiness,
-        expected_duration:   baseline.expected_duration,
-        expected_services:   baseline.expected_services.clone(),
-        service_scores:      baseline.service_scores.clone(),
+        promoted_at: promoted_at.clone(),
+        ..BaselineHistoryEntry::from(baseline)
     };
 
     let hist_dir = baseline_history_dir(stack)?;
@@ -153,22 +309,146 @@ pub fn load_baseline(stack: &str) -> Option<StackBaseline> {
     serde_json::from_str(&content).ok()
 }
 
+// ======================================================
+// TOLERANCE
+// ======================================================
+
+/// Update the drift tolerances of a pinned baseline. Fields left None
+/// keep their current value. The contract itself is unchanged, so no
+/// new history version is recorded.
+pub fn set_tolerance(stack: &str, update: DriftTolerance) -> Result<DriftTolerance, String> {
+    let mut baseline = load_baseline(stack)
+        .ok_or_else(|| format!("No baseline found for '{}'", stack))?;
+
+    let t = &mut baseline.tolerance;
+    t.confidence_tolerance   = update.confidence_tolerance.or(t.confidence_tolerance);
+    t.readiness_tolerance    = update.readiness_tolerance.or(t.readiness_tolerance);
+    t.duration_tolerance_pct = update.duration_tolerance_pct.or(t.duration_tolerance_pct);
+
+    write_baseline_file(stack, &baseline)?;
+
+    Ok(baseline.tolerance)
+}
+
+// ======================================================
+// EXPORT / IMPORT
+// ======================================================
+
+/// Serialize a stack's pinned baseline as pretty JSON, for export.
+pub fn export_baseline(stack: &str) -> Result<String, String> {
+    let baseline = load_baseline(stack)
+        .ok_or_else(|| format!("No baseline found for '{}'", stack))?;
+
+    serde_json::to_string_pretty(&baseline)
+        .map_err(|e| format!("Failed to serialize baseline: {}", e))
+}
+
+/// Import a baseline exported from another host (or from Git) as the
+/// current contract for `stack`. The import is saved like any other
+/// promotion, so it appears as a new version in the history log. An
+/// existing baseline is only replaced when `force` is set.
+pub fn import_baseline(stack: &str, content: &str, force: bool) -> Result<StackBaseline, String> {
+    if !force && load_baseline(stack).is_some() {
+        return Err(format!(
+            "A baseline is already pinned for '{}'. Use --force to replace it \
+             (the current one stays in the baseline history).",
+            stack
+        ));
+    }
+
+    let mut baseline: StackBaseline = serde_json::from_str(content)
+        .map_err(|e| format!("Invalid baseline file: {}", e))?;
+
+    if baseline.schema_version > CURRENT_SCHEMA_VERSION {
+        return Err(format!(
+            "Baseline schema version {} is newer than this Rehearsa supports ({}).",
+            baseline.schema_version, CURRENT_SCHEMA_VERSION
+        ));
+    }
+
+    if baseline.expected_confidence > 100 {
+        return Err(format!(
+            "Invalid baseline: expected_confidence {} exceeds 100.",
+            baseline.expected_confidence
+        ));
+    }
+
+    if baseline.expected_readiness.is_some_and(|r| r > 100) {
+        return Err("Invalid baseline: expected_readiness exceeds 100.".to_string());
+    }
+
+    if let Some((svc, _)) = baseline.service_scores.iter().find(|(_, s)| **s > 100) {
+        return Err(format!("Invalid baseline: score for service '{}' exceeds 100.", svc));
+    }
+
+    if baseline.expected_services.is_empty() {
+        return Err("Invalid baseline: expected_services is empty.".to_string());
+    }
+
+    baseline.schema_version = CURRENT_SCHEMA_VERSION;
+    baseline.stack = stack.to_string();
+    baseline.promoted_at = Some(chrono::Utc::now().to_rfc3339());
+
+    save_baseline(stack, &baseline)?;
+
+    Ok(baseline)
+}
+
 // ======================================================
 // DELETE
 // ======================================================
 
-pub fn delete_baseline(stack: &str) -> Result<(), String> {
+/// Everything `baseline delete` would remove for a stack.
+#[derive(Debug)]
+pub struct BaselineDeletePlan {
+    /// Current baseline file, if one is pinned.
+    pub baseline_file:    Option<PathBuf>,
+    /// Baseline history log directory, if one exists.
+    pub history_dir:      Option<PathBuf>,
+    /// Number of versions recorded in the history log.
+    pub history_versions: usize,
+}
+
+impl BaselineDeletePlan {
+    pub fn is_empty(&self) -> bool {
+        self.baseline_file.is_none() && self.history_dir.is_none()
+    }
+}
+
+/// Work out what deleting a stack's baseline would remove, without touching disk.
+pub fn plan_baseline_delete(stack: &str) -> Result<BaselineDeletePlan, String> {
 
     let path = baseline_path(stack)?;
+    let hist_dir = baseline_history_dir(stack)?;
 
-    if !path.exists() {
-        return Ok(());
+    let history_versions = fs::read_dir(&hist_dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).count())
+        .unwrap_or(0);
+
+    Ok(BaselineDeletePlan {
+        baseline_file:    if path.exists() { Some(path) } else { None },
+        history_dir:      if hist_dir.exists() { Some(hist_dir) } else { None },
+        history_versions,
+    })
+}
+
+/// Delete the pinned baseline and its history log.
+/// Returns Ok(false) if there was nothing to delete.
+pub fn delete_baseline(stack: &str) -> Result<bool, String> {
+
+    let plan = plan_baseline_delete(stack)?;
+
+    if let Some(ref path) = plan.baseline_file {
+        fs::remove_file(path)
+            .map_err(|e| format!("Failed to delete baseline: {}", e))?;
     }
 
-    fs::remove_file(path)
-        .map_err(|e| format!("Failed to delete baseline: {}", e))?;
+    if let Some(ref dir) = plan.history_dir {
+        fs::remove_dir_all(dir)
+            .map_err(|e| format!("Failed to delete baseline history: {}", e))?;
+    }
 
-    Ok(())
+    Ok(!plan.is_empty())
 }
 
 // ======================================================
@@ -177,7 +457,14 @@ pub fn delete_baseline(stack: &str) -> Result<(), String> {
 
 /// Pin a historical run as the new baseline for a stack.
 /// If `timestamp` is None, the latest run is used.
-pub fn promote_baseline(stack: &str, timestamp: Option<&str>) -> Result<(), String> {
+/// If `duration_percentile` is set, `expected_duration` is computed as that
+/// percentile over the last `window` runs instead of the pinned run's duration.
+pub fn promote_baseline(
+    stack: &str,
+    timestamp: Option<&str>,
+    duration_percentile: Option<u32>,
+    window: usize,
+) -> Result<(), String> {
 
     let home = dirs::home_dir()
         .ok_or("Could not determine home directory")?;
@@ -232,9 +519,18 @@ pub fn promote_baseline(stack: &str, timestamp: Option<&str>) -> Result<(), Stri
     let record: crate::history::RunRecord = serde_json::from_str(&content)
         .map_err(|e| format!("Failed to parse history entry: {}", e))?;
 
+    // A --changed-only run's duration and reused scores don't describe
+    // the whole stack.
+    if !record.reused_services.is_empty() {
+        return Err(format!(
+            "Run {} of '{}' was a --changed-only run ({} service(s) reused). Promote a full run instead.",
+            record.timestamp, stack, record.reused_services.len()
+        ));
+    }
+
     let now = chrono::Utc::now().to_rfc3339();
 
-    let baseline = StackBaseline {
+    let mut baseline = StackBaseline {
         schema_version:      CURRENT_SCHEMA_VERSION,
         stack:               stack.to_string(),
         expected_services:   record.services.keys().cloned().collect(),
@@ -244,8 +540,16 @@ pub fn promote_baseline(stack: &str, timestamp: Option<&str>) -> Result<(), Stri
         service_scores:      record.services,
         pinned_at:           Some(record.timestamp.clone()),
         promoted_at:         Some(now),
+        duration_method:     Some(DurationMethod::PinnedRun),
+        service_hashes:      record.service_hashes,
+        // Tolerances are a setting on the contract, not a property of the run.
+        tolerance:           load_baseline(stack).map(|b| b.tolerance).unwrap_or_default(),
     };
 
+    if let Some(p) = duration_percentile {
+        apply_duration_percentile(&mut baseline, p, window)?;
+    }
+
     save_baseline(stack, &baseline)?;
 
     println!("Baseline promoted for stack '{}'.", stack);
@@ -254,7 +558,11 @@ pub fn promote_baseline(stack: &str, timestamp: Option<&str>) -> Result<(), Stri
     if let Some(r) = baseline.expected_readiness {
         println!("  Readiness       : {}%", r);
     }
-    println!("  Duration        : {}s", baseline.expected_duration);
+    println!(
+        "  Duration        : {}s ({})",
+        baseline.expected_duration,
+        baseline.duration_method.as_ref().unwrap_or(&DurationMethod::PinnedRun),
+    );
     println!(
         "  Services        : {}",
         baseline.expected_services.join(", ")
@@ -263,6 +571,190 @@ pub fn promote_baseline(stack: &str, timestamp: Option<&str>) -> Result<(), Stri
     Ok(())
 }
 
+// ======================================================
+// ROLLBACK
+// ======================================================
+
+/// Re-pin an earlier baseline version. `version` is 1-based as shown by
+/// `baseline history`; `to` matches a `promoted_at` timestamp (partial
+/// match supported). With neither, the version before the latest is used.
+/// The rollback is saved as a new version, so it can itself be undone.
+pub fn rollback_baseline(
+    stack: &str,
+    version: Option<usize>,
+    to: Option<&str>,
+) -> Result<(), String> {
+
+    let current = load_baseline(stack)
+        .ok_or_else(|| format!("No baseline found for '{}'.", stack))?;
+
+    let entries = load_history_entries(stack)?;
+
+    if entries.is_empty() {
+        return Err(format!("No baseline history entries found for '{}'.", stack));
+    }
+
+    let index = if let Some(v) = version {
+        if v == 0 || v > entries.len() {
+            return Err(format!(
+                "Version {} does not exist for '{}' (1-{} on record).",
+                v, stack, entries.len()
+            ));
+        }
+        v - 1
+    } else if let Some(ts) = to {
+        let needle = ts.replace(':', "-");
+        let matches: Vec<usize> = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.promoted_at.replace(':', "-").contains(&needle))
+            .map(|(i, _)| i)
+            .collect();
+
+        match matches.as_slice() {
+            [i] => *i,
+            []  => return Err(format!(
+                "No baseline version promoted at '{}' for '{}'.\n\
+                 Run `rehearsa baseline history --stack {}` to see available versions.",
+                ts, stack, stack
+            )),
+            _   => return Err(format!(
+                "'{}' matches {} baseline versions: {}",
+                ts,
+                matches.len(),
+                matches.iter().map(|i| format!("v{}", i + 1)).collect::<Vec<_>>().join(", "),
+            )),
+        }
+    } else {
+        if entries.len() < 2 {
+            return Err(format!(
+                "'{}' has only one baseline version — nothing to roll back to.",
+                stack
+            ));
+        }
+        entries.len() - 2
+    };
+
+    let target = &entries[index];
+
+    let baseline = StackBaseline {
+        schema_version:      CURRENT_SCHEMA_VERSION,
+        stack:               stack.to_string(),
+        expected_services:   target.expected_services.clone(),
+        expected_confidence: target.expected_confidence,
+        expected_readiness:  target.expected_readiness,
+        expected_duration:   target.expected_duration,
+        service_scores:      target.service_scores.clone(),
+        pinned_at:           target.pinned_at.clone(),
+        promoted_at:         Some(chrono::Utc::now().to_rfc3339()),
+        duration_method:     target.duration_method.clone(),
+        service_hashes:      target.service_hashes.clone(),
+        tolerance:           current.tolerance,
+    };
+
+    save_baseline(stack, &baseline)?;
+
+    let diffs = version_changes(&BaselineHistoryEntry::from(&current), target);
+
+    println!("Baseline for '{}' rolled back to v{} (promoted {}).", stack, index + 1, target.promoted_at);
+    println!("  Confidence      : {}%", baseline.expected_confidence);
+    if let Some(r) = baseline.expected_readiness {
+        println!("  Readiness       : {}%", r);
+    }
+    println!("  Duration        : {}s", baseline.expected_duration);
+    println!("  Services        : {}", baseline.expected_services.join(", "));
+    if diffs.is_empty() {
+        println!("  Δ from current  : no changes");
+    } else {
+        println!("  Δ from current  : {}", diffs.join(", "));
+    }
+
+    Ok(())
+}
+
+// ======================================================
+// EXPECTED DURATION
+// ======================================================
+
+/// Nearest-rank percentile of a set of durations. Returns None for an empty set.
+fn percentile(values: &[u64], pct: u32) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+
+    let rank = (pct as usize * sorted.len()).div_ceil(100);
+    let idx = rank.clamp(1, sorted.len()) - 1;
+
+    Some(sorted[idx])
+}
+
+/// Replace a baseline's expected duration with the given percentile over the
+/// stack's most recent `window` runs. A single pinned run is a noisy ceiling;
+/// a percentile over history is far less sensitive to one atypical run.
+pub fn apply_duration_percentile(
+    baseline: &mut StackBaseline,
+    pct: u32,
+    window: usize,
+) -> Result<(), String> {
+
+    if pct == 0 || pct > 100 {
+        return Err(format!("Percentile must be between 1 and 100 (got {}).", pct));
+    }
+
+    if window == 0 {
+        return Err("Window must be at least 1 run.".to_string());
+    }
+
+    let durations: Vec<u64> = crate::history::load_recent(&baseline.stack, window)
+        .iter()
+        .map(|r| r.duration_seconds)
+        .collect();
+
+    let value = percentile(&durations, pct).ok_or_else(|| format!(
+        "No rehearsal history found for stack '{}'. Run a rehearsal first.",
+        baseline.stack
+    ))?;
+
+    baseline.expected_duration = value;
+    baseline.duration_method = Some(DurationMethod::Percentile {
+        percentile: pct,
+        window,
+        samples: durations.len(),
+    });
+
+    Ok(())
+}
+
+/// Recompute the expected duration of an existing baseline from recent history.
+/// The result is saved as a new baseline version.
+pub fn recompute_duration(stack: &str, pct: u32, window: usize) -> Result<(), String> {
+
+    let mut baseline = load_baseline(stack).ok_or_else(|| format!(
+        "No baseline found for stack '{}'. Pin one first.",
+        stack
+    ))?;
+
+    let previous = baseline.expected_duration;
+
+    apply_duration_percentile(&mut baseline, pct, window)?;
+    baseline.promoted_at = Some(chrono::Utc::now().to_rfc3339());
+
+    save_baseline(stack, &baseline)?;
+
+    println!("Expected duration recomputed for stack '{}'.", stack);
+    println!("  Previous : {}s", previous);
+    println!(
+        "  Current  : {}s ({})",
+        baseline.expected_duration,
+        baseline.duration_method.as_ref().unwrap_or(&DurationMethod::PinnedRun),
+    );
+
+    Ok(())
+}
+
 // ======================================================
 // BASELINE HISTORY
 // ======================================================
@@ -302,8 +794,8 @@ pub fn show_all_baseline_history() -> Result<(), String> {
     println!("Baseline History — All Stacks");
     println!("{}", "─".repeat(90));
     println!(
-        "{:<20} {:<26} {:<12} {:<12} {:<12} {}",
-        "Stack", "Promoted At", "Confidence", "Readiness", "Duration", "Status"
+        "{:<20} {:<26} {:<12} {:<12} {:<12} Status",
+        "Stack", "Promoted At", "Confidence", "Readiness", "Duration"
     );
     println!("{}", "─".repeat(90));
 
@@ -330,13 +822,7 @@ pub fn show_all_baseline_history() -> Result<(), String> {
                 latest.duration_seconds,
             );
 
-            let has_drift = !drift.new_services.is_empty()
-                || !drift.missing_services.is_empty()
-                || drift.confidence_delta != 0
-                || drift.readiness_delta.unwrap_or(0) != 0
-                || drift.duration_delta_percent.unwrap_or(0) != 0;
-
-            if has_drift { "DRIFT DETECTED" } else { "CONTRACT HONOURED" }
+            if drift.severity() != DriftSeverity::None { "DRIFT DETECTED" } else { "CONTRACT HONOURED" }
         } else {
             "NO RUNS"
         };
@@ -367,6 +853,63 @@ pub fn show_all_baseline_history() -> Result<(), String> {
     Ok(())
 }
 
+/// Parsed baseline history entries for a stack, oldest first. Version N
+/// in `baseline history` output is index N-1 here.
+fn load_history_entries(stack: &str) -> Result<Vec<BaselineHistoryEntry>, String> {
+    let hist_dir = baseline_history_dir(stack)?;
+
+    if !hist_dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(&hist_dir)
+        .map_err(|e| format!("Failed to read baseline history: {}", e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .collect();
+
+    entries.sort();
+
+    Ok(entries
+        .iter()
+        .filter_map(|p| {
+            let content = fs::read_to_string(p).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect())
+}
+
+/// Human-readable changes going from baseline version `prev` to `entry`.
+fn version_changes(prev: &BaselineHistoryEntry, entry: &BaselineHistoryEntry) -> Vec<String> {
+    let mut diffs: Vec<String> = vec![];
+
+    let conf_delta = entry.expected_confidence as i32 - prev.expected_confidence as i32;
+    if conf_delta != 0 {
+        diffs.push(format!("confidence {:+}%", conf_delta));
+    }
+
+    if let (Some(cur_r), Some(prev_r)) = (entry.expected_readiness, prev.expected_readiness) {
+        let r_delta = cur_r as i32 - prev_r as i32;
+        if r_delta != 0 {
+            diffs.push(format!("readiness {:+}%", r_delta));
+        }
+    }
+
+    if prev.expected_duration > 0 {
+        let dur_delta = (entry.expected_duration as i64 - prev.expected_duration as i64)
+            * 100 / prev.expected_duration as i64;
+        if dur_delta != 0 {
+            diffs.push(format!("duration {:+}%", dur_delta));
+        }
+    }
+
+    let prev_set: HashSet<_> = prev.expected_services.iter().collect();
+    let cur_set:  HashSet<_> = entry.expected_services.iter().collect();
+    for added   in cur_set.difference(&prev_set)   { diffs.push(format!("+{}", added)); }
+    for removed in prev_set.difference(&cur_set)   { diffs.push(format!("-{}", removed)); }
+
+    diffs
+}
+
 /// Show the full baseline version history for a single stack,
 /// with a diff between each consecutive version.
 pub fn show_stack_baseline_history(stack: &str) -> Result<(), String> {
@@ -399,29 +942,16 @@ pub fn show_stack_baseline_history(stack: &str) -> Result<(), String> {
         ));
     }
 
-    let mut entries: Vec<PathBuf> = fs::read_dir(&hist_dir)
-        .map_err(|e| format!("Failed to read baseline history: {}", e))?
-        .filter_map(|e| e.ok().map(|e| e.path()))
-        .collect();
-
-    entries.sort();
+    let parsed = load_history_entries(stack)?;
 
-    if entries.is_empty() {
+    if parsed.is_empty() {
         return Err(format!("No baseline history entries found for '{}'.", stack));
     }
 
     println!();
     println!("Baseline History: {}", stack);
     println!("{}", "─".repeat(70));
-    println!("{} version{} on record.\n", entries.len(), if entries.len() == 1 { "" } else { "s" });
-
-    let parsed: Vec<BaselineHistoryEntry> = entries
-        .iter()
-        .filter_map(|p| {
-            let content = fs::read_to_string(p).ok()?;
-            serde_json::from_str(&content).ok()
-        })
-        .collect();
+    println!("{} version{} on record.\n", parsed.len(), if parsed.len() == 1 { "" } else { "s" });
 
     for (i, entry) in parsed.iter().enumerate() {
         let promoted = if entry.promoted_at.len() >= 19 {
@@ -456,33 +986,7 @@ pub fn show_stack_baseline_history(stack: &str) -> Result<(), String> {
 
         // Show diff from previous version
         if i > 0 {
-            let prev = &parsed[i - 1];
-            let mut diffs: Vec<String> = vec![];
-
-            let conf_delta = entry.expected_confidence as i32 - prev.expected_confidence as i32;
-            if conf_delta != 0 {
-                diffs.push(format!("confidence {:+}%", conf_delta));
-            }
-
-            if let (Some(cur_r), Some(prev_r)) = (entry.expected_readiness, prev.expected_readiness) {
-                let r_delta = cur_r as i32 - prev_r as i32;
-                if r_delta != 0 {
-                    diffs.push(format!("readiness {:+}%", r_delta));
-                }
-            }
-
-            if prev.expected_duration > 0 {
-                let dur_delta = (entry.expected_duration as i64 - prev.expected_duration as i64)
-                    * 100 / prev.expected_duration as i64;
-                if dur_delta != 0 {
-                    diffs.push(format!("duration {:+}%", dur_delta));
-                }
-            }
-
-            let prev_set: HashSet<_> = prev.expected_services.iter().collect();
-            let cur_set:  HashSet<_> = entry.expected_services.iter().collect();
-            for added   in cur_set.difference(&prev_set)   { diffs.push(format!("+{}", added)); }
-            for removed in prev_set.difference(&cur_set)   { diffs.push(format!("-{}", removed)); }
+            let diffs = version_changes(&parsed[i - 1], entry);
 
             if diffs.is_empty() {
                 println!("    Δ from v{}: no changes", i);
@@ -565,5 +1069,66 @@ pub fn compare_to_baseline(
         confidence_delta,
         readiness_delta,
         duration_delta_percent,
+        tolerance: baseline.tolerance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drift(confidence: i32, readiness: i32, duration_pct: i32, tolerance: DriftTolerance) -> BaselineDrift {
+        BaselineDrift {
+            new_services: Vec::new(),
+            missing_services: Vec::new(),
+            confidence_delta: confidence,
+            readiness_delta: Some(readiness),
+            duration_delta_percent: Some(duration_pct),
+            tolerance,
+        }
+    }
+
+    #[test]
+    fn untuned_baseline_ignores_ordinary_jitter() {
+        let untuned = DriftTolerance::default();
+
+        assert_eq!(drift(-5, -5, 20, untuned).severity(), DriftSeverity::None);
+        assert_eq!(drift(0, 0, 1, untuned).severity(), DriftSeverity::None);
+        assert_eq!(drift(-6, 0, 0, untuned).severity(), DriftSeverity::Minor);
+        assert_eq!(drift(0, 0, 21, untuned).severity(), DriftSeverity::Minor);
+    }
+
+    #[test]
+    fn improvements_are_not_drift() {
+        let exact = DriftTolerance {
+            confidence_tolerance:   Some(0),
+            readiness_tolerance:    Some(0),
+            duration_tolerance_pct: Some(0),
+        };
+
+        assert_eq!(drift(15, 20, -60, exact).severity(), DriftSeverity::None);
+        assert_eq!(drift(15, 0, 0, DriftTolerance::default()).severity(), DriftSeverity::None);
+    }
+
+    #[test]
+    fn structural_change_is_major_even_when_scores_improve() {
+        let mut d = drift(10, 10, -50, DriftTolerance::default());
+        d.missing_services.push("worker".to_string());
+
+        assert_eq!(d.severity(), DriftSeverity::Major);
+    }
+
+    #[test]
+    fn zero_tolerance_counts_any_regression() {
+        let exact = DriftTolerance {
+            confidence_tolerance:   Some(0),
+            readiness_tolerance:    Some(0),
+            duration_tolerance_pct: Some(0),
+        };
+
+        assert_eq!(drift(0, 0, 0, exact).severity(), DriftSeverity::None);
+        assert_eq!(drift(-1, 0, 0, exact).severity(), DriftSeverity::Minor);
+        assert_eq!(drift(0, -1, 0, exact).severity(), DriftSeverity::Minor);
+        assert_eq!(drift(0, 0, 1, exact).severity(), DriftSeverity::Minor);
     }
 }
@@ -0,0 +1,133 @@
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+// ======================================================
+// CLI DEFAULTS
+// ======================================================
+//
+// ~/.rehearsa/config.toml supplies defaults for the top-level flags, so a
+// team can agree on --timeout or --pull once instead of retyping them.
+// Precedence: command-line flag > environment variable > this file >
+// built-in default. Not to be confused with the daemon's
+// /etc/rehearsa/config.json. --inject-failure is deliberately absent:
+// a persisted fault injection would quietly fail every rehearsal.
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct CliDefaults {
+    pub json:             Option<bool>,
+    pub format:           Option<String>,
+    pub ci:               Option<bool>,
+    pub timeout:          Option<u64>,
+    pub profiles:         Option<Vec<String>>,
+    pub explain_policy:   Option<bool>,
+    pub strict_integrity: Option<bool>,
+    pub pull:             Option<String>,
+    pub image_archive:    Option<String>,
+    pub docker_host:      Option<String>,
+    pub quiet:            Option<bool>,
+    pub verbose:          Option<bool>,
+}
+
+const TEMPLATE: &str = r#"# Rehearsa CLI defaults.
+#
+# Each key supplies a default for the top-level flag of the same name.
+# Precedence: command-line flag > environment variable > this file > built-in default.
+# Uncomment a line to set it.
+
+# Seconds each service gets to become healthy (--timeout).
+# timeout = 30
+
+# Image pull policy: always | if-missing | never (--pull).
+# pull = "if-missing"
+
+# Refuse to run when the stack's run history fails its per-record hash check (--strict-integrity).
+# strict_integrity = false
+
+# Print every policy rule with its threshold after `stack test` (--explain-policy).
+# explain_policy = false
+
+# Directory of `docker save` archives to load before rehearsing (--image-archive).
+# image_archive = "/srv/rehearsa/images"
+
+# Container engine API instead of the local Docker socket (--docker-host),
+# e.g. a Podman socket.
+# docker_host = "unix:///run/podman/podman.sock"
+
+# Compose profiles to activate (--profile).
+# profiles = ["backup"]
+
+# Human output detail (--quiet, --verbose); quiet wins if both are true.
+# quiet = false
+# verbose = false
+
+# Output for status, coverage and stack test: text | json | yaml (--format).
+# format = "text"
+
+# Machine-readable output (--json, --ci).
+# json = false
+# ci = false
+"#;
+
+fn config_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+    Ok(home.join(".rehearsa").join("config.toml"))
+}
+
+/// Defaults from ~/.rehearsa/config.toml; empty when the file is absent.
+pub fn load_cli_defaults() -> Result<CliDefaults, String> {
+    let path = config_path()?;
+    let raw = match fs::read_to_string(&path) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(CliDefaults::default()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    let defaults: CliDefaults = toml::from_str(&raw)
+        .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+
+    if let Some(pull) = &defaults.pull {
+        if !matches!(pull.as_str(), "always" | "if-missing" | "never") {
+            return Err(format!(
+                "Invalid {}: pull must be always, if-missing or never (got '{}')",
+                path.display(), pull
+            ));
+        }
+    }
+
+    if let Some(format) = &defaults.format {
+        if !matches!(format.as_str(), "text" | "json" | "yaml") {
+            return Err(format!(
+                "Invalid {}: format must be text, json or yaml (got '{}')",
+                path.display(), format
+            ));
+        }
+    }
+
+    Ok(defaults)
+}
+
+/// Write the commented template. Refuses to overwrite unless `force`.
+pub fn init_config(force: bool) -> Result<(), String> {
+    let path = config_path()?;
+
+    if path.exists() && !force {
+        return Err(format!(
+            "{} already exists. Pass --force to overwrite it.",
+            path.display()
+        ));
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    crate::fsutil::write_atomic(&path, TEMPLATE.as_bytes(), None)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    println!("Wrote {}", path.display());
+    println!("Uncomment the defaults you want; command-line flags always win.");
+    Ok(())
+}
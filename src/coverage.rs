@@ -1,8 +1,20 @@
 use serde::{Serialize, Deserialize};
+use std::fs;
+use std::path::PathBuf;
 
-use crate::baseline::{load_baseline, compare_to_baseline};
+use crate::baseline::{load_baseline, compare_to_baseline, DriftSeverity};
 use crate::daemon::load_registry;
-use crate::history::load_latest;
+use crate::history::{load_latest, sparkline};
+use crate::output::{render, OutputFormat};
+
+/// Badge colour bands: at or above GREEN is green, at or above AMBER is amber.
+const BADGE_GREEN_PCT: u32 = 90;
+const BADGE_AMBER_PCT: u32 = 70;
+
+/// One JSON snapshot per line under ~/.rehearsa.
+const COVERAGE_HISTORY_FILE: &str = "coverage-history.jsonl";
+/// Oldest snapshots are dropped beyond this many.
+const MAX_COVERAGE_SNAPSHOTS: usize = 1000;
 
 // ======================================================
 // DATA MODEL
@@ -24,6 +36,8 @@ pub struct StackCoverage {
     pub confidence:      Option<u32>,
     /// Latest readiness score. None if no history.
     pub readiness:       Option<u32>,
+    /// Severity of the latest run's drift from baseline. None without a baseline or history.
+    pub drift_severity:  Option<DriftSeverity>,
 }
 
 /// Fleet-wide coverage summary.
@@ -34,12 +48,22 @@ pub struct CoverageSummary {
     pub with_baseline:         usize,
     /// Stacks with a baseline AND currently honouring it.
     pub honouring_contract:    usize,
+    /// Stacks with a baseline whose latest run drifted from it.
+    pub drifting:              usize,
     /// Stacks with history but no baseline — running blind.
     pub uncontracted:          usize,
     /// Stacks with no rehearsal history at all.
     pub never_rehearsed:       usize,
     /// 0–100: percentage of watched stacks honouring their contract.
     pub coverage_pct:          u32,
+    /// Gate threshold: coverage below this fails.
+    #[serde(default)]
+    pub min_coverage_pct:      u32,
+    /// Whether any drifting stack also fails the gate.
+    #[serde(default)]
+    pub fail_on_drift:         bool,
+    #[serde(default)]
+    pub gate_passed:           bool,
     pub stacks:                Vec<StackCoverage>,
 }
 
@@ -52,15 +76,21 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
     let watches = &registry.watches;
 
     if watches.is_empty() {
-        return Ok(CoverageSummary {
+        let mut summary = CoverageSummary {
             total_watched:      0,
             with_baseline:      0,
             honouring_contract: 0,
+            drifting:           0,
             uncontracted:       0,
             never_rehearsed:    0,
             coverage_pct:       0,
+            min_coverage_pct:   0,
+            fail_on_drift:      false,
+            gate_passed:        false,
             stacks:             vec![],
-        });
+        };
+        summary.apply_gate(crate::daemon::resolve_config().min_coverage_pct.value, false);
+        return Ok(summary);
     }
 
     let mut stacks: Vec<StackCoverage> = Vec::new();
@@ -74,16 +104,18 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
         let has_history  = latest.is_some();
         let has_baseline = baseline.is_some();
 
-        let (status, confidence, readiness) = match (&latest, &baseline) {
+        let (status, confidence, readiness, drift_severity) = match (&latest, &baseline) {
             (None, _) => (
                 "NO_RUNS".to_string(),
                 None,
                 None,
+                None,
             ),
             (Some(run), None) => (
                 "NO_BASELINE".to_string(),
                 Some(run.confidence),
                 run.readiness,
+                None,
             ),
             (Some(run), Some(bl)) => {
                 let drift = compare_to_baseline(
@@ -94,11 +126,8 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
                     run.duration_seconds,
                 );
 
-                let has_drift = !drift.new_services.is_empty()
-                    || !drift.missing_services.is_empty()
-                    || drift.confidence_delta < -5        // tolerate tiny float noise
-                    || drift.readiness_delta.unwrap_or(0) < -5
-                    || drift.duration_delta_percent.unwrap_or(0).abs() > 20;
+                // Jitter within the baseline's tolerances is not drift
+                let has_drift = drift.severity() != DriftSeverity::None;
 
                 let status = if has_drift {
                     "DRIFT_DETECTED".to_string()
@@ -106,7 +135,7 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
                     "CONTRACT_HONOURED".to_string()
                 };
 
-                (status, Some(run.confidence), run.readiness)
+                (status, Some(run.confidence), run.readiness, Some(drift.severity()))
             }
         };
 
@@ -118,6 +147,7 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
             status,
             confidence,
             readiness,
+            drift_severity,
         });
     }
 
@@ -128,14 +158,13 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
     let total_watched      = stacks.len();
     let with_baseline      = stacks.iter().filter(|s| s.has_baseline).count();
     let honouring_contract = stacks.iter().filter(|s| s.status == "CONTRACT_HONOURED").count();
+    let drifting           = stacks.iter().filter(|s| s.status == "DRIFT_DETECTED").count();
     let uncontracted       = stacks.iter().filter(|s| s.has_history && !s.has_baseline).count();
     let never_rehearsed    = stacks.iter().filter(|s| !s.has_history).count();
 
-    let coverage_pct = if total_watched > 0 {
-        ((honouring_contract * 100) / total_watched) as u32
-    } else {
-        0
-    };
+    let coverage_pct = (honouring_contract * 100)
+        .checked_div(total_watched)
+        .unwrap_or(0) as u32;
 
     // Sort: honouring first, then drift, then no baseline, then no runs
     stacks.sort_by_key(|s| match s.status.as_str() {
@@ -145,15 +174,32 @@ pub fn build_coverage() -> Result<CoverageSummary, String> {
         _                   => 3,
     });
 
-    Ok(CoverageSummary {
+    let mut summary = CoverageSummary {
         total_watched,
         with_baseline,
         honouring_contract,
+        drifting,
         uncontracted,
         never_rehearsed,
         coverage_pct,
+        min_coverage_pct: 0,
+        fail_on_drift:    false,
+        gate_passed:      false,
         stacks,
-    })
+    };
+    summary.apply_gate(crate::daemon::resolve_config().min_coverage_pct.value, false);
+    Ok(summary)
+}
+
+impl CoverageSummary {
+    /// Evaluate the CI gate: coverage must reach `min_pct`, and with
+    /// `fail_on_drift` no stack may be DRIFT_DETECTED.
+    pub fn apply_gate(&mut self, min_pct: u32, fail_on_drift: bool) {
+        self.min_coverage_pct = min_pct;
+        self.fail_on_drift    = fail_on_drift;
+        self.gate_passed      = self.coverage_pct >= min_pct
+            && !(fail_on_drift && self.drifting > 0);
+    }
 }
 
 // ======================================================
@@ -223,7 +269,11 @@ pub fn print_coverage(summary: &CoverageSummary) {
 
         let status_icon = match s.status.as_str() {
             "CONTRACT_HONOURED" => "✓  CONTRACT HONOURED",
-            "DRIFT_DETECTED"    => "⚠  DRIFT DETECTED",
+            "DRIFT_DETECTED"    => match s.drift_severity {
+                Some(DriftSeverity::Major) => "⚠  MAJOR DRIFT",
+                Some(DriftSeverity::Minor) => "⚠  MINOR DRIFT",
+                _                          => "⚠  DRIFT DETECTED",
+            },
             "NO_BASELINE"       => "·  NO BASELINE",
             "NO_RUNS"           => "✗  NEVER REHEARSED",
             _                   => &s.status,
@@ -248,6 +298,21 @@ pub fn print_coverage(summary: &CoverageSummary) {
         println!("  All contracts are honoured.");
     }
 
+    // ── Gate ─────────────────────────────────────
+    println!();
+    let mut reasons = vec![];
+    if summary.coverage_pct < summary.min_coverage_pct {
+        reasons.push(format!("coverage {}% < {}% minimum", summary.coverage_pct, summary.min_coverage_pct));
+    }
+    if summary.fail_on_drift && summary.drifting > 0 {
+        reasons.push(format!("{} stack(s) drifting", summary.drifting));
+    }
+    if summary.gate_passed {
+        println!("  Gate: PASS  (coverage {}% ≥ {}% minimum)", summary.coverage_pct, summary.min_coverage_pct);
+    } else {
+        println!("  Gate: FAIL  ({})", reasons.join("; "));
+    }
+
     println!();
 }
 
@@ -259,12 +324,292 @@ fn coverage_bar(pct: u32) -> String {
 }
 
 // ======================================================
-// JSON OUTPUT
+// STRUCTURED OUTPUT
+// ======================================================
+
+pub fn print_coverage_structured(summary: &CoverageSummary, format: OutputFormat) -> Result<(), String> {
+    println!("{}", render(summary, format)?);
+    Ok(())
+}
+
+// ======================================================
+// ROLLUP (status --summary)
 // ======================================================
 
-pub fn print_coverage_json(summary: &CoverageSummary) -> Result<(), String> {
-    let json = serde_json::to_string_pretty(summary)
-        .map_err(|e| format!("JSON error: {}", e))?;
-    println!("{}", json);
+/// Fleet counters only — the per-stack list is left out.
+#[derive(Debug, Serialize)]
+pub struct CoverageRollup {
+    pub watched:         usize,
+    pub honouring:       usize,
+    pub drifting:        usize,
+    pub unbaselined:     usize,
+    pub never_rehearsed: usize,
+    pub coverage_pct:    u32,
+}
+
+impl CoverageSummary {
+    pub fn rollup(&self) -> CoverageRollup {
+        CoverageRollup {
+            watched:         self.total_watched,
+            honouring:       self.honouring_contract,
+            drifting:        self.drifting,
+            unbaselined:     self.uncontracted,
+            never_rehearsed: self.never_rehearsed,
+            coverage_pct:    self.coverage_pct,
+        }
+    }
+}
+
+pub fn print_coverage_rollup(summary: &CoverageSummary) {
+    let r = summary.rollup();
+    println!(
+        "{} watched, {} honouring, {} drifting, {} unbaselined, {} never rehearsed, coverage {}%",
+        r.watched, r.honouring, r.drifting, r.unbaselined, r.never_rehearsed, r.coverage_pct
+    );
+}
+
+pub fn print_coverage_rollup_structured(summary: &CoverageSummary, format: OutputFormat) -> Result<(), String> {
+    println!("{}", render(&summary.rollup(), format)?);
     Ok(())
 }
+
+// ======================================================
+// TREND
+// ======================================================
+//
+// Each `rehearsa coverage` run appends its fleet counters to
+// ~/.rehearsa/coverage-history.jsonl, capped at MAX_COVERAGE_SNAPSHOTS.
+
+/// Aggregate counters of one coverage run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CoverageSnapshot {
+    pub timestamp:          String,
+    pub total_watched:      usize,
+    pub with_baseline:      usize,
+    pub honouring_contract: usize,
+    pub drifting:           usize,
+    pub uncontracted:       usize,
+    pub never_rehearsed:    usize,
+    pub coverage_pct:       u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CoverageTrend {
+    /// Change in coverage_pct from the first to the last snapshot shown.
+    pub delta:     i32,
+    /// Oldest first.
+    pub snapshots: Vec<CoverageSnapshot>,
+}
+
+fn coverage_history_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+
+    Ok(home.join(".rehearsa").join(COVERAGE_HISTORY_FILE))
+}
+
+/// All recorded snapshots, oldest first. Unparseable lines are skipped.
+fn load_snapshots() -> Result<Vec<CoverageSnapshot>, String> {
+    let path = coverage_history_path()?;
+
+    let content = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Append this summary's counters to the coverage history. An empty fleet
+/// is not recorded.
+pub fn record_snapshot(summary: &CoverageSummary) -> Result<(), String> {
+    if summary.total_watched == 0 {
+        return Ok(());
+    }
+
+    let mut snapshots = load_snapshots()?;
+    snapshots.push(CoverageSnapshot {
+        timestamp:          chrono::Utc::now().to_rfc3339(),
+        total_watched:      summary.total_watched,
+        with_baseline:      summary.with_baseline,
+        honouring_contract: summary.honouring_contract,
+        drifting:           summary.drifting,
+        uncontracted:       summary.uncontracted,
+        never_rehearsed:    summary.never_rehearsed,
+        coverage_pct:       summary.coverage_pct,
+    });
+
+    let excess = snapshots.len().saturating_sub(MAX_COVERAGE_SNAPSHOTS);
+    snapshots.drain(..excess);
+
+    let mut out = String::new();
+    for snapshot in &snapshots {
+        let line = serde_json::to_string(snapshot)
+            .map_err(|e| format!("Failed to serialize coverage snapshot: {}", e))?;
+        out.push_str(&line);
+        out.push('\n');
+    }
+
+    let path = coverage_history_path()?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    crate::fsutil::write_atomic(&path, out.as_bytes(), None)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// The last `last` snapshots, oldest first.
+pub fn coverage_trend(last: usize) -> Result<CoverageTrend, String> {
+    let mut snapshots = load_snapshots()?;
+    let skip = snapshots.len().saturating_sub(last);
+    snapshots.drain(..skip);
+
+    let delta = match (snapshots.first(), snapshots.last()) {
+        (Some(first), Some(latest)) => latest.coverage_pct as i32 - first.coverage_pct as i32,
+        _ => 0,
+    };
+
+    Ok(CoverageTrend { delta, snapshots })
+}
+
+pub fn print_coverage_trend(trend: &CoverageTrend) {
+    let (first, latest) = match (trend.snapshots.first(), trend.snapshots.last()) {
+        (Some(f), Some(l)) => (f, l),
+        _ => {
+            println!("No coverage history yet. Each `rehearsa coverage` run records a snapshot.");
+            return;
+        }
+    };
+
+    let short = |ts: &str| ts.get(..16).unwrap_or(ts).replace('T', " ");
+    let scores: Vec<Option<u32>> = trend.snapshots.iter().map(|s| Some(s.coverage_pct)).collect();
+
+    println!();
+    println!("Coverage Trend — last {} snapshot(s), oldest first", trend.snapshots.len());
+    println!("{}", "─".repeat(60));
+    println!(
+        "  {}  {}%  ({:+}% since {})",
+        sparkline(&scores),
+        latest.coverage_pct,
+        trend.delta,
+        short(&first.timestamp),
+    );
+    println!();
+    println!("{:<18} {:>8} {:>10} {:>9}", "Timestamp", "Coverage", "Honouring", "Drifting");
+    println!("{}", "─".repeat(48));
+    for s in &trend.snapshots {
+        println!(
+            "{:<18} {:>7}% {:>10} {:>9}",
+            short(&s.timestamp),
+            s.coverage_pct,
+            format!("{}/{}", s.honouring_contract, s.total_watched),
+            s.drifting,
+        );
+    }
+    println!();
+}
+
+// ======================================================
+// BADGE
+// ======================================================
+//
+// Shields-style flat badge, built by hand. Text widths are estimated
+// (~7px per character at 11px Verdana), which is close enough for the
+// short strings a badge carries.
+
+pub fn render_badge(summary: &CoverageSummary, label: &str) -> String {
+    let value = format!("{}%", summary.coverage_pct);
+
+    let color = if summary.coverage_pct >= BADGE_GREEN_PCT {
+        "#4c1"
+    } else if summary.coverage_pct >= BADGE_AMBER_PCT {
+        "#dfb317"
+    } else {
+        "#e05d44"
+    };
+
+    let text_width = |s: &str| s.chars().count() * 7 + 10;
+    let lw = text_width(label);
+    let vw = text_width(&value);
+    let width = lw + vw;
+
+    let label = xml_escape(label);
+
+    format!(
+        concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"20\" role=\"img\" aria-label=\"{label}: {value}\">",
+            "<title>{label}: {value}</title>",
+            "<linearGradient id=\"s\" x2=\"0\" y2=\"100%\"><stop offset=\"0\" stop-color=\"#bbb\" stop-opacity=\".1\"/><stop offset=\"1\" stop-opacity=\".1\"/></linearGradient>",
+            "<clipPath id=\"r\"><rect width=\"{w}\" height=\"20\" rx=\"3\" fill=\"#fff\"/></clipPath>",
+            "<g clip-path=\"url(#r)\"><rect width=\"{lw}\" height=\"20\" fill=\"#555\"/><rect x=\"{lw}\" width=\"{vw}\" height=\"20\" fill=\"{color}\"/><rect width=\"{w}\" height=\"20\" fill=\"url(#s)\"/></g>",
+            "<g fill=\"#fff\" text-anchor=\"middle\" font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">",
+            "<text x=\"{lx}\" y=\"15\" fill=\"#010101\" fill-opacity=\".3\">{label}</text><text x=\"{lx}\" y=\"14\">{label}</text>",
+            "<text x=\"{vx}\" y=\"15\" fill=\"#010101\" fill-opacity=\".3\">{value}</text><text x=\"{vx}\" y=\"14\">{value}</text>",
+            "</g></svg>\n",
+        ),
+        w = width,
+        lw = lw,
+        vw = vw,
+        lx = lw / 2,
+        vx = lw + vw / 2,
+        color = color,
+        label = label,
+        value = value,
+    )
+}
+
+pub fn write_badge(summary: &CoverageSummary, label: &str, output: &str) -> Result<(), String> {
+    fs::write(output, render_badge(summary, label))
+        .map_err(|e| format!("Failed to write {}: {}", output, e))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ======================================================
+// CSV OUTPUT
+// ======================================================
+
+/// One row per stack, then a TOTAL row with the fleet counters.
+/// Missing scores are left empty so spreadsheets treat them as blank.
+pub fn print_coverage_csv(summary: &CoverageSummary) {
+    use crate::csv::row;
+
+    println!("{}", row(&[
+        "stack", "watched", "has_baseline", "has_history", "status", "confidence", "readiness",
+        "drift_severity",
+    ]));
+
+    for s in &summary.stacks {
+        println!("{}", row(&[
+            s.stack.clone(),
+            s.watched.to_string(),
+            s.has_baseline.to_string(),
+            s.has_history.to_string(),
+            s.status.clone(),
+            s.confidence.map(|c| c.to_string()).unwrap_or_default(),
+            s.readiness.map(|r| r.to_string()).unwrap_or_default(),
+            s.drift_severity.map(|d| d.to_string()).unwrap_or_default(),
+        ]));
+    }
+
+    println!("{}", row(&[
+        "TOTAL".to_string(),
+        summary.total_watched.to_string(),
+        summary.with_baseline.to_string(),
+        (summary.total_watched - summary.never_rehearsed).to_string(),
+        format!("{}% honouring contract", summary.coverage_pct),
+        String::new(),
+        String::new(),
+        String::new(),
+    ]));
+}
@@ -0,0 +1,24 @@
+// ======================================================
+// CSV OUTPUT
+// ======================================================
+//
+// Minimal RFC 4180 writer shared by the CSV exporters. Fields are
+// quoted only when they contain a comma, quote or line break.
+
+/// Quote a single field if needed, doubling any embedded quotes.
+pub fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into one CSV line (no trailing newline).
+pub fn row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|f| escape(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
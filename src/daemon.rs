@@ -1,11 +1,13 @@
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
-use tokio::sync::Semaphore;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use chrono::Utc;
+use crate::history::StabilityWeighting;
 
 // ======================================================
 // DAEMON CONFIG
@@ -13,6 +15,56 @@ use chrono::Utc;
 
 const CONFIG_PATH: &str = "/etc/rehearsa/config.json";
 const DEFAULT_MAX_CONCURRENT: usize = 1;
+const DEFAULT_LOCK_DIR: &str = "/var/lock/rehearsa";
+/// Write-often daemon state. Defaults to the config directory; point it at
+/// a writable path (e.g. /var/lib/rehearsa) when /etc is read-only.
+const DEFAULT_STATE_DIR: &str = "/etc/rehearsa";
+/// A lock held by a live process longer than this is reported as stuck.
+const DEFAULT_MAX_LOCK_AGE_MINUTES: u64 = 180;
+/// 0 = award the no-healthcheck score the moment a container is RUNNING.
+const DEFAULT_RUNNING_PROBE_SECONDS: u64 = 0;
+/// Repeat (stack, event) notifications inside this window are suppressed.
+const DEFAULT_NOTIFY_DEDUP_MINUTES: u64 = 30;
+/// `rehearsa coverage` fails below this percentage.
+const DEFAULT_MIN_COVERAGE_PCT: u32 = 100;
+/// Quiet period after a Compose file change before it triggers a rehearsal.
+const DEFAULT_WATCH_DEBOUNCE_SECONDS: u64 = 2;
+/// Upper bound of the random delay before a scheduled rehearsal (0 = none).
+const DEFAULT_SCHEDULE_JITTER_SECONDS: u64 = 0;
+/// Listen address of the health/metrics endpoint ("off" = disabled).
+const DEFAULT_METRICS_ADDR: &str = "off";
+
+/// How the daemon writes its log lines.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// `[timestamp] message` lines.
+    #[default]
+    Human,
+    /// One JSON object per event: ts, level, stack, event, msg.
+    Json,
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Human => write!(f, "human"),
+            LogFormat::Json  => write!(f, "json"),
+        }
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "human" => Ok(LogFormat::Human),
+            "json"  => Ok(LogFormat::Json),
+            other   => Err(format!("Unknown log format '{}'. Expected human or json.", other)),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct DaemonConfig {
@@ -20,6 +72,81 @@ pub struct DaemonConfig {
     /// Override via REHEARSA_MAX_CONCURRENT env var or `rehearsa daemon set-concurrency`.
     #[serde(default)]
     pub max_concurrent_rehearsals: Option<usize>,
+
+    /// How runs are weighted when computing stability (flat | linear | exponential).
+    /// Override via REHEARSA_STABILITY_WEIGHTING env var or `rehearsa daemon set-stability-weighting`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability_weighting: Option<StabilityWeighting>,
+
+    /// Directory for per-stack lock files.
+    /// Override via REHEARSA_LOCK_DIR env var or `rehearsa daemon set-lock-dir`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_dir: Option<String>,
+
+    /// Directory for mutable daemon state (scheduler state). Config files stay in /etc/rehearsa.
+    /// Override via REHEARSA_STATE_DIR env var or `rehearsa daemon set-state-dir`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_dir: Option<String>,
+
+    /// Minutes a live rehearsal may hold its lock before it is reported as stuck (0 disables).
+    /// Override via REHEARSA_MAX_LOCK_AGE_MINUTES env var or `rehearsa daemon set-lock-timeout`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_lock_age_minutes: Option<u64>,
+
+    /// Seconds a service without a healthcheck must stay RUNNING before it is scored (0 = immediately).
+    /// Override via REHEARSA_RUNNING_PROBE_SECONDS env var or `rehearsa daemon set-running-probe`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running_probe_seconds: Option<u64>,
+
+    /// Minutes during which a repeated (stack, event) notification is suppressed (0 disables).
+    /// Override via REHEARSA_NOTIFY_DEDUP_MINUTES env var or `rehearsa daemon set-notify-dedup`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify_dedup_minutes: Option<u64>,
+
+    /// Coverage percentage below which `rehearsa coverage` exits non-zero (0–100).
+    /// Override via REHEARSA_MIN_COVERAGE env var, `--min-coverage`, or `rehearsa daemon set-min-coverage`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_coverage_pct: Option<u32>,
+
+    /// Seconds a watched Compose file must be quiet before a change triggers a rehearsal (0 = immediately).
+    /// Override via REHEARSA_WATCH_DEBOUNCE_SECONDS env var or `rehearsa daemon set-watch-debounce`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub watch_debounce_seconds: Option<u64>,
+
+    /// Scheduled rehearsals wait a random 0..N seconds before starting (0 disables). A watch's --jitter wins.
+    /// Override via REHEARSA_SCHEDULE_JITTER_SECONDS env var or `rehearsa daemon set-schedule-jitter`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule_jitter_seconds: Option<u64>,
+
+    /// Named concurrency groups and how many of their stacks may rehearse at once.
+    /// Watches join one with `--group`; set via `rehearsa daemon set-group`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub concurrency_groups: BTreeMap<String, usize>,
+
+    /// Address (host:port) on which the daemon serves /healthz and /metrics. Disabled when unset.
+    /// Override via REHEARSA_METRICS_ADDR env var or `rehearsa daemon set-metrics-addr`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_addr: Option<String>,
+
+    /// Daemon log output: human (default) or json.
+    /// Override via REHEARSA_LOG_FORMAT env var or `rehearsa daemon set-log-format`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_format: Option<LogFormat>,
+
+    /// While set, the daemon triggers no rehearsals. Set via `rehearsa daemon pause` / `resume`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub paused: bool,
+
+    /// When the daemon was paused (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paused_since: Option<String>,
+}
+
+impl DaemonConfig {
+    /// When paused, the time it was paused (or "unknown").
+    pub fn paused_since(&self) -> Option<&str> {
+        self.paused.then(|| self.paused_since.as_deref().unwrap_or("unknown"))
+    }
 }
 
 pub fn load_config() -> DaemonConfig {
@@ -46,30 +173,238 @@ Try running with sudo.", e))?;
     Ok(())
 }
 
-/// Resolve the concurrency limit using three-tier precedence:
-/// 1. REHEARSA_MAX_CONCURRENT env var
+// ======================================================
+// RESOLVED CONFIG
+// ======================================================
+
+/// Where a resolved daemon setting came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigSource {
+    /// Named environment variable.
+    Env(&'static str),
+    /// /etc/rehearsa/config.json
+    ConfigFile,
+    /// Built-in default.
+    Default,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigSource::Env(var)   => write!(f, "{} env var", var),
+            ConfigSource::ConfigFile => write!(f, "{}", CONFIG_PATH),
+            ConfigSource::Default    => write!(f, "default"),
+        }
+    }
+}
+
+/// A resolved setting value together with its source.
+#[derive(Debug, Clone)]
+pub struct Resolved<T> {
+    pub value:  T,
+    pub source: ConfigSource,
+}
+
+/// Resolve a single setting using three-tier precedence:
+/// 1. Environment variable
 /// 2. /etc/rehearsa/config.json
-/// 3. DEFAULT_MAX_CONCURRENT (1)
-pub fn resolve_concurrency() -> usize {
+/// 3. Built-in default
+///
+/// Values that parse but fail `valid` fall through to the next tier.
+fn resolve_setting<T: std::str::FromStr + Clone>(
+    env_var:    &'static str,
+    file_value: Option<T>,
+    default:    T,
+    valid:      fn(&T) -> bool,
+) -> Resolved<T> {
     // Tier 1: environment variable
-    if let Ok(val) = std::env::var("REHEARSA_MAX_CONCURRENT") {
-        if let Ok(n) = val.trim().parse::<usize>() {
-            if n > 0 {
-                return n;
+    if let Ok(raw) = std::env::var(env_var) {
+        if let Ok(v) = raw.trim().parse::<T>() {
+            if valid(&v) {
+                return Resolved { value: v, source: ConfigSource::Env(env_var) };
             }
         }
     }
 
     // Tier 2: config file
-    let config = load_config();
-    if let Some(n) = config.max_concurrent_rehearsals {
-        if n > 0 {
-            return n;
+    if let Some(v) = file_value {
+        if valid(&v) {
+            return Resolved { value: v, source: ConfigSource::ConfigFile };
         }
     }
 
     // Tier 3: default
-    DEFAULT_MAX_CONCURRENT
+    Resolved { value: default, source: ConfigSource::Default }
+}
+
+/// Every daemon setting, fully resolved.
+#[derive(Debug, Clone)]
+pub struct ResolvedConfig {
+    pub max_concurrent_rehearsals: Resolved<usize>,
+    pub stability_weighting:       Resolved<StabilityWeighting>,
+    pub lock_dir:                  Resolved<String>,
+    pub state_dir:                 Resolved<String>,
+    pub max_lock_age_minutes:      Resolved<u64>,
+    pub running_probe_seconds:     Resolved<u64>,
+    pub notify_dedup_minutes:      Resolved<u64>,
+    pub min_coverage_pct:          Resolved<u32>,
+    pub watch_debounce_seconds:    Resolved<u64>,
+    pub schedule_jitter_seconds:   Resolved<u64>,
+    pub metrics_addr:              Resolved<String>,
+    pub log_format:                Resolved<LogFormat>,
+}
+
+impl ResolvedConfig {
+    /// (setting, resolved value, source) rows for display.
+    pub fn rows(&self) -> Vec<(&'static str, String, ConfigSource)> {
+        vec![
+            (
+                "max_concurrent_rehearsals",
+                self.max_concurrent_rehearsals.value.to_string(),
+                self.max_concurrent_rehearsals.source.clone(),
+            ),
+            (
+                "stability_weighting",
+                self.stability_weighting.value.to_string(),
+                self.stability_weighting.source.clone(),
+            ),
+            (
+                "lock_dir",
+                self.lock_dir.value.clone(),
+                self.lock_dir.source.clone(),
+            ),
+            (
+                "state_dir",
+                self.state_dir.value.clone(),
+                self.state_dir.source.clone(),
+            ),
+            (
+                "max_lock_age_minutes",
+                self.max_lock_age_minutes.value.to_string(),
+                self.max_lock_age_minutes.source.clone(),
+            ),
+            (
+                "running_probe_seconds",
+                self.running_probe_seconds.value.to_string(),
+                self.running_probe_seconds.source.clone(),
+            ),
+            (
+                "notify_dedup_minutes",
+                self.notify_dedup_minutes.value.to_string(),
+                self.notify_dedup_minutes.source.clone(),
+            ),
+            (
+                "min_coverage_pct",
+                self.min_coverage_pct.value.to_string(),
+                self.min_coverage_pct.source.clone(),
+            ),
+            (
+                "watch_debounce_seconds",
+                self.watch_debounce_seconds.value.to_string(),
+                self.watch_debounce_seconds.source.clone(),
+            ),
+            (
+                "schedule_jitter_seconds",
+                self.schedule_jitter_seconds.value.to_string(),
+                self.schedule_jitter_seconds.source.clone(),
+            ),
+            (
+                "metrics_addr",
+                self.metrics_addr.value.clone(),
+                self.metrics_addr.source.clone(),
+            ),
+            (
+                "log_format",
+                self.log_format.value.to_string(),
+                self.log_format.source.clone(),
+            ),
+        ]
+    }
+}
+
+/// Resolve every daemon setting against the environment and config file.
+pub fn resolve_config() -> ResolvedConfig {
+    let config = load_config();
+
+    ResolvedConfig {
+        max_concurrent_rehearsals: resolve_setting(
+            "REHEARSA_MAX_CONCURRENT",
+            config.max_concurrent_rehearsals,
+            DEFAULT_MAX_CONCURRENT,
+            |n| *n > 0,
+        ),
+        stability_weighting: resolve_setting(
+            "REHEARSA_STABILITY_WEIGHTING",
+            config.stability_weighting,
+            StabilityWeighting::default(),
+            |_| true,
+        ),
+        lock_dir: resolve_setting(
+            "REHEARSA_LOCK_DIR",
+            config.lock_dir,
+            DEFAULT_LOCK_DIR.to_string(),
+            |d| !d.trim().is_empty(),
+        ),
+        state_dir: resolve_setting(
+            "REHEARSA_STATE_DIR",
+            config.state_dir,
+            DEFAULT_STATE_DIR.to_string(),
+            |d| !d.trim().is_empty(),
+        ),
+        max_lock_age_minutes: resolve_setting(
+            "REHEARSA_MAX_LOCK_AGE_MINUTES",
+            config.max_lock_age_minutes,
+            DEFAULT_MAX_LOCK_AGE_MINUTES,
+            |_| true,
+        ),
+        running_probe_seconds: resolve_setting(
+            "REHEARSA_RUNNING_PROBE_SECONDS",
+            config.running_probe_seconds,
+            DEFAULT_RUNNING_PROBE_SECONDS,
+            |_| true,
+        ),
+        notify_dedup_minutes: resolve_setting(
+            "REHEARSA_NOTIFY_DEDUP_MINUTES",
+            config.notify_dedup_minutes,
+            DEFAULT_NOTIFY_DEDUP_MINUTES,
+            |_| true,
+        ),
+        min_coverage_pct: resolve_setting(
+            "REHEARSA_MIN_COVERAGE",
+            config.min_coverage_pct,
+            DEFAULT_MIN_COVERAGE_PCT,
+            |p| *p <= 100,
+        ),
+        watch_debounce_seconds: resolve_setting(
+            "REHEARSA_WATCH_DEBOUNCE_SECONDS",
+            config.watch_debounce_seconds,
+            DEFAULT_WATCH_DEBOUNCE_SECONDS,
+            |_| true,
+        ),
+        schedule_jitter_seconds: resolve_setting(
+            "REHEARSA_SCHEDULE_JITTER_SECONDS",
+            config.schedule_jitter_seconds,
+            DEFAULT_SCHEDULE_JITTER_SECONDS,
+            |_| true,
+        ),
+        metrics_addr: resolve_setting(
+            "REHEARSA_METRICS_ADDR",
+            config.metrics_addr,
+            DEFAULT_METRICS_ADDR.to_string(),
+            |a| a == DEFAULT_METRICS_ADDR || a.parse::<std::net::SocketAddr>().is_ok(),
+        ),
+        log_format: resolve_setting(
+            "REHEARSA_LOG_FORMAT",
+            config.log_format,
+            LogFormat::default(),
+            |_| true,
+        ),
+    }
+}
+
+/// Resolve the concurrency limit (see `resolve_setting` for precedence).
+pub fn resolve_concurrency() -> usize {
+    resolve_config().max_concurrent_rehearsals.value
 }
 
 pub fn set_concurrency(n: usize) -> Result<(), String> {
@@ -84,28 +419,169 @@ pub fn set_concurrency(n: usize) -> Result<(), String> {
     Ok(())
 }
 
+pub fn set_stability_weighting(weighting: StabilityWeighting) -> Result<(), String> {
+    let mut config = load_config();
+    config.stability_weighting = Some(weighting);
+    save_config(&config)?;
+    println!("Stability weighting set to {}.", weighting);
+    Ok(())
+}
+
+pub fn set_lock_dir(dir: &str) -> Result<(), String> {
+    if dir.trim().is_empty() {
+        return Err("Lock directory must not be empty.".to_string());
+    }
+    let mut config = load_config();
+    config.lock_dir = Some(dir.to_string());
+    save_config(&config)?;
+    println!("Lock directory set to {}.", dir);
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
+pub fn set_state_dir(dir: &str) -> Result<(), String> {
+    if dir.trim().is_empty() {
+        return Err("State directory must not be empty.".to_string());
+    }
+    let mut config = load_config();
+    config.state_dir = Some(dir.to_string());
+    save_config(&config)?;
+    println!("State directory set to {}.", dir);
+    println!("Existing state is not moved — copy scheduler_state.json across to keep catch_up history.");
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
+/// Path of a file under the resolved state directory.
+pub fn state_path(file: &str) -> PathBuf {
+    PathBuf::from(resolve_config().state_dir.value).join(file)
+}
+
+pub fn set_lock_timeout(minutes: u64) -> Result<(), String> {
+    let mut config = load_config();
+    config.max_lock_age_minutes = Some(minutes);
+    save_config(&config)?;
+    if minutes == 0 {
+        println!("Stuck-lock detection disabled.");
+    } else {
+        println!("Locks held longer than {} minute(s) will be reported as stuck.", minutes);
+    }
+    Ok(())
+}
+
+pub fn set_running_probe(seconds: u64) -> Result<(), String> {
+    let mut config = load_config();
+    config.running_probe_seconds = Some(seconds);
+    save_config(&config)?;
+    if seconds == 0 {
+        println!("Services without a healthcheck are scored as soon as they are running.");
+    } else {
+        println!("Services without a healthcheck must stay running for {}s to score.", seconds);
+    }
+    Ok(())
+}
+
+pub fn set_notify_dedup(minutes: u64) -> Result<(), String> {
+    let mut config = load_config();
+    config.notify_dedup_minutes = Some(minutes);
+    save_config(&config)?;
+    if minutes == 0 {
+        println!("Notification deduplication disabled.");
+    } else {
+        println!("Repeat notifications for the same stack and event are suppressed for {} minute(s).", minutes);
+    }
+    Ok(())
+}
+
+pub fn set_min_coverage(pct: u32) -> Result<(), String> {
+    if pct > 100 {
+        return Err("Minimum coverage must be between 0 and 100.".to_string());
+    }
+    let mut config = load_config();
+    config.min_coverage_pct = Some(pct);
+    save_config(&config)?;
+    println!("`rehearsa coverage` now fails below {}% coverage.", pct);
+    Ok(())
+}
+
+pub fn set_watch_debounce(seconds: u64) -> Result<(), String> {
+    let mut config = load_config();
+    config.watch_debounce_seconds = Some(seconds);
+    save_config(&config)?;
+    if seconds == 0 {
+        println!("Compose file changes trigger a rehearsal immediately.");
+    } else {
+        println!("Compose file changes trigger a rehearsal after {}s without further changes.", seconds);
+    }
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
+pub fn set_schedule_jitter(seconds: u64) -> Result<(), String> {
+    let mut config = load_config();
+    config.schedule_jitter_seconds = Some(seconds);
+    save_config(&config)?;
+    if seconds == 0 {
+        println!("Scheduled rehearsals start as soon as their slot is due.");
+    } else {
+        println!("Scheduled rehearsals start after a random delay of up to {}s.", seconds);
+    }
+    Ok(())
+}
+
+pub fn set_metrics_addr(addr: &str) -> Result<(), String> {
+    let mut config = load_config();
+    if addr == DEFAULT_METRICS_ADDR {
+        config.metrics_addr = None;
+        save_config(&config)?;
+        println!("Metrics endpoint disabled.");
+    } else {
+        addr.parse::<std::net::SocketAddr>()
+            .map_err(|_| format!("Invalid metrics address '{}'. Expected host:port, e.g. 127.0.0.1:9464", addr))?;
+        config.metrics_addr = Some(addr.to_string());
+        save_config(&config)?;
+        println!("Daemon serves /healthz and /metrics on http://{}", addr);
+    }
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
+pub fn set_log_format(format: LogFormat) -> Result<(), String> {
+    let mut config = load_config();
+    config.log_format = Some(format);
+    save_config(&config)?;
+    println!("Daemon log format set to {}.", format);
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
 pub fn show_config() -> Result<(), String> {
-    let config = load_config();
-    let resolved = resolve_concurrency();
+    let resolved = resolve_config();
 
     println!();
     println!("Rehearsa Daemon Config");
-    println!("{}", "─".repeat(40));
-    println!(
-        "  max_concurrent_rehearsals : {} (resolved: {})",
-        config.max_concurrent_rehearsals
-            .map(|n| n.to_string())
-            .unwrap_or_else(|| "not set".to_string()),
-        resolved
-    );
+    println!("{}", "─".repeat(80));
+    println!("{:<30} {:<16} Source", "Setting", "Value");
+    println!("{}", "─".repeat(80));
 
-    // Show source
-    if std::env::var("REHEARSA_MAX_CONCURRENT").is_ok() {
-        println!("  source: REHEARSA_MAX_CONCURRENT env var");
-    } else if config.max_concurrent_rehearsals.is_some() {
-        println!("  source: {}", CONFIG_PATH);
-    } else {
-        println!("  source: default");
+    for (setting, value, source) in resolved.rows() {
+        println!("{:<30} {:<16} {}", setting, value, source);
+    }
+
+    let config = load_config();
+    println!();
+    match config.paused_since() {
+        Some(since) => println!("State: paused since {}", since),
+        None        => println!("State: active"),
+    }
+
+    let groups = config.concurrency_groups;
+    if !groups.is_empty() {
+        println!();
+        println!("Concurrency groups");
+        for (name, limit) in groups {
+            println!("  {:<28} {}", name, limit);
+        }
     }
 
     println!();
@@ -124,17 +600,59 @@ pub struct WatchEntry {
     /// Optional cron expression (5-field, e.g. "0 3 * * *"). If absent, file-watch only.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schedule: Option<String>,
+    /// IANA timezone (e.g. "Europe/London") the schedule is evaluated in. UTC when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
     /// If true, run immediately on daemon start if a scheduled run was missed. Defaults false.
     #[serde(default)]
     pub catch_up: bool,
-    /// Optional named backup provider to verify before each rehearsal.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub provider: Option<String>,
+    /// Named backup providers to verify before each rehearsal. All must pass.
+    /// Older registries stored a single `provider` string; both forms load.
+    #[serde(
+        default,
+        alias = "provider",
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub providers: Vec<String>,
 
     /// Optional named notify channel override for this stack.
     /// If absent, the global default channel is used (if configured).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notify: Option<String>,
+
+    /// Promote the latest run to baseline after this many consecutive
+    /// clean scheduled rehearsals. Off when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_promote_after: Option<u32>,
+
+    /// Random delay bound in seconds for scheduled runs of this stack.
+    /// Falls back to the daemon's `schedule_jitter_seconds` when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jitter_seconds: Option<u64>,
+
+    /// Concurrency group (from the daemon config) this stack's rehearsals
+    /// are also limited by, on top of the global limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// Accept either a single string (legacy `provider`) or a list of strings.
+fn one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(s)  => vec![s],
+        OneOrMany::Many(v) => v,
+    })
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -197,16 +715,25 @@ pub fn save_registry(registry: &WatchRegistry) -> Result<(), String> {
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn add_watch(
     stack: &str,
     compose_path: &str,
     schedule: Option<&str>,
+    timezone: Option<&str>,
     catch_up: bool,
-    provider: Option<&str>,
+    providers: &[String],
     notify: Option<&str>,
+    auto_promote_after: Option<u32>,
+    jitter_seconds: Option<u64>,
+    group: Option<&str>,
 ) -> Result<(), String> {
     use std::str::FromStr;
 
+    if auto_promote_after == Some(0) {
+        return Err("--auto-promote-after must be at least 1".to_string());
+    }
+
     let mut registry = load_registry()?;
 
     // Validate cron expression if provided
@@ -217,6 +744,19 @@ pub fn add_watch(
             .map_err(|e| format!("Invalid cron expression '{}': {}", expr, e))?;
     }
 
+    if let Some(name) = timezone {
+        parse_timezone(name)?;
+    }
+
+    if let Some(name) = group {
+        if !load_config().concurrency_groups.contains_key(name) {
+            return Err(format!(
+                "Unknown concurrency group '{}'. Create it first with: rehearsa daemon set-group {} <limit>",
+                name, name
+            ));
+        }
+    }
+
     // Remove existing entry for this stack if present
     registry.watches.retain(|w| w.stack != stack);
 
@@ -229,22 +769,35 @@ pub fn add_watch(
         compose_path: abs_path.to_string_lossy().to_string(),
         added: Utc::now().to_rfc3339(),
         schedule: schedule.map(|s| s.to_string()),
+        timezone: timezone.map(|s| s.to_string()),
         catch_up,
-        provider: provider.map(|s| s.to_string()),
+        providers: providers.to_vec(),
         notify: notify.map(|s| s.to_string()),
+        auto_promote_after,
+        jitter_seconds,
+        group: group.map(|s| s.to_string()),
     });
 
     save_registry(&registry)?;
     println!("Watching '{}' at {}", stack, abs_path.display());
     if let Some(expr) = schedule {
-        println!("Schedule : {}", expr);
+        println!("Schedule : {} ({})", expr, timezone.unwrap_or("UTC"));
     }
-    if let Some(pname) = provider {
-        println!("Provider : {}", pname);
+    if !providers.is_empty() {
+        println!("Provider : {}", providers.join(", "));
     }
     if let Some(nchan) = notify {
         println!("Notify   : {}", nchan);
     }
+    if let Some(n) = auto_promote_after {
+        println!("Auto-promote after {} clean scheduled run(s)", n);
+    }
+    if let Some(secs) = jitter_seconds {
+        println!("Jitter   : up to {}s", secs);
+    }
+    if let Some(name) = group {
+        println!("Group    : {}", name);
+    }
     Ok(())
 }
 
@@ -260,8 +813,41 @@ pub fn remove_watch(stack: &str) -> Result<(), String> {
     Ok(())
 }
 
-pub fn list_watches() -> Result<(), String> {
+/// A watch as printed by `daemon list --json`.
+#[derive(Serialize)]
+struct WatchListing<'a> {
+    #[serde(flatten)]
+    watch: &'a WatchEntry,
+    /// Next scheduled slot (RFC 3339, UTC); null without a schedule.
+    next_run: Option<String>,
+    /// Consecutive clean runs towards auto-promotion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clean_streak: Option<u32>,
+}
+
+pub fn list_watches(json: bool) -> Result<(), String> {
     let registry = load_registry()?;
+    let now = Utc::now();
+    let streaks = load_streaks();
+
+    if json {
+        let listing: Vec<WatchListing> = registry.watches
+            .iter()
+            .map(|w| WatchListing {
+                watch: w,
+                next_run: w.schedule.as_deref()
+                    .and_then(|expr| next_slot(expr, w.timezone.as_deref(), now).ok().flatten())
+                    .map(|t| t.with_timezone(&Utc).to_rfc3339()),
+                clean_streak: w.auto_promote_after
+                    .map(|_| streaks.get(&w.stack).copied().unwrap_or(0)),
+            })
+            .collect();
+        let json = serde_json::to_string_pretty(&listing)
+            .map_err(|e| format!("JSON error: {}", e))?;
+        println!("{}", json);
+        return Ok(());
+    }
+
     if registry.watches.is_empty() {
         println!("No stacks being watched.");
         println!("Add one with: rehearsa daemon watch <stack> <compose-file>");
@@ -269,14 +855,32 @@ pub fn list_watches() -> Result<(), String> {
     }
 
     println!("Watched Stacks");
-    println!("{}", "─".repeat(110));
-    println!("{:<20} {:<30} {:<16} {:<20} {}", "Stack", "Compose Path", "Schedule", "Provider", "Notify");
-    println!("{}", "─".repeat(110));
+    println!("{}", "─".repeat(158));
+    println!(
+        "{:<20} {:<30} {:<30} {:<21} {:<20} {:<16} Auto-promote",
+        "Stack", "Compose Path", "Schedule", "Next Run", "Provider", "Notify"
+    );
+    println!("{}", "─".repeat(158));
     for w in &registry.watches {
-        let schedule = w.schedule.as_deref().unwrap_or("—");
-        let provider = w.provider.as_deref().unwrap_or("—");
+        let schedule = schedule_label(w).unwrap_or_else(|| "—".to_string());
+        let next_run = match w.schedule.as_deref() {
+            None       => "—".to_string(),
+            Some(expr) => match next_slot(expr, w.timezone.as_deref(), now) {
+                Ok(Some(t)) => t.format("%Y-%m-%d %H:%M %Z").to_string(),
+                Ok(None)    => "never".to_string(),
+                Err(_)      => "invalid schedule".to_string(),
+            },
+        };
+        let provider = if w.providers.is_empty() { "—".to_string() } else { w.providers.join(", ") };
         let notify   = w.notify.as_deref().unwrap_or("—");
-        println!("{:<20} {:<30} {:<16} {:<20} {}", w.stack, w.compose_path, schedule, provider, notify);
+        let promote  = match w.auto_promote_after {
+            Some(n) => format!("{}/{} clean", streaks.get(&w.stack).copied().unwrap_or(0), n),
+            None    => "—".to_string(),
+        };
+        println!(
+            "{:<20} {:<30} {:<30} {:<21} {:<20} {:<16} {}",
+            w.stack, w.compose_path, schedule, next_run, provider, notify, promote
+        );
     }
     Ok(())
 }
@@ -368,6 +972,34 @@ pub fn uninstall_daemon() -> Result<(), String> {
     Ok(())
 }
 
+/// Stop triggering rehearsals; watches, schedules and the unit stay in place.
+pub fn pause_daemon() -> Result<(), String> {
+    let mut config = load_config();
+    if let Some(since) = config.paused_since() {
+        println!("Daemon already paused since {}.", since);
+        return Ok(());
+    }
+    config.paused = true;
+    config.paused_since = Some(Utc::now().to_rfc3339());
+    save_config(&config)?;
+    println!("Daemon paused. No rehearsals are triggered until: rehearsa daemon resume");
+    println!("Scheduled slots that fall due while paused are skipped, not caught up.");
+    Ok(())
+}
+
+pub fn resume_daemon() -> Result<(), String> {
+    let mut config = load_config();
+    let Some(since) = config.paused_since().map(str::to_string) else {
+        println!("Daemon is not paused.");
+        return Ok(());
+    };
+    config.paused = false;
+    config.paused_since = None;
+    save_config(&config)?;
+    println!("Daemon resumed (was paused since {}).", since);
+    Ok(())
+}
+
 pub fn daemon_status() -> Result<(), String> {
     let output = Command::new("systemctl")
         .args(["status", "rehearsa.service", "--no-pager"])
@@ -379,18 +1011,114 @@ pub fn daemon_status() -> Result<(), String> {
         eprint!("{}", String::from_utf8_lossy(&output.stderr));
     }
 
+    if let Some(since) = load_config().paused_since() {
+        println!();
+        println!("Rehearsals paused since {} — resume with: rehearsa daemon resume", since);
+    }
+
+    print_locks();
+
     Ok(())
 }
 
+/// List held rehearsal locks, flagging stuck and stale ones.
+fn print_locks() {
+    let locks = crate::lock::list_locks();
+    let max_age = resolve_config().max_lock_age_minutes.value;
+
+    println!();
+    println!("Rehearsal Locks");
+    println!("{}", "─".repeat(80));
+
+    if locks.is_empty() {
+        println!("No rehearsals in progress.");
+        return;
+    }
+
+    println!("{:<24} {:<10} {:<10} State", "Stack", "PID", "Held");
+    println!("{}", "─".repeat(80));
+
+    for lock in locks {
+        let pid = lock.pid.map(|p| p.to_string()).unwrap_or_else(|| "?".to_string());
+        let held = lock.age_minutes().map(|m| format!("{}m", m)).unwrap_or_else(|| "?".to_string());
+        let state = if lock.is_stuck(max_age) {
+            format!("STUCK (> {}m) — break with: rehearsa daemon run --break-stuck", max_age)
+        } else if lock.alive() {
+            "running".to_string()
+        } else {
+            "stale (will be cleared on next run)".to_string()
+        };
+        println!("{:<24} {:<10} {:<10} {}", lock.label(), pid, held, state);
+    }
+}
+
+// ======================================================
+// LOGGING
+// ======================================================
+//
+// Every line the daemon path writes goes through `log_event`, so the
+// human and JSON formats cannot drift apart. Info goes to stdout,
+// warnings and errors to stderr, in either format.
+
+#[derive(Debug, Clone, Copy)]
+enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogLevel::Info  => write!(f, "info"),
+            LogLevel::Warn  => write!(f, "warn"),
+            LogLevel::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// One JSON log line; field order is the order on the wire.
+#[derive(Serialize)]
+struct LogLine<'a> {
+    ts:    &'a str,
+    level: String,
+    stack: Option<&'a str>,
+    event: &'a str,
+    msg:   &'a str,
+}
+
+/// Resolved once per process — a format change applies on daemon restart.
+static LOG_FORMAT: std::sync::OnceLock<LogFormat> = std::sync::OnceLock::new();
+
+/// Write one daemon event. `event` is a stable snake_case name for log pipelines.
+fn log_event(level: LogLevel, stack: Option<&str>, event: &str, msg: &str) {
+    let ts = Utc::now().to_rfc3339();
+    let line = match LOG_FORMAT.get_or_init(|| resolve_config().log_format.value) {
+        LogFormat::Human => format!("[{}] {}", ts, msg),
+        LogFormat::Json  => serde_json::to_string(&LogLine {
+            ts:    &ts,
+            level: level.to_string(),
+            stack,
+            event,
+            msg,
+        }).unwrap_or_default(),
+    };
+
+    match level {
+        LogLevel::Info                   => println!("{}", line),
+        LogLevel::Warn | LogLevel::Error => eprintln!("{}", line),
+    }
+}
+
 // ======================================================
 // DAEMON RUN LOOP
 // ======================================================
 
-pub async fn run_daemon() -> Result<(), String> {
+pub async fn run_daemon(break_stuck: bool) -> Result<(), String> {
     use notify::{Watcher, RecursiveMode, Event};
     use notify::event::EventKind;
     use std::sync::mpsc;
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
 
     let registry = load_registry()?;
 
@@ -399,12 +1127,13 @@ pub async fn run_daemon() -> Result<(), String> {
         return Ok(());
     }
 
-    println!("Rehearsa daemon starting...");
-    println!("Watching {} stack(s):", registry.watches.len());
+    log_event(LogLevel::Info, None, "daemon_start",
+        &format!("Rehearsa daemon starting — watching {} stack(s)", registry.watches.len()));
     for w in &registry.watches {
-        let sched = w.schedule.as_deref().unwrap_or("no schedule");
-        let prov  = w.provider.as_deref().unwrap_or("no provider");
-        println!("  {} → {}  [{}]  [{}]", w.stack, w.compose_path, sched, prov);
+        let sched = schedule_label(w).unwrap_or_else(|| "no schedule".to_string());
+        let prov  = if w.providers.is_empty() { "no provider".to_string() } else { w.providers.join(", ") };
+        log_event(LogLevel::Info, Some(&w.stack), "watch_registered",
+            &format!("{} → {}  [{}]  [{}]", w.stack, w.compose_path, sched, prov));
     }
 
     let (tx, rx) = mpsc::channel::<Result<Event, notify::Error>>();
@@ -422,87 +1151,426 @@ pub async fn run_daemon() -> Result<(), String> {
         }
     }
 
-    println!("Watching for changes. Logs via: journalctl -u rehearsa -f");
+    log_event(LogLevel::Info, None, "watching",
+        "Watching for changes. Logs via: journalctl -u rehearsa -f");
 
-    // Shared semaphore — limits simultaneous rehearsals across scheduler and file watcher
-    let max_concurrent = resolve_concurrency();
-    println!("Concurrency limit: {} simultaneous rehearsal(s)", max_concurrent);
-    let semaphore = Arc::new(Semaphore::new(max_concurrent));
+    // Shared limits — bound simultaneous rehearsals across scheduler and file watcher
+    let limits = Arc::new(ConcurrencyLimits::load());
+    log_event(LogLevel::Info, None, "concurrency_limit",
+        &format!("Concurrency limit: {} simultaneous rehearsal(s)", resolve_concurrency()));
+    let config = load_config();
+    for (group, limit) in &config.concurrency_groups {
+        log_event(LogLevel::Info, None, "concurrency_group",
+            &format!("Concurrency group '{}': {} at a time", group, limit));
+    }
 
     // Spawn the cron scheduler as a separate task
-    tokio::spawn(run_scheduler(Arc::clone(&semaphore)));
+    tokio::spawn(run_scheduler(Arc::clone(&limits), break_stuck));
+
+    let metrics_addr = resolve_config().metrics_addr.value;
+    if metrics_addr != DEFAULT_METRICS_ADDR {
+        let listener = tokio::net::TcpListener::bind(&metrics_addr).await
+            .map_err(|e| format!("Failed to bind metrics endpoint on {}: {}", metrics_addr, e))?;
+        log_event(LogLevel::Info, None, "metrics_listening",
+            &format!("Metrics: http://{}/metrics  Health: http://{}/healthz", metrics_addr, metrics_addr));
+        tokio::spawn(serve_metrics(listener));
+    }
+
+    // An editor save emits several events in quick succession. Changes are
+    // coalesced per stack — (last event, event count) — and fire once the
+    // file has been quiet for the debounce window.
+    let debounce = Duration::from_secs(resolve_config().watch_debounce_seconds.value);
+    log_event(LogLevel::Info, None, "debounce", &format!("Change debounce: {}s", debounce.as_secs()));
+
+    notify_started(registry.watches.len());
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut term = signal(SignalKind::terminate())
+            .map_err(|e| format!("Failed to install SIGTERM handler: {}", e))?;
+        let mut int = signal(SignalKind::interrupt())
+            .map_err(|e| format!("Failed to install SIGINT handler: {}", e))?;
+        tokio::spawn(async move {
+            let name = tokio::select! {
+                _ = term.recv() => "SIGTERM",
+                _ = int.recv()  => "SIGINT",
+            };
+            notify_stopped(name).await;
+            std::process::exit(0);
+        });
+    }
+    let mut pending: HashMap<String, (Instant, u32)> = HashMap::new();
 
     loop {
-        match rx.recv_timeout(Duration::from_secs(60)) {
+        WATCH_LOOP_TICK.store(Utc::now().timestamp(), Ordering::Relaxed);
+
+        let wait = pending
+            .values()
+            .map(|(last, _)| debounce.saturating_sub(last.elapsed()))
+            .min()
+            .unwrap_or(Duration::from_secs(60));
+
+        match rx.recv_timeout(wait) {
             Ok(Ok(event)) => {
                 if matches!(
                     event.kind,
                     EventKind::Modify(_) | EventKind::Create(_)
                 ) {
+                    let registry = load_registry().unwrap_or_default();
                     for changed_path in &event.paths {
-                        let registry = load_registry().unwrap_or_default();
                         for watch in &registry.watches {
-                            let watch_path = PathBuf::from(&watch.compose_path);
-                            if changed_path == &watch_path {
-                                println!(
-                                    "[{}] Change detected in {} — triggering rehearsal",
-                                    Utc::now().to_rfc3339(),
-                                    watch.stack
-                                );
-                                let sem = Arc::clone(&semaphore);
-                                let stack = watch.stack.clone();
-                                let compose_path = watch.compose_path.clone();
-                                let provider = watch.provider.clone();
-                                let notify_ch = watch.notify.clone();
-                                tokio::spawn(async move {
-                                    let _permit = sem.acquire().await;
-                                    trigger_rehearsal(
-                                        &stack,
-                                        &compose_path,
-                                        provider.as_deref(),
-                                        notify_ch.as_deref(),
-                                    ).await;
-                                });
+                            if changed_path == &PathBuf::from(&watch.compose_path) {
+                                let entry = pending.entry(watch.stack.clone()).or_insert((Instant::now(), 0));
+                                *entry = (Instant::now(), entry.1 + 1);
                             }
                         }
                     }
                 }
             }
-            Ok(Err(e)) => eprintln!("Watch error: {}", e),
-            Err(mpsc::RecvTimeoutError::Timeout) => {
-                println!("[{}] Daemon heartbeat — watching {} stacks",
-                    Utc::now().to_rfc3339(),
-                    load_registry().unwrap_or_default().watches.len()
-                );
+            Ok(Err(e)) => log_event(LogLevel::Error, None, "watch_error", &format!("Watch error: {}", e)),
+            Err(mpsc::RecvTimeoutError::Timeout) if pending.is_empty() => {
+                log_event(LogLevel::Info, None, "heartbeat", &format!(
+                    "Daemon heartbeat — watching {} stacks{}",
+                    load_registry().unwrap_or_default().watches.len(),
+                    match load_config().paused_since() {
+                        Some(since) => format!(" (paused since {})", since),
+                        None        => String::new(),
+                    }
+                ));
             }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
             Err(mpsc::RecvTimeoutError::Disconnected) => {
-                eprintln!("Watcher channel disconnected — exiting");
+                log_event(LogLevel::Error, None, "watcher_disconnected", "Watcher channel disconnected — exiting");
                 break;
             }
         }
+
+        let quiet: Vec<(String, u32)> = pending
+            .iter()
+            .filter(|(_, (last, _))| last.elapsed() >= debounce)
+            .map(|(stack, (_, events))| (stack.clone(), *events))
+            .collect();
+
+        if quiet.is_empty() {
+            continue;
+        }
+
+        let registry = load_registry().unwrap_or_default();
+        let paused = load_config().paused;
+
+        for (stack, events) in quiet {
+            pending.remove(&stack);
+
+            let Some(watch) = registry.watches.iter().find(|w| w.stack == stack) else {
+                continue;
+            };
+
+            if paused {
+                log_event(LogLevel::Info, Some(&watch.stack), "change_ignored",
+                    &format!("Change detected in {} — daemon paused, not rehearsing", watch.stack));
+                continue;
+            }
+
+            log_event(LogLevel::Info, Some(&watch.stack), "change_detected", &format!(
+                "Change detected in {} — triggering rehearsal{}",
+                watch.stack,
+                if events > 1 { format!(" ({} events coalesced)", events) } else { String::new() }
+            ));
+            let limits = Arc::clone(&limits);
+            let group = watch.group.clone();
+            let stack = watch.stack.clone();
+            let compose_path = watch.compose_path.clone();
+            let providers = watch.providers.clone();
+            let notify_ch = watch.notify.clone();
+            tokio::spawn(async move {
+                let _permits = limits.acquire(group.as_deref()).await;
+                trigger_rehearsal(
+                    &stack,
+                    &compose_path,
+                    &providers,
+                    notify_ch.as_deref(),
+                    break_stuck,
+                    // File-change runs do not count towards auto-promotion
+                    None,
+                ).await;
+            });
+        }
     }
 
     Ok(())
 }
 
+// Lifecycle notifications go to the default channel (or an event route),
+// keyed by host rather than stack. A crash or SIGKILL cannot be reported;
+// the next start notification is the tell that the daemon went away.
+
+fn notify_started(watches: usize) {
+    use crate::notify::{notify, NotifyEvent};
+
+    let host = crate::lock::get_hostname();
+    let msg = format!(
+        "Rehearsa daemon started on {}: watching {} stack(s), concurrency limit {}{}.",
+        host,
+        watches,
+        resolve_concurrency(),
+        match load_config().paused_since() {
+            Some(since) => format!(" (paused since {})", since),
+            None        => String::new(),
+        }
+    );
+    notify(&host, NotifyEvent::DaemonStarted, &msg, None);
+}
+
+async fn notify_stopped(signal: &'static str) {
+    use crate::notify::{notify, NotifyEvent};
+
+    log_event(LogLevel::Info, None, "daemon_stop", &format!("Received {} — shutting down", signal));
+    let _ = tokio::task::spawn_blocking(move || {
+        let host = crate::lock::get_hostname();
+        let msg = format!(
+            "Rehearsa daemon on {} stopped ({}). No rehearsals run until it is back.",
+            host, signal
+        );
+        notify(&host, NotifyEvent::DaemonStopped, &msg, None);
+    }).await;
+}
+
+// ======================================================
+// CONCURRENCY LIMITS
+// ======================================================
+//
+// Every rehearsal takes a permit from the global pool
+// (max_concurrent_rehearsals). A stack whose watch names a concurrency
+// group also takes one from that group's pool, so heavyweight stacks can
+// be held to one at a time while lightweight ones share the rest.
+//
+// Deadlock avoidance: permits are always taken in the same order — group
+// first, then global — and released together. A rehearsal queued behind
+// its group holds no global permit, so it can neither block stacks outside
+// the group nor form a wait cycle with a rehearsal holding a global permit.
+
+struct ConcurrencyLimits {
+    global: Arc<Semaphore>,
+    groups: HashMap<String, Arc<Semaphore>>,
+}
+
+/// Permits held for one rehearsal; released when dropped.
+struct RehearsalPermits {
+    _group:  Option<OwnedSemaphorePermit>,
+    _global: Option<OwnedSemaphorePermit>,
+}
+
+impl ConcurrencyLimits {
+    /// Pools sized from the resolved config. Groups are read once, so a
+    /// change takes effect on daemon restart, like the global limit.
+    fn load() -> Self {
+        ConcurrencyLimits {
+            global: Arc::new(Semaphore::new(resolve_concurrency())),
+            groups: load_config()
+                .concurrency_groups
+                .into_iter()
+                .map(|(name, limit)| (name, Arc::new(Semaphore::new(limit))))
+                .collect(),
+        }
+    }
+
+    /// Wait for the group permit (if any), then the global one.
+    async fn acquire(&self, group: Option<&str>) -> RehearsalPermits {
+        let group_pool = group.and_then(|name| {
+            let pool = self.groups.get(name);
+            if pool.is_none() {
+                log_event(LogLevel::Warn, None, "group_unknown", &format!(
+                    "Concurrency group '{}' is not configured — using the global limit only",
+                    name
+                ));
+            }
+            pool
+        });
+
+        let group_permit = match group_pool {
+            Some(pool) => Arc::clone(pool).acquire_owned().await.ok(),
+            None       => None,
+        };
+        let global_permit = Arc::clone(&self.global).acquire_owned().await.ok();
+
+        RehearsalPermits { _group: group_permit, _global: global_permit }
+    }
+}
+
+/// Create or resize a concurrency group; a limit of 0 removes it.
+pub fn set_concurrency_group(name: &str, limit: usize) -> Result<(), String> {
+    let mut config = load_config();
+
+    if limit == 0 {
+        if config.concurrency_groups.remove(name).is_none() {
+            return Err(format!("No concurrency group named '{}'", name));
+        }
+        save_config(&config)?;
+        println!("Removed concurrency group '{}'. Stacks in it now use the global limit only.", name);
+    } else {
+        config.concurrency_groups.insert(name.to_string(), limit);
+        save_config(&config)?;
+        println!("Concurrency group '{}' allows {} simultaneous rehearsal(s).", name, limit);
+    }
+
+    println!("Restart the daemon for the change to take effect: systemctl restart rehearsa");
+    Ok(())
+}
+
+// ======================================================
+// METRICS ENDPOINT
+// ======================================================
+//
+// A deliberately small HTTP/1.1 responder: one request per connection,
+// GET only. Counters cover rehearsals started since the daemon came up
+// (Prometheus handles the reset on restart); gauges are read from run
+// history and coverage on every scrape, so they never go stale.
+
+static REHEARSALS_TOTAL: AtomicU64 = AtomicU64::new(0);
+static REHEARSAL_FAILURES_TOTAL: AtomicU64 = AtomicU64::new(0);
+/// Unix time of the watch loop's last pass.
+static WATCH_LOOP_TICK: AtomicI64 = AtomicI64::new(0);
+
+/// The watch loop wakes at least once a minute; anything much older means it is wedged.
+const HEALTHZ_STALE_SECONDS: i64 = 180;
+
+/// Count a rehearsal that ran. A fatal error or policy violation is a failure.
+fn record_rehearsal(failed: bool) {
+    REHEARSALS_TOTAL.fetch_add(1, Ordering::Relaxed);
+    if failed {
+        REHEARSAL_FAILURES_TOTAL.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+async fn serve_metrics(listener: tokio::net::TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_metrics_request(stream));
+            }
+            Err(e) => log_event(LogLevel::Error, None, "metrics_accept_failed",
+                &format!("Metrics endpoint accept failed: {}", e)),
+        }
+    }
+}
+
+async fn handle_metrics_request(mut stream: tokio::net::TcpStream) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use std::time::Duration;
+
+    // Only the request line matters; headers and body are ignored.
+    let mut buf = vec![0u8; 4096];
+    let mut len = 0;
+    let read = tokio::time::timeout(Duration::from_secs(5), async {
+        while len < buf.len() && !buf[..len].windows(2).any(|w| w == b"\r\n") {
+            match stream.read(&mut buf[len..]).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => len += n,
+            }
+        }
+    }).await;
+    if read.is_err() {
+        return;
+    }
+
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let mut parts = request.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("").split('?').next().unwrap_or("");
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET", "/healthz") => {
+            let age = Utc::now().timestamp() - WATCH_LOOP_TICK.load(Ordering::Relaxed);
+            if age <= HEALTHZ_STALE_SECONDS {
+                ("200 OK", "text/plain", "ok\n".to_string())
+            } else {
+                ("503 Service Unavailable", "text/plain", format!("watch loop silent for {}s\n", age))
+            }
+        }
+        ("GET", "/metrics") => {
+            // History and coverage are read from disk — keep it off the async workers.
+            let body = tokio::task::spawn_blocking(render_metrics).await.unwrap_or_default();
+            ("200 OK", "text/plain; version=0.0.4", body)
+        }
+        ("GET", _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => ("405 Method Not Allowed", "text/plain", "method not allowed\n".to_string()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, content_type, body.len(), body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Prometheus text exposition of daemon counters and per-stack gauges.
+fn render_metrics() -> String {
+    use std::fmt::Write;
+
+    let mut out = String::new();
+
+    let _ = writeln!(out, "# HELP rehearsa_rehearsals_total Rehearsals run since the daemon started.");
+    let _ = writeln!(out, "# TYPE rehearsa_rehearsals_total counter");
+    let _ = writeln!(out, "rehearsa_rehearsals_total {}", REHEARSALS_TOTAL.load(Ordering::Relaxed));
+    let _ = writeln!(out, "# HELP rehearsa_rehearsal_failures_total Rehearsals that errored or violated policy since the daemon started.");
+    let _ = writeln!(out, "# TYPE rehearsa_rehearsal_failures_total counter");
+    let _ = writeln!(out, "rehearsa_rehearsal_failures_total {}", REHEARSAL_FAILURES_TOTAL.load(Ordering::Relaxed));
+
+    let latest: Vec<_> = load_registry()
+        .unwrap_or_default()
+        .watches
+        .iter()
+        .filter_map(|w| crate::history::load_latest(&w.stack))
+        .collect();
+
+    let _ = writeln!(out, "# HELP rehearsa_last_confidence Confidence score of the latest rehearsal.");
+    let _ = writeln!(out, "# TYPE rehearsa_last_confidence gauge");
+    for record in &latest {
+        let _ = writeln!(out, "rehearsa_last_confidence{{stack=\"{}\"}} {}", metric_label(&record.stack), record.confidence);
+    }
+    let _ = writeln!(out, "# HELP rehearsa_last_run_timestamp_seconds Unix time of the latest rehearsal.");
+    let _ = writeln!(out, "# TYPE rehearsa_last_run_timestamp_seconds gauge");
+    for record in &latest {
+        if let Ok(ts) = chrono::DateTime::parse_from_rfc3339(&record.timestamp) {
+            let _ = writeln!(out, "rehearsa_last_run_timestamp_seconds{{stack=\"{}\"}} {}", metric_label(&record.stack), ts.timestamp());
+        }
+    }
+
+    if let Ok(summary) = crate::coverage::build_coverage() {
+        let _ = writeln!(out, "# HELP rehearsa_coverage_pct Percentage of watched stacks honouring their restore contract.");
+        let _ = writeln!(out, "# TYPE rehearsa_coverage_pct gauge");
+        let _ = writeln!(out, "rehearsa_coverage_pct {}", summary.coverage_pct);
+    }
+
+    out
+}
+
+/// Escape a Prometheus label value.
+fn metric_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
 // ======================================================
 // CRON SCHEDULER
 // ======================================================
 
-const SCHEDULER_STATE_PATH: &str = "/etc/rehearsa/scheduler_state.json";
+const SCHEDULER_STATE_FILE: &str = "scheduler_state.json";
+
+type SlotMap = HashMap<String, chrono::DateTime<Utc>>;
 
 /// Load persisted last_run map from disk. Returns empty map if file is absent or unreadable.
-fn load_scheduler_state() -> HashMap<String, chrono::DateTime<Utc>> {
-    let raw = match fs::read_to_string(SCHEDULER_STATE_PATH) {
+fn load_scheduler_state(path: &Path) -> SlotMap {
+    let raw = match fs::read_to_string(path) {
         Ok(r) => r,
         Err(_) => return HashMap::new(),
     };
     serde_json::from_str(&raw).unwrap_or_default()
 }
 
-/// Persist the last_run map to disk. Logs on failure but never panics —
-/// a write failure is not worth crashing the daemon over.
-fn save_scheduler_state(last_run: &HashMap<String, chrono::DateTime<Utc>>) {
+/// Persist the last_run map to disk atomically. Logs on failure but never
+/// panics — a write failure is not worth crashing the daemon over.
+fn save_scheduler_state(path: &Path, last_run: &SlotMap) {
     let raw = match serde_json::to_string_pretty(last_run) {
         Ok(r) => r,
         Err(e) => {
@@ -510,48 +1578,228 @@ fn save_scheduler_state(last_run: &HashMap<String, chrono::DateTime<Utc>>) {
             return;
         }
     };
-    if let Err(e) = fs::write(SCHEDULER_STATE_PATH, raw) {
-        eprintln!("Scheduler: failed to write state to {}: {}", SCHEDULER_STATE_PATH, e);
-        return;
+    let result = match path.parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None      => Ok(()),
+    }.and_then(|_| crate::fsutil::write_atomic(path, raw.as_bytes(), Some(0o600)));
+    if let Err(e) = result {
+        eprintln!("Scheduler: failed to write state to {}: {}", path.display(), e);
     }
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let _ = std::fs::set_permissions(SCHEDULER_STATE_PATH, std::fs::Permissions::from_mode(0o600));
+}
+
+/// In-memory last_run map and its on-disk copy. Every update goes through
+/// `record`, which holds the lock across the whole read-merge-write so the
+/// two never disagree. The file is shared with `daemon run --once`, so the
+/// in-memory map can be stale until the next `record`.
+struct SchedulerState {
+    path: PathBuf,
+    last_run: std::sync::Mutex<SlotMap>,
+}
+
+impl SchedulerState {
+    fn load() -> Self {
+        Self::at(state_path(SCHEDULER_STATE_FILE))
+    }
+
+    fn at(path: PathBuf) -> Self {
+        let last_run = std::sync::Mutex::new(load_scheduler_state(&path));
+        SchedulerState { path, last_run }
+    }
+
+    fn map(&self) -> std::sync::MutexGuard<'_, SlotMap> {
+        self.last_run.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn get(&self, stack: &str) -> Option<chrono::DateTime<Utc>> {
+        self.map().get(stack).copied()
+    }
+
+    fn len(&self) -> usize {
+        self.map().len()
+    }
+
+    /// Claim `slot` for `stack`: returns true if it was not yet recorded
+    /// here or on disk, and persists it. The file is re-read and merged
+    /// first, so a slot another process already recorded is not claimed
+    /// twice and its entries are kept. Slots never move backwards.
+    fn record(&self, stack: &str, slot: chrono::DateTime<Utc>) -> bool {
+        let mut map = self.map();
+        let mut on_disk = load_scheduler_state(&self.path);
+
+        for (name, recorded) in &on_disk {
+            let entry = map.entry(name.clone()).or_insert(*recorded);
+            if *recorded > *entry {
+                *entry = *recorded;
+            }
+        }
+
+        if map.get(stack).is_some_and(|prev| *prev >= slot) {
+            return false;
+        }
+        map.insert(stack.to_string(), slot);
+        on_disk.insert(stack.to_string(), slot);
+        save_scheduler_state(&self.path, &on_disk);
+        true
+    }
+}
+
+/// Seconds between scheduler passes.
+const SCHEDULER_TICK_SECS: u64 = 30;
+
+/// What the scheduler should do with the most recent due slot of a watch.
+#[derive(Debug, PartialEq)]
+enum SlotAction {
+    /// This slot has already been recorded.
+    AlreadyRan,
+    /// The daemon is paused — record the slot without running.
+    SkipPaused,
+    /// A slot was missed while the daemon was down and catch_up is off —
+    /// record it without running.
+    SkipMissed,
+    /// A slot was missed and catch_up is on — run once now. Only the most
+    /// recent slot is ever considered, so a long outage runs once.
+    CatchUp,
+    /// The slot is due now, or is the first one seen for this stack.
+    Fire,
+}
+
+/// A slot first seen more than two ticks after it passed was missed
+/// (daemon down or stalled) rather than picked up on time.
+fn slot_action(
+    prev: Option<chrono::DateTime<Utc>>,
+    last_fire: chrono::DateTime<Utc>,
+    now: chrono::DateTime<Utc>,
+    catch_up: bool,
+    paused: bool,
+) -> SlotAction {
+    let missed = now - last_fire > chrono::Duration::seconds(2 * SCHEDULER_TICK_SECS as i64);
+
+    match prev {
+        Some(p) if p >= last_fire     => SlotAction::AlreadyRan,
+        _ if paused                   => SlotAction::SkipPaused,
+        Some(_) if missed && catch_up => SlotAction::CatchUp,
+        Some(_) if missed             => SlotAction::SkipMissed,
+        _                             => SlotAction::Fire,
+    }
+}
+
+/// Parse a 5-field cron expression and the timezone it is evaluated in.
+fn parse_schedule(expr: &str, timezone: Option<&str>) -> Result<(cron::Schedule, chrono_tz::Tz), String> {
+    use std::str::FromStr;
+
+    // cron crate requires a 6-field expression (with seconds). We store
+    // 5-field (standard cron) and prepend "0 " to fix seconds at 0.
+    let schedule = cron::Schedule::from_str(&format!("0 {}", expr))
+        .map_err(|e| format!("invalid cron '{}': {}", expr, e))?;
+
+    let tz = match timezone {
+        Some(name) => parse_timezone(name)?,
+        None       => chrono_tz::UTC,
+    };
+    Ok((schedule, tz))
+}
+
+/// Most recent slot of a 5-field cron expression at or before `now`,
+/// looking back 25 hours. The expression is evaluated as wall-clock time
+/// in `timezone` (UTC when None); the slot is returned in UTC for the
+/// `last_run` comparisons. None when no slot has passed in that window.
+fn last_slot(
+    expr: &str,
+    timezone: Option<&str>,
+    now: chrono::DateTime<Utc>,
+) -> Result<Option<chrono::DateTime<Utc>>, String> {
+    let (schedule, tz) = parse_schedule(expr, timezone)?;
+    let now = now.with_timezone(&tz);
+
+    Ok(schedule
+        .after(&(now - chrono::Duration::hours(25)))
+        .take_while(|t| t <= &now)
+        .last()
+        .map(|t| t.with_timezone(&Utc)))
+}
+
+/// Random delay in [0, jitter) for a scheduled run of `watch`, drawn fresh
+/// each time a slot fires. The slot itself is recorded before the delay,
+/// so jitter can never make it missed or fire twice.
+fn jitter_delay(watch: &WatchEntry) -> std::time::Duration {
+    use std::hash::{BuildHasher, Hasher};
+
+    let bound = watch.jitter_seconds
+        .unwrap_or_else(|| resolve_config().schedule_jitter_seconds.value);
+
+    if bound == 0 {
+        return std::time::Duration::ZERO;
+    }
+
+    // RandomState is seeded randomly per instance — enough to spread
+    // rehearsals without pulling in an RNG crate.
+    let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+    std::time::Duration::from_millis(random % (bound * 1000))
+}
+
+fn jitter_note(jitter: std::time::Duration) -> String {
+    if jitter.is_zero() {
+        String::new()
+    } else {
+        format!(" — starting in {}s (jitter)", jitter.as_secs())
     }
 }
 
+/// Parse an IANA timezone name such as "Europe/London".
+pub fn parse_timezone(name: &str) -> Result<chrono_tz::Tz, String> {
+    name.parse::<chrono_tz::Tz>().map_err(|_| {
+        format!("Invalid timezone '{}' — expected an IANA name such as Europe/London", name)
+    })
+}
+
+/// The first slot strictly after `now`, in the schedule's timezone.
+fn next_slot(
+    expr: &str,
+    timezone: Option<&str>,
+    now: chrono::DateTime<Utc>,
+) -> Result<Option<chrono::DateTime<chrono_tz::Tz>>, String> {
+    let (schedule, tz) = parse_schedule(expr, timezone)?;
+    Ok(schedule.after(&now.with_timezone(&tz)).next())
+}
+
+/// A watch's schedule for display, with its timezone when not UTC.
+fn schedule_label(watch: &WatchEntry) -> Option<String> {
+    let expr = watch.schedule.as_deref()?;
+    Some(match &watch.timezone {
+        Some(tz) => format!("{} ({})", expr, tz),
+        None     => expr.to_string(),
+    })
+}
+
 /// Runs in a background task. Every 30 seconds it re-reads the registry,
 /// checks whether any scheduled stack is due, and fires trigger_rehearsal.
 /// Last-run times are persisted to disk so catch_up works correctly across
 /// daemon restarts.
-async fn run_scheduler(semaphore: Arc<Semaphore>) {
-    use std::str::FromStr;
+async fn run_scheduler(limits: Arc<ConcurrencyLimits>, break_stuck: bool) {
     use tokio::time::Duration;
 
     // Load persisted state — survives daemon restarts
-    let mut last_run = load_scheduler_state();
+    let state = SchedulerState::load();
 
-    if !last_run.is_empty() {
-        println!(
-            "[{}] Scheduler: loaded last_run state for {} stack(s)",
-            Utc::now().to_rfc3339(),
-            last_run.len()
-        );
+    if state.len() > 0 {
+        log_event(LogLevel::Info, None, "scheduler_state_loaded",
+            &format!("Scheduler: loaded last_run state for {} stack(s)", state.len()));
     }
 
     loop {
-        tokio::time::sleep(Duration::from_secs(30)).await;
+        tokio::time::sleep(Duration::from_secs(SCHEDULER_TICK_SECS)).await;
 
         let registry = match load_registry() {
             Ok(r) => r,
             Err(e) => {
-                eprintln!("[{}] Scheduler: failed to load registry: {}", Utc::now().to_rfc3339(), e);
+                log_event(LogLevel::Error, None, "registry_load_failed",
+                    &format!("Scheduler: failed to load registry: {}", e));
                 continue;
             }
         };
 
         let now = Utc::now();
+        let paused = load_config().paused;
 
         for watch in &registry.watches {
             let expr = match &watch.schedule {
@@ -559,127 +1807,297 @@ async fn run_scheduler(semaphore: Arc<Semaphore>) {
                 None => continue, // no schedule for this stack
             };
 
-            // cron crate requires a 6-field expression (with seconds). We store
-            // 5-field (standard cron) and prepend "0 " to fix seconds at 0.
-            let full_expr = format!("0 {}", expr);
-            let schedule = match cron::Schedule::from_str(&full_expr) {
-                Ok(s) => s,
+            let last_fire = match last_slot(expr, watch.timezone.as_deref(), now) {
+                Ok(Some(t)) => t,
+                Ok(None) => continue, // no scheduled time has passed yet
                 Err(e) => {
-                    eprintln!(
-                        "[{}] Scheduler: invalid cron '{}' for stack '{}': {}",
-                        now.to_rfc3339(), expr, watch.stack, e
-                    );
+                    log_event(LogLevel::Error, Some(&watch.stack), "schedule_invalid",
+                        &format!("Scheduler: {} for stack '{}'", e, watch.stack));
                     continue;
                 }
             };
 
-            // Find the most recent scheduled time that has already passed
-            let last_fire = schedule
-                .after(&(now - chrono::Duration::hours(25)))
-                .take_while(|t| t <= &now)
-                .last();
-
-            let last_fire = match last_fire {
-                Some(t) => t,
-                None => continue, // no scheduled time has passed yet
-            };
-
-            // Have we already run this slot?
-            if let Some(&prev) = last_run.get(&watch.stack) {
-                if prev >= last_fire {
-                    continue; // already fired this window
+            let catch_up = match slot_action(state.get(&watch.stack), last_fire, now, watch.catch_up, paused) {
+                SlotAction::AlreadyRan => continue,
+                SlotAction::SkipMissed => {
+                    state.record(&watch.stack, last_fire);
+                    continue;
                 }
-
-                // A window was missed while the daemon was down.
-                // If catch_up is false, record the slot and skip silently.
-                if !watch.catch_up {
-                    last_run.insert(watch.stack.clone(), last_fire);
-                    save_scheduler_state(&last_run);
+                // Recorded, so resuming does not fire a burst of stale slots
+                SlotAction::SkipPaused => {
+                    state.record(&watch.stack, last_fire);
+                    log_event(LogLevel::Info, Some(&watch.stack), "slot_skipped", &format!(
+                        "Scheduler: daemon paused — skipping slot {} for '{}'",
+                        last_fire.to_rfc3339(), watch.stack
+                    ));
                     continue;
                 }
+                SlotAction::CatchUp => true,
+                SlotAction::Fire => false,
+            };
 
-                println!(
-                    "[{}] Scheduler: catch_up triggered for '{}' (missed slot: {})",
-                    now.to_rfc3339(),
+            // Record before firing so a crash mid-rehearsal cannot re-fire this
+            // slot; a slot `daemon run --once` already recorded is not run again
+            if !state.record(&watch.stack, last_fire) {
+                continue;
+            }
+
+            if catch_up {
+                log_event(LogLevel::Info, Some(&watch.stack), "catch_up", &format!(
+                    "Scheduler: catch_up triggered for '{}' (missed slot: {})",
                     watch.stack,
                     last_fire.to_rfc3339()
-                );
+                ));
             }
 
-            // Record, persist, and fire
-            last_run.insert(watch.stack.clone(), last_fire);
-            save_scheduler_state(&last_run);
-
-            println!(
-                "[{}] Scheduler: running rehearsal for '{}' (schedule: {})",
-                now.to_rfc3339(), watch.stack, expr
-            );
-            let sem = Arc::clone(&semaphore);
+            let jitter = jitter_delay(watch);
+            log_event(LogLevel::Info, Some(&watch.stack), "schedule_fired", &format!(
+                "Scheduler: running rehearsal for '{}' (schedule: {}){}",
+                watch.stack, expr, jitter_note(jitter)
+            ));
+            let limits = Arc::clone(&limits);
+            let group = watch.group.clone();
             let stack = watch.stack.clone();
             let compose_path = watch.compose_path.clone();
-            let provider = watch.provider.clone();
+            let providers = watch.providers.clone();
             let notify_ch = watch.notify.clone();
+            let auto_promote_after = watch.auto_promote_after;
             tokio::spawn(async move {
-                let _permit = sem.acquire().await;
-                println!(
-                    "[{}] Semaphore acquired for '{}' — starting rehearsal",
-                    chrono::Utc::now().to_rfc3339(), stack
-                );
+                tokio::time::sleep(jitter).await;
+                let _permits = limits.acquire(group.as_deref()).await;
+                log_event(LogLevel::Info, Some(&stack), "permits_acquired",
+                    &format!("Concurrency permits acquired for '{}' — starting rehearsal", stack));
                 trigger_rehearsal(
                     &stack,
                     &compose_path,
-                    provider.as_deref(),
+                    &providers,
                     notify_ch.as_deref(),
+                    break_stuck,
+                    auto_promote_after,
                 ).await;
             });
         }
     }
 }
 
+// ======================================================
+// ONE-SHOT PASS
+// ======================================================
+
+/// A single scheduler pass for hosts driven by their own cron or a
+/// Kubernetes CronJob: rehearse every watch with an unrecorded due slot
+/// (every watch with `all`), wait for them to finish, and return how many
+/// ran. Slots are recorded in the resident daemon's scheduler state, so
+/// the two never fire the same slot twice.
+pub async fn run_once(all: bool, break_stuck: bool) -> Result<usize, String> {
+    let registry = load_registry()?;
+
+    if registry.watches.is_empty() {
+        eprintln!("No watches configured. Add with: rehearsa daemon watch <stack> <compose-file>");
+        return Ok(0);
+    }
+
+    let state = SchedulerState::load();
+    let limits = Arc::new(ConcurrencyLimits::load());
+    let now = Utc::now();
+    let paused = load_config().paused;
+    let mut rehearsals = Vec::new();
+
+    for watch in &registry.watches {
+        let slot = watch.schedule.as_deref().and_then(|expr| {
+            match last_slot(expr, watch.timezone.as_deref(), now) {
+                Ok(slot) => slot,
+                Err(e) => {
+                    log_event(LogLevel::Error, Some(&watch.stack), "schedule_invalid",
+                        &format!("Scheduler: {} for stack '{}'", e, watch.stack));
+                    None
+                }
+            }
+        });
+
+        // Each pass is itself the scheduler tick, so a slot that passed
+        // since the last recorded run is due now, not missed. Claiming it
+        // also stops the resident daemon from firing it again.
+        let due = slot.is_some_and(|s| state.record(&watch.stack, s));
+
+        if !due && !all {
+            continue;
+        }
+
+        if paused {
+            log_event(LogLevel::Info, Some(&watch.stack), "slot_skipped",
+                &format!("Scheduler: daemon paused — skipping '{}'", watch.stack));
+            continue;
+        }
+
+        // Forced (--all) runs start straight away; only due slots are spread out.
+        let jitter = if due { jitter_delay(watch) } else { std::time::Duration::ZERO };
+        log_event(LogLevel::Info, Some(&watch.stack), "schedule_fired", &format!(
+            "Scheduler: running rehearsal for '{}' ({}){}",
+            watch.stack,
+            if due { "schedule due" } else { "--all" },
+            jitter_note(jitter)
+        ));
+
+        let limits = Arc::clone(&limits);
+        let group = watch.group.clone();
+        let stack = watch.stack.clone();
+        let compose_path = watch.compose_path.clone();
+        let providers = watch.providers.clone();
+        let notify_ch = watch.notify.clone();
+        // Only scheduled runs count towards auto-promotion
+        let auto_promote_after = if due { watch.auto_promote_after } else { None };
+        rehearsals.push(tokio::spawn(async move {
+            tokio::time::sleep(jitter).await;
+            let _permits = limits.acquire(group.as_deref()).await;
+            trigger_rehearsal(
+                &stack,
+                &compose_path,
+                &providers,
+                notify_ch.as_deref(),
+                break_stuck,
+                auto_promote_after,
+            ).await;
+        }));
+    }
+
+    let ran = rehearsals.len();
+    futures_util::future::join_all(rehearsals).await;
+
+    Ok(ran)
+}
+
+// ======================================================
+// AUTO-PROMOTE STREAKS
+// ======================================================
+//
+// Consecutive clean scheduled rehearsals per stack, kept next to the
+// scheduler state so a streak survives daemon restarts. Rehearsals for
+// different stacks finish concurrently; STREAK_LOCK serialises the
+// read-modify-write of the shared file.
+
+const STREAK_STATE_FILE: &str = "promotion_streaks.json";
+
+static STREAK_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn load_streaks() -> HashMap<String, u32> {
+    fs::read_to_string(state_path(STREAK_STATE_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Extend the stack's streak after a clean run, or reset it to 0.
+/// Returns the new streak length.
+fn update_streak(stack: &str, clean: bool) -> u32 {
+    let _guard = STREAK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut streaks = load_streaks();
+    let streak = if clean { streaks.get(stack).copied().unwrap_or(0) + 1 } else { 0 };
+    streaks.insert(stack.to_string(), streak);
+
+    let path = state_path(STREAK_STATE_FILE);
+    let result = serde_json::to_string_pretty(&streaks)
+        .map_err(std::io::Error::other)
+        .and_then(|raw| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            crate::fsutil::write_atomic(&path, raw.as_bytes(), Some(0o600))
+        });
+    if let Err(e) = result {
+        eprintln!("Auto-promote: failed to write streaks to {}: {}", path.display(), e);
+    }
+
+    streak
+}
+
 // ======================================================
 // REHEARSAL TRIGGER
 // ======================================================
 
+/// `stack test` options for unattended rehearsals (scheduled, watched and
+/// `baseline auto-init`): text output with no CLI flags applied, and
+/// 120 s per service instead of the CLI's 30, since a cold host may pull
+/// and start slowly. Bind sources are cloned and the disk-space check runs
+/// as with a manual `stack test`, so a scheduled run rehearses against the
+/// real data. A held lock fails the run at once rather than waiting.
+pub fn unattended_test_options() -> crate::engine::stack::TestOptions {
+    crate::engine::stack::TestOptions {
+        timeout:       120,
+        running_probe_seconds: resolve_config().running_probe_seconds.value,
+        clone_volumes: true,
+        disk_check:    true,
+        lock_wait:     None,
+        ..Default::default()
+    }
+}
+
 async fn trigger_rehearsal(
     stack: &str,
     compose_path: &str,
-    provider: Option<&str>,
+    providers: &[String],
     notify_channel: Option<&str>,
+    break_stuck: bool,
+    auto_promote_after: Option<u32>,
 ) {
-    use crate::engine::stack::{test_stack, PullPolicy};
+    use crate::engine::stack::test_stack;
     use crate::provider::verify_provider;
     use crate::notify::{notify, NotifyEvent};
 
-    // Provider verification — critical gate before rehearsal
-    if let Some(pname) = provider {
-        println!(
-            "[{}] Verifying provider '{}' before rehearsal for '{}'",
-            Utc::now().to_rfc3339(), pname, stack
-        );
+    // Stuck-lock gate — a live PID holding the lock past the max age is
+    // almost certainly a hung rehearsal. Report it (or break it) rather
+    // than skipping this stack indefinitely.
+    let max_lock_age = resolve_config().max_lock_age_minutes.value;
+    if let Some(lock) = crate::lock::read_lock(stack).filter(|l| l.is_stuck(max_lock_age)) {
+        let held = lock.age_minutes().unwrap_or(0);
+        let pid = lock.pid.unwrap_or(0);
+
+        if break_stuck {
+            let msg = format!(
+                "Broke stuck lock held by PID {} for {}m (limit {}m); the hung process was left running.",
+                pid, held, max_lock_age
+            );
+            if let Err(e) = crate::lock::break_lock(stack) {
+                log_event(LogLevel::Error, Some(stack), "lock_break_failed", &format!("{} for '{}'", e, stack));
+                return;
+            }
+            log_event(LogLevel::Warn, Some(stack), "lock_broken", &format!("{} — '{}'", msg, stack));
+            notify(stack, NotifyEvent::RehearsalStuck, &msg, notify_channel);
+        } else {
+            let msg = format!(
+                "Rehearsal appears stuck: lock held by PID {} for {}m (limit {}m). \
+                 Restart the daemon with --break-stuck to force it.",
+                pid, held, max_lock_age
+            );
+            log_event(LogLevel::Warn, Some(stack), "lock_stuck", &format!("{} — skipping '{}'", msg, stack));
+            notify(stack, NotifyEvent::RehearsalStuck, &msg, notify_channel);
+            return;
+        }
+    }
+
+    // Provider verification — critical gate before rehearsal. Every attached
+    // provider must pass; the first failure aborts.
+    for pname in providers {
+        log_event(LogLevel::Info, Some(stack), "provider_verifying",
+            &format!("Verifying provider '{}' before rehearsal for '{}'", pname, stack));
         if let Err(e) = verify_provider(pname) {
             let msg = format!("Provider '{}' verification failed: {}", pname, e);
-            eprintln!("[{}] {} — skipping rehearsal for '{}'", Utc::now().to_rfc3339(), msg, stack);
+            log_event(LogLevel::Error, Some(stack), "provider_failed",
+                &format!("{} — skipping rehearsal for '{}'", msg, stack));
             notify(stack, NotifyEvent::ProviderVerificationFailed, &msg, notify_channel);
             return;
         }
-        println!(
-            "[{}] Provider '{}' OK — proceeding with rehearsal for '{}'",
-            Utc::now().to_rfc3339(), pname, stack
-        );
+        log_event(LogLevel::Info, Some(stack), "provider_ok",
+            &format!("Provider '{}' OK — proceeding with rehearsal for '{}'", pname, stack));
     }
 
-    println!("[{}] Starting rehearsal for '{}'", Utc::now().to_rfc3339(), stack);
+    log_event(LogLevel::Info, Some(stack), "rehearsal_started", &format!("Starting rehearsal for '{}'", stack));
 
-    match test_stack(
-        compose_path,
-        120,
-        false,
-        None,
-        false,
-        PullPolicy::IfMissing,
-    ).await {
+    match test_stack(&[compose_path.to_string()], unattended_test_options()).await {
         Ok(summary) => {
-            println!("[{}] Rehearsal complete for '{}'", Utc::now().to_rfc3339(), stack);
+            log_event(LogLevel::Info, Some(stack), "rehearsal_complete", &format!("Rehearsal complete for '{}'", stack));
+            record_rehearsal(summary.policy_violated);
 
             if summary.policy_violated {
                 let msg = format!(
@@ -688,7 +2106,13 @@ async fn trigger_rehearsal(
                 );
                 notify(stack, NotifyEvent::PolicyViolation, &msg, notify_channel);
             } else if summary.baseline_drift {
-                let msg = "Restore contract drift detected against pinned baseline.".to_owned();
+                let msg = match summary.drift_severity {
+                    Some(severity) => format!(
+                        "Restore contract drift detected against pinned baseline (severity: {}).",
+                        severity
+                    ),
+                    None => "Restore contract drift detected against pinned baseline.".to_owned(),
+                };
                 notify(stack, NotifyEvent::BaselineDrift, &msg, notify_channel);
             } else {
                 notify(
@@ -698,19 +2122,46 @@ async fn trigger_rehearsal(
                     notify_channel,
                 );
             }
+
+            // Only a run against a pinned baseline, with no drift and no
+            // policy violation, extends the streak.
+            if let Some(after) = auto_promote_after {
+                let clean = summary.drift_severity.is_some()
+                    && !summary.baseline_drift
+                    && !summary.policy_violated;
+
+                if update_streak(stack, clean) >= after {
+                    match crate::baseline::promote_baseline(stack, None, None, 10) {
+                        Ok(()) => {
+                            update_streak(stack, false);
+                            let msg = format!(
+                                "Baseline auto-promoted from the latest run after {} consecutive clean rehearsals.",
+                                after
+                            );
+                            log_event(LogLevel::Info, Some(stack), "baseline_auto_promoted",
+                                &format!("{} — '{}'", msg, stack));
+                            notify(stack, NotifyEvent::BaselineAutoPromoted, &msg, notify_channel);
+                        }
+                        Err(e) => log_event(LogLevel::Error, Some(stack), "auto_promote_failed",
+                            &format!("Auto-promote failed for '{}': {}", stack, e)),
+                    }
+                }
+            }
         }
         Err(e) => {
             let msg = format!("{}", e);
+            if auto_promote_after.is_some() && !msg.contains("already being rehearsed") {
+                update_streak(stack, false);
+            }
             // Lock contention is expected when scheduler and file watcher both
             // fire simultaneously. Log as a skip, not a failure — no notification.
             if msg.contains("already being rehearsed") {
-                println!(
-                    "[{}] Rehearsal skipped for '{}' — already in progress (lock held)",
-                    Utc::now().to_rfc3339(), stack
-                );
+                log_event(LogLevel::Info, Some(stack), "rehearsal_skipped",
+                    &format!("Rehearsal skipped for '{}' — already in progress (lock held)", stack));
             } else {
+                record_rehearsal(true);
                 let full_msg = format!("Rehearsal failed: {}", msg);
-                eprintln!("[{}] {} for '{}'", Utc::now().to_rfc3339(), full_msg, stack);
+                log_event(LogLevel::Error, Some(stack), "rehearsal_failed", &format!("{} for '{}'", full_msg, stack));
                 notify(stack, NotifyEvent::RehearsalFatalError, &full_msg, notify_channel);
             }
         }
@@ -736,3 +2187,101 @@ fn run_systemctl(args: &[&str]) -> Result<(), String> {
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const HOURLY: &str = "0 * * * *";
+
+    fn at(hour: u32, minute: u32) -> chrono::DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 2, hour, minute, 0).unwrap()
+    }
+
+    /// Run the scheduler's slot decisions for "app" every tick from `from`
+    /// to `to`, recording slots as the scheduler does. Returns each run.
+    fn simulate(
+        state: &mut SlotMap,
+        from: chrono::DateTime<Utc>,
+        to: chrono::DateTime<Utc>,
+        catch_up: bool,
+        paused: bool,
+    ) -> Vec<(chrono::DateTime<Utc>, SlotAction)> {
+        let mut runs = Vec::new();
+        let mut now = from;
+
+        while now <= to {
+            if let Some(slot) = last_slot(HOURLY, None, now).unwrap() {
+                match slot_action(state.get("app").copied(), slot, now, catch_up, paused) {
+                    SlotAction::AlreadyRan => {}
+                    action => {
+                        state.insert("app".to_string(), slot);
+                        if matches!(action, SlotAction::CatchUp | SlotAction::Fire) {
+                            runs.push((slot, action));
+                        }
+                    }
+                }
+            }
+            now += chrono::Duration::seconds(SCHEDULER_TICK_SECS as i64);
+        }
+
+        runs
+    }
+
+    #[test]
+    fn missed_window_across_restart_catches_up_exactly_once() {
+        // Last recorded run at 02:00, then the daemon was down until 07:10.
+        let mut state = SlotMap::from([("app".to_string(), at(2, 0))]);
+
+        let after_restart = simulate(&mut state, at(7, 10), at(7, 59), true, false);
+        assert_eq!(after_restart, [(at(7, 0), SlotAction::CatchUp)]);
+
+        let next_hour = simulate(&mut state, at(8, 0), at(8, 30), true, false);
+        assert_eq!(next_hour, [(at(8, 0), SlotAction::Fire)]);
+    }
+
+    #[test]
+    fn missed_window_without_catch_up_is_skipped_but_later_slots_run() {
+        let mut state = SlotMap::from([("app".to_string(), at(2, 0))]);
+
+        assert!(simulate(&mut state, at(7, 10), at(7, 59), false, false).is_empty());
+        assert_eq!(state["app"], at(7, 0));
+
+        let next_hours = simulate(&mut state, at(8, 0), at(9, 30), false, false);
+        assert_eq!(
+            next_hours,
+            [(at(8, 0), SlotAction::Fire), (at(9, 0), SlotAction::Fire)]
+        );
+    }
+
+    #[test]
+    fn slot_due_while_paused_is_skipped() {
+        let mut state = SlotMap::from([("app".to_string(), at(2, 0))]);
+
+        assert!(simulate(&mut state, at(3, 0), at(3, 10), true, true).is_empty());
+        assert_eq!(state["app"], at(3, 0));
+
+        // Resuming does not run the slot that passed while paused.
+        assert!(simulate(&mut state, at(3, 20), at(3, 59), true, false).is_empty());
+    }
+
+    #[test]
+    fn slot_recorded_by_another_process_is_not_claimed_again() {
+        let path = std::env::temp_dir()
+            .join(format!("rehearsa_scheduler_{}.json", uuid::Uuid::new_v4()));
+
+        // The resident daemon loads its state before `run --once` records a slot.
+        let daemon = SchedulerState::at(path.clone());
+        let once = SchedulerState::at(path.clone());
+
+        assert!(once.record("app", at(3, 0)));
+        assert!(!daemon.record("app", at(3, 0)));
+        assert_eq!(daemon.get("app"), Some(at(3, 0)));
+
+        assert!(daemon.record("app", at(4, 0)));
+        assert_eq!(load_scheduler_state(&path)["app"], at(4, 0));
+
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,113 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// ======================================================
+// MOUNT CLONING
+// ======================================================
+//
+// Bind-mount sources are copied into a per-run sandbox and the copies
+// are mounted instead, so a rehearsal boots against the real restored
+// data without ever writing to it. Named and anonymous volumes have no
+// host path and are skipped.
+
+/// A bind source copied into the sandbox, ready to mount.
+#[derive(Debug, Clone)]
+pub struct ClonedMount {
+    pub source:    PathBuf,
+    pub clone:     PathBuf,
+    /// Path inside the container.
+    pub target:    String,
+    pub read_only: bool,
+}
+
+/// Split a short-form volume entry (`src:dst[:mode]`) into its host
+/// source, container target and read-only flag. Returns None for named
+/// or anonymous volumes.
+pub fn parse_bind(entry: &str, base: &Path) -> Option<(PathBuf, String, bool)> {
+    let mut parts = entry.splitn(3, ':');
+    let source = parts.next()?;
+    let target = parts.next()?;
+    let read_only = parts.next().is_some_and(|m| m.split(',').any(|o| o == "ro"));
+
+    let source = if source.starts_with('/') {
+        PathBuf::from(source)
+    } else if source.starts_with("./") || source.starts_with("../") || source == "." {
+        base.join(source)
+    } else if let Some(rest) = source.strip_prefix("~/") {
+        dirs::home_dir()?.join(rest)
+    } else {
+        return None; // named volume
+    };
+
+    Some((source, target.to_string(), read_only))
+}
+
+/// Copy every bind source of one service into `sandbox/<service>/<n>`.
+/// `base` is the Compose file's directory, for relative sources. A
+/// missing source becomes an empty directory, as Docker would create.
+pub fn clone_mounts(
+    sandbox: &Path,
+    service: &str,
+    volumes: &[String],
+    base: &Path,
+) -> Result<Vec<ClonedMount>, String> {
+    let mut out = Vec::new();
+
+    for (i, entry) in volumes.iter().enumerate() {
+        let (source, target, read_only) = match parse_bind(entry, base) {
+            Some(b) => b,
+            None    => continue,
+        };
+
+        let clone = sandbox.join(service).join(i.to_string());
+
+        let result = if source.is_dir() {
+            copy_dir(&source, &clone)
+        } else if source.exists() {
+            fs::create_dir_all(sandbox.join(service))
+                .and_then(|_| fs::copy(&source, &clone).map(|_| ()))
+        } else {
+            fs::create_dir_all(&clone)
+        };
+
+        result.map_err(|e| format!(
+            "Failed to clone bind mount '{}' for service '{}': {}",
+            source.display(), service, e
+        ))?;
+
+        out.push(ClonedMount { source, clone, target, read_only });
+    }
+
+    Ok(out)
+}
+
+/// Remove a run's sandbox. Failures are reported, never fatal.
+pub fn cleanup_clone(sandbox: &Path) {
+    if sandbox.exists() {
+        if let Err(e) = fs::remove_dir_all(sandbox) {
+            eprintln!("Warning: failed to remove clone sandbox {}: {}", sandbox.display(), e);
+        }
+    }
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let kind = entry.file_type()?;
+        let dest = to.join(entry.file_name());
+
+        if kind.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else if kind.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &dest)?;
+        } else {
+            fs::copy(entry.path(), &dest)?;
+        }
+    }
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use sha2::{Digest, Sha256};
 
 // ======================================================
 // PUBLIC STRUCTS
@@ -13,14 +14,208 @@ pub struct ComposeFile {
 pub struct Service {
     pub image: Option<String>,
     pub environment: Option<Vec<String>>,
-    #[allow(dead_code)]
     pub volumes: Option<Vec<String>>,
-    pub depends_on: Option<Vec<String>>,
+    pub depends_on: Option<Vec<Dependency>>,
     pub command: Option<Vec<String>>,
     pub healthcheck: Option<HealthCheck>,
     pub ports: Option<Vec<String>>,
     pub entrypoint: Option<Vec<String>>,
     pub labels: Option<std::collections::HashMap<String, String>>,
+    /// Compose `restart:` policy (no | always | on-failure[:N] | unless-stopped).
+    pub restart: Option<String>,
+    /// Compose `profiles:`. A service with none is always active.
+    pub profiles: Option<Vec<String>>,
+}
+
+// ======================================================
+// DEPENDENCIES
+// ======================================================
+//
+// Each `depends_on` edge carries the condition the dependency must
+// reach before the dependent is started. Short (list) form means
+// `service_started`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependsCondition {
+    Started,
+    Healthy,
+    CompletedSuccessfully,
+}
+
+impl DependsCondition {
+    fn parse(s: &str) -> Self {
+        match s {
+            "service_healthy"                => DependsCondition::Healthy,
+            "service_completed_successfully" => DependsCondition::CompletedSuccessfully,
+            _                                => DependsCondition::Started,
+        }
+    }
+}
+
+impl std::fmt::Display for DependsCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DependsCondition::Started               => write!(f, "service_started"),
+            DependsCondition::Healthy               => write!(f, "service_healthy"),
+            DependsCondition::CompletedSuccessfully => write!(f, "service_completed_successfully"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub service:   String,
+    pub condition: DependsCondition,
+}
+
+// ======================================================
+// PROFILES
+// ======================================================
+//
+// Mirrors `docker compose`: profiles come from COMPOSE_PROFILES
+// (comma-separated) plus any --profile flags, which add to the env
+// rather than replace it. "*" activates every profile.
+
+/// Merge COMPOSE_PROFILES with `--profile` flags, de-duplicated in order.
+pub fn active_profiles(flags: &[String]) -> Vec<String> {
+    let from_env = std::env::var("COMPOSE_PROFILES").unwrap_or_default();
+
+    let mut out: Vec<String> = Vec::new();
+    for p in from_env.split(',').chain(flags.iter().map(|s| s.as_str())) {
+        let p = p.trim();
+        if !p.is_empty() && !out.iter().any(|x| x == p) {
+            out.push(p.to_string());
+        }
+    }
+    out
+}
+
+impl ComposeFile {
+    /// Drop services whose profiles are not active. Returns the names of
+    /// the excluded services, sorted. Fails if an active service depends
+    /// on an excluded one — `docker compose` would refuse to start it.
+    pub fn apply_profiles(&mut self, active: &[String]) -> Result<Vec<String>, String> {
+        let all = active.iter().any(|p| p == "*");
+
+        let is_active = |svc: &Service| match &svc.profiles {
+            None => true,
+            Some(p) if p.is_empty() => true,
+            Some(p) => all || p.iter().any(|x| active.contains(x)),
+        };
+
+        let mut excluded: Vec<String> = self.services
+            .iter()
+            .filter(|(_, s)| !is_active(s))
+            .map(|(n, _)| n.clone())
+            .collect();
+        excluded.sort();
+
+        for (name, svc) in &self.services {
+            if excluded.contains(name) {
+                continue;
+            }
+            for dep in svc.depends_on.iter().flatten() {
+                if excluded.contains(&dep.service) {
+                    return Err(format!(
+                        "Service '{}' depends on '{}', which is not enabled by the active profiles ({})",
+                        name,
+                        dep.service,
+                        if active.is_empty() { "none".to_string() } else { active.join(", ") }
+                    ));
+                }
+            }
+        }
+
+        self.services.retain(|n, _| !excluded.contains(n));
+
+        Ok(excluded)
+    }
+
+    /// `depends_on` targets that name no defined service, as
+    /// (service, dependency) pairs sorted by service.
+    pub fn undefined_dependencies(&self) -> Vec<(String, String)> {
+        let mut dangling: Vec<(String, String)> = self.services
+            .iter()
+            .flat_map(|(name, svc)| {
+                svc.depends_on.iter().flatten()
+                    .filter(|dep| !self.services.contains_key(&dep.service))
+                    .map(move |dep| (name.clone(), dep.service.clone()))
+            })
+            .collect();
+        dangling.sort();
+        dangling.dedup();
+        dangling
+    }
+}
+
+// ======================================================
+// SERVICE CLASSIFICATION
+// ======================================================
+//
+// Decides whether a clean exit is success (task container) or failure
+// (long-running service). The explicit oneshot label wins; otherwise the
+// compose `restart:` policy is taken as a statement of intent.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceKind {
+    /// `com.rehearsa.oneshot` label — any exit is success.
+    OneshotLabelled,
+    /// Inferred from `restart: "no"` / `on-failure` — exit 0 is success.
+    OneshotInferred,
+    /// Expected to stay up — any exit is failure.
+    LongRunning,
+}
+
+impl std::fmt::Display for ServiceKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceKind::OneshotLabelled | ServiceKind::OneshotInferred => write!(f, "oneshot"),
+            ServiceKind::LongRunning => write!(f, "long-running"),
+        }
+    }
+}
+
+/// A classification together with the reason it was chosen.
+#[derive(Debug, Clone)]
+pub struct ServiceClass {
+    pub kind:   ServiceKind,
+    pub reason: String,
+}
+
+impl Service {
+    pub fn classify(&self) -> ServiceClass {
+        let label = self.labels
+            .as_ref()
+            .and_then(|l| l.get("com.rehearsa.oneshot"))
+            .map(|v| v.as_str());
+
+        match label {
+            Some("true") | Some("1") => return ServiceClass {
+                kind:   ServiceKind::OneshotLabelled,
+                reason: "com.rehearsa.oneshot label".to_string(),
+            },
+            Some("false") | Some("0") => return ServiceClass {
+                kind:   ServiceKind::LongRunning,
+                reason: "com.rehearsa.oneshot=false label".to_string(),
+            },
+            _ => {}
+        }
+
+        match self.restart.as_deref().map(str::trim) {
+            Some(r) if r == "no" || r.starts_with("on-failure") => ServiceClass {
+                kind:   ServiceKind::OneshotInferred,
+                reason: format!("restart: {}", r),
+            },
+            Some(r) => ServiceClass {
+                kind:   ServiceKind::LongRunning,
+                reason: format!("restart: {}", r),
+            },
+            None => ServiceClass {
+                kind:   ServiceKind::LongRunning,
+                reason: "no restart policy".to_string(),
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -29,6 +224,7 @@ pub struct HealthCheck {
     pub interval: Option<String>,
     pub timeout: Option<String>,
     pub retries: Option<u64>,
+    pub start_period: Option<String>,
 }
 
 // ======================================================
@@ -36,8 +232,7 @@ pub struct HealthCheck {
 // ======================================================
 
 pub fn parse_compose(content: &str) -> Result<ComposeFile, String> {
-    let root: serde_yaml::Value =
-        serde_yaml::from_str(content).map_err(|e| format!("YAML parse error: {}", e))?;
+    let root = parse_yaml(content)?;
 
     let services_raw = match root.get("services") {
         Some(serde_yaml::Value::Mapping(m)) => m,
@@ -67,6 +262,8 @@ pub fn parse_compose(content: &str) -> Result<ComposeFile, String> {
             healthcheck: extract_healthcheck(svc_map),
             ports: extract_ports(svc_map),
             labels: extract_labels(svc_map),
+            restart: extract_restart(svc_map),
+            profiles: extract_string_or_list(svc_map, "profiles"),
         };
 
         services.insert(name, service);
@@ -75,6 +272,174 @@ pub fn parse_compose(content: &str) -> Result<ComposeFile, String> {
     Ok(ComposeFile { services })
 }
 
+// ======================================================
+// MERGING
+// ======================================================
+//
+// `docker compose -f base.yml -f override.yml` semantics: the services
+// maps are unioned and each service is merged field by field, with the
+// later file winning for scalars. Keyed fields merge by key whichever
+// form (list or map) either file uses, volumes merge by container path,
+// the append fields below are unioned, and any other list is replaced.
+
+/// Service fields whose lists are unioned rather than replaced.
+const APPEND_FIELDS: &[&str] = &[
+    "ports", "expose", "dns", "dns_search", "tmpfs", "cap_add", "cap_drop",
+    "env_file", "external_links", "security_opt", "profiles",
+];
+
+/// Service fields merged by key; the list form is normalised to a map.
+const KEYED_FIELDS: &[&str] = &["environment", "labels", "depends_on"];
+
+/// Merge `overlay` onto `base` (both raw Compose files) and re-serialise,
+/// so the result parses and interpolates like a single file.
+pub fn merge_compose(base: &str, overlay: &str) -> Result<String, String> {
+    // Merge keys are resolved first so an override lands on the
+    // service as written out, not beside its `<<` defaults.
+    let mut root = parse_yaml(base)?;
+    let overlay = parse_yaml(overlay)?;
+
+    // An empty override file changes nothing.
+    let serde_yaml::Value::Mapping(overlay) = overlay else {
+        return Ok(base.to_string());
+    };
+
+    let serde_yaml::Value::Mapping(top) = &mut root else {
+        return serde_yaml::to_string(&overlay)
+            .map_err(|e| format!("Failed to re-serialise merged Compose file: {}", e));
+    };
+
+    for (key, value) in overlay {
+        match (key.as_str(), top.get_mut(&key), value) {
+            (Some("services"), Some(serde_yaml::Value::Mapping(services)), serde_yaml::Value::Mapping(incoming)) => {
+                for (name, service) in incoming {
+                    match services.get_mut(&name) {
+                        Some(existing) => merge_service(existing, service),
+                        None => { services.insert(name, service); }
+                    }
+                }
+            }
+            (_, Some(existing), value) => merge_value(existing, value),
+            (_, None, value) => { top.insert(key, value); }
+        }
+    }
+
+    serde_yaml::to_string(&root)
+        .map_err(|e| format!("Failed to re-serialise merged Compose file: {}", e))
+}
+
+fn merge_service(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    // `name:` with no body adds nothing.
+    let serde_yaml::Value::Mapping(overlay) = overlay else { return };
+
+    let serde_yaml::Value::Mapping(fields) = base else {
+        *base = serde_yaml::Value::Mapping(overlay);
+        return;
+    };
+
+    for (key, value) in overlay {
+        let field = key.as_str().unwrap_or_default().to_string();
+
+        let Some(existing) = fields.get_mut(&key) else {
+            fields.insert(key, value);
+            continue;
+        };
+
+        if KEYED_FIELDS.contains(&field.as_str()) {
+            let mut merged = keyed_map(existing, &field);
+            for (k, v) in keyed_map(&value, &field) {
+                merged.insert(k, v);
+            }
+            *existing = serde_yaml::Value::Mapping(merged);
+        } else if field == "volumes" {
+            merge_volumes(existing, value);
+        } else if APPEND_FIELDS.contains(&field.as_str()) {
+            match (&mut *existing, value) {
+                (serde_yaml::Value::Sequence(items), serde_yaml::Value::Sequence(incoming)) => {
+                    for item in incoming {
+                        if !items.contains(&item) {
+                            items.push(item);
+                        }
+                    }
+                }
+                (existing, value) => *existing = value,
+            }
+        } else {
+            merge_value(existing, value);
+        }
+    }
+}
+
+/// Maps merge recursively; anything else is replaced by the overlay.
+fn merge_value(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    match (&mut *base, overlay) {
+        (serde_yaml::Value::Mapping(fields), serde_yaml::Value::Mapping(incoming)) => {
+            for (key, value) in incoming {
+                match fields.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => { fields.insert(key, value); }
+                }
+            }
+        }
+        (_, overlay) => *base = overlay,
+    }
+}
+
+/// A keyed field as a map. List-form `depends_on` entries become
+/// `service_started` conditions; `KEY=value` entries split on the first `=`.
+fn keyed_map(value: &serde_yaml::Value, field: &str) -> serde_yaml::Mapping {
+    match value {
+        serde_yaml::Value::Mapping(m) => m.clone(),
+        serde_yaml::Value::Sequence(seq) => seq
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| {
+                if field == "depends_on" {
+                    let mut condition = serde_yaml::Mapping::new();
+                    condition.insert("condition".into(), "service_started".into());
+                    return (s.into(), serde_yaml::Value::Mapping(condition));
+                }
+                match s.split_once('=') {
+                    Some((k, v)) => (k.into(), v.into()),
+                    None         => (s.into(), serde_yaml::Value::Null),
+                }
+            })
+            .collect(),
+        _ => serde_yaml::Mapping::new(),
+    }
+}
+
+/// Volumes keyed by container path: an overlay entry mounting the same
+/// target replaces the base entry in place; new targets are appended.
+fn merge_volumes(base: &mut serde_yaml::Value, overlay: serde_yaml::Value) {
+    let (serde_yaml::Value::Sequence(items), serde_yaml::Value::Sequence(incoming)) = (&mut *base, &overlay) else {
+        *base = overlay;
+        return;
+    };
+
+    for volume in incoming {
+        let target = volume_target(volume);
+        match items.iter_mut().find(|v| target.is_some() && volume_target(v) == target) {
+            Some(existing) => *existing = volume.clone(),
+            None => items.push(volume.clone()),
+        }
+    }
+}
+
+/// Container path of a short (`src:dst[:mode]` or anonymous `dst`) or
+/// long-syntax (`target:`) volume entry.
+fn volume_target(volume: &serde_yaml::Value) -> Option<String> {
+    match volume {
+        serde_yaml::Value::String(s) => {
+            let mut parts = s.split(':');
+            let first = parts.next()?;
+            Some(parts.next().unwrap_or(first).to_string())
+        }
+        serde_yaml::Value::Mapping(m) => m.get("target").and_then(value_to_string),
+        _ => None,
+    }
+}
+
 // ======================================================
 // FIELD EXTRACTORS
 // ======================================================
@@ -135,9 +500,6 @@ fn extract_environment(map: &serde_yaml::Mapping) -> Option<Vec<String>> {
         Some(serde_yaml::Value::Mapping(m)) => {
             let mut out = Vec::new();
             for (k, v) in m {
-                if k.as_str() == Some("<<") {
-                    continue;
-                }
                 let key = match k.as_str() {
                     Some(s) => s,
                     None => continue,
@@ -160,22 +522,32 @@ fn extract_environment(map: &serde_yaml::Mapping) -> Option<Vec<String>> {
     }
 }
 
-fn extract_depends_on(map: &serde_yaml::Mapping) -> Option<Vec<String>> {
+fn extract_depends_on(map: &serde_yaml::Mapping) -> Option<Vec<Dependency>> {
     match map.get("depends_on") {
         None | Some(serde_yaml::Value::Null) => None,
 
         Some(serde_yaml::Value::Sequence(seq)) => {
-            let out: Vec<String> = seq
+            let out: Vec<Dependency> = seq
                 .iter()
-                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .filter_map(|v| v.as_str())
+                .map(|s| Dependency {
+                    service:   s.to_string(),
+                    condition: DependsCondition::Started,
+                })
                 .collect();
             if out.is_empty() { None } else { Some(out) }
         }
 
         Some(serde_yaml::Value::Mapping(m)) => {
-            let out: Vec<String> = m
+            let out: Vec<Dependency> = m
                 .iter()
-                .filter_map(|(k, _)| k.as_str().map(|s| s.to_string()))
+                .filter_map(|(k, v)| {
+                    let condition = v
+                        .get("condition")
+                        .and_then(|c| c.as_str())
+                        .map_or(DependsCondition::Started, DependsCondition::parse);
+                    k.as_str().map(|s| Dependency { service: s.to_string(), condition })
+                })
                 .collect();
             if out.is_empty() { None } else { Some(out) }
         }
@@ -209,8 +581,9 @@ fn extract_healthcheck(map: &serde_yaml::Mapping) -> Option<HealthCheck> {
     let interval = hc.get("interval").and_then(|v| v.as_str()).map(|s| s.to_string());
     let timeout  = hc.get("timeout").and_then(|v| v.as_str()).map(|s| s.to_string());
     let retries  = hc.get("retries").and_then(|v| v.as_u64());
+    let start_period = hc.get("start_period").and_then(|v| v.as_str()).map(|s| s.to_string());
 
-    Some(HealthCheck { test, interval, timeout, retries })
+    Some(HealthCheck { test, interval, timeout, retries, start_period })
 }
 
 fn extract_ports(map: &serde_yaml::Mapping) -> Option<Vec<String>> {
@@ -239,6 +612,20 @@ fn extract_ports(map: &serde_yaml::Mapping) -> Option<Vec<String>> {
     }
 }
 
+// ======================================================
+// RESTART EXTRACTOR
+// ======================================================
+
+/// Some files write `restart: false` (or YAML 1.1 tooling emitted it from
+/// a bare `no`) — normalise that back to "no".
+fn extract_restart(map: &serde_yaml::Mapping) -> Option<String> {
+    match map.get("restart") {
+        Some(serde_yaml::Value::Bool(false)) => Some("no".to_string()),
+        Some(v) => value_to_string(v),
+        None => None,
+    }
+}
+
 // ======================================================
 // LABELS EXTRACTOR
 // ======================================================
@@ -284,6 +671,17 @@ fn extract_labels(map: &serde_yaml::Mapping) -> Option<std::collections::HashMap
 // HELPERS
 // ======================================================
 
+/// Parse Compose YAML with `<<` merge keys applied, so anchored defaults
+/// (`<<: *defaults`) read as if written out in place. Aliases themselves
+/// are expanded by the parser.
+fn parse_yaml(content: &str) -> Result<serde_yaml::Value, String> {
+    let mut root: serde_yaml::Value =
+        serde_yaml::from_str(content).map_err(|e| format!("YAML parse error: {}", e))?;
+    root.apply_merge()
+        .map_err(|e| format!("YAML merge key error: {}", e))?;
+    Ok(root)
+}
+
 fn value_to_string(v: &serde_yaml::Value) -> Option<String> {
     match v {
         serde_yaml::Value::String(s) => Some(s.clone()),
@@ -293,6 +691,196 @@ fn value_to_string(v: &serde_yaml::Value) -> Option<String> {
     }
 }
 
+// ======================================================
+// VARIABLE INTERPOLATION
+// ======================================================
+//
+// Substitutes `${VAR}`, `${VAR:-default}` and `${VAR:?error}` (plus the
+// colon-less `-`/`?` forms) in every string value, the way Compose does
+// before it reads the file. `$$` is a literal `$`. Runs on the parsed
+// YAML tree so each unset variable can be attributed to its service;
+// the result is re-serialised so every later parse sees plain values.
+
+/// A variable referenced without a default that is not set on this host.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnsetVariable {
+    /// Service the reference appears in; None for top-level blocks.
+    pub service: Option<String>,
+    pub name:    String,
+}
+
+#[derive(Debug)]
+pub struct Interpolated {
+    pub content: String,
+    pub unset:   Vec<UnsetVariable>,
+}
+
+/// Interpolate a raw Compose file. Errors on a `:?`/`?` reference to an
+/// unset variable, mirroring `docker compose`.
+pub fn interpolate_compose(
+    content: &str,
+    env: &HashMap<String, String>,
+) -> Result<Interpolated, String> {
+    let mut root = parse_yaml(content)?;
+
+    let mut unset = Vec::new();
+
+    if let serde_yaml::Value::Mapping(top) = &mut root {
+        for (key, value) in top.iter_mut() {
+            match (key.as_str(), value) {
+                (Some("services"), serde_yaml::Value::Mapping(services)) => {
+                    for (name, service) in services.iter_mut() {
+                        let name = name.as_str().map(str::to_string);
+                        interpolate_value(service, env, name.as_deref(), &mut unset)?;
+                    }
+                }
+                (_, value) => interpolate_value(value, env, None, &mut unset)?,
+            }
+        }
+    }
+
+    unset.sort();
+    unset.dedup();
+
+    let content = serde_yaml::to_string(&root)
+        .map_err(|e| format!("Failed to re-serialise interpolated Compose file: {}", e))?;
+
+    Ok(Interpolated { content, unset })
+}
+
+fn interpolate_value(
+    value: &mut serde_yaml::Value,
+    env: &HashMap<String, String>,
+    service: Option<&str>,
+    unset: &mut Vec<UnsetVariable>,
+) -> Result<(), String> {
+    match value {
+        serde_yaml::Value::String(s) => {
+            *s = interpolate_str(s, env, service, unset)?;
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                interpolate_value(item, env, service, unset)?;
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (_, v) in map.iter_mut() {
+                interpolate_value(v, env, service, unset)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => {
+            interpolate_value(&mut tagged.value, env, service, unset)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(
+    input: &str,
+    env: &HashMap<String, String>,
+    service: Option<&str>,
+    unset: &mut Vec<UnsetVariable>,
+) -> Result<String, String> {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+
+        if let Some(r) = after.strip_prefix('$') {
+            out.push('$');
+            rest = r;
+            continue;
+        }
+
+        let body = match after.strip_prefix('{') {
+            Some(b) => b,
+            None => {
+                out.push('$');
+                rest = after;
+                continue;
+            }
+        };
+
+        // Match the closing brace, allowing nested ${...} in defaults.
+        let mut depth = 1;
+        let mut end = None;
+        for (i, c) in body.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        match end {
+            Some(end) => {
+                out.push_str(&resolve_variable(&body[..end], env, service, unset)?);
+                rest = &body[end + 1..];
+            }
+            None => {
+                // Unterminated — keep it literally.
+                out.push('$');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn resolve_variable(
+    expr: &str,
+    env: &HashMap<String, String>,
+    service: Option<&str>,
+    unset: &mut Vec<UnsetVariable>,
+) -> Result<String, String> {
+    let name_len = expr
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(expr.len());
+    let (name, op) = expr.split_at(name_len);
+    let value = env.get(name);
+
+    // `:-` / `:?` treat an empty value as unset; `-` / `?` only a missing one.
+    let (missing, arg) = if let Some(arg) = op.strip_prefix(":-").or_else(|| op.strip_prefix(":?")) {
+        (value.is_none_or(|v| v.is_empty()), Some(arg))
+    } else if let Some(arg) = op.strip_prefix('-').or_else(|| op.strip_prefix('?')) {
+        (value.is_none(), Some(arg))
+    } else {
+        (value.is_none(), None)
+    };
+
+    if !missing {
+        return Ok(value.cloned().unwrap_or_default());
+    }
+
+    match (op.trim_start_matches(':').chars().next(), arg) {
+        (Some('-'), Some(default)) => interpolate_str(default, env, service, unset),
+        (Some('?'), Some(message)) => Err(format!(
+            "Required variable '{}' is not set{}{}",
+            name,
+            service.map(|s| format!(" (service '{}')", s)).unwrap_or_default(),
+            if message.is_empty() { String::new() } else { format!(": {}", message) }
+        )),
+        _ => {
+            unset.push(UnsetVariable {
+                service: service.map(str::to_string),
+                name:    name.to_string(),
+            });
+            Ok(String::new())
+        }
+    }
+}
+
 // ======================================================
 // NETWORK EXTRACTION (top-level)
 // ======================================================
@@ -300,7 +888,7 @@ fn value_to_string(v: &serde_yaml::Value) -> Option<String> {
 /// Extract top-level external network names from the Compose file.
 /// Returns names of networks declared as external: true.
 pub fn extract_external_networks(content: &str) -> Vec<String> {
-    let root: serde_yaml::Value = match serde_yaml::from_str(content) {
+    let root = match parse_yaml(content) {
         Ok(v) => v,
         Err(_) => return vec![],
     };
@@ -334,3 +922,320 @@ pub fn extract_external_networks(content: &str) -> Vec<String> {
 
     external
 }
+
+// ======================================================
+// VOLUME EXTRACTION (top-level)
+// ======================================================
+
+/// A top-level `volumes:` entry.
+#[derive(Debug, Clone)]
+pub struct NamedVolume {
+    /// Key the services refer to the volume by.
+    pub key:      String,
+    /// Docker-side name: `name:` (or legacy `external: {name: ...}`)
+    /// when given, else the key.
+    pub name:     String,
+    /// Declared `external: true` — must already exist on the host.
+    pub external: bool,
+}
+
+/// Extract the top-level named volumes, sorted by key.
+pub fn extract_named_volumes(content: &str) -> Vec<NamedVolume> {
+    let root = match parse_yaml(content) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+
+    let volumes = match root.get("volumes") {
+        Some(serde_yaml::Value::Mapping(m)) => m,
+        _ => return vec![],
+    };
+
+    let mut out: Vec<NamedVolume> = volumes
+        .iter()
+        .filter_map(|(k, v)| {
+            let key = k.as_str()?.to_string();
+
+            let (external, legacy_name) = match v.get("external") {
+                Some(serde_yaml::Value::Bool(b)) => (*b, None),
+                Some(serde_yaml::Value::Mapping(m)) => (true, m.get("name").and_then(value_to_string)),
+                _ => (false, None),
+            };
+
+            let name = v.get("name")
+                .and_then(value_to_string)
+                .or(legacy_name)
+                .unwrap_or_else(|| key.clone());
+
+            Some(NamedVolume { key, name, external })
+        })
+        .collect();
+
+    out.sort_by(|a, b| a.key.cmp(&b.key));
+    out
+}
+
+// ======================================================
+// SCHEMA VERSION (top-level)
+// ======================================================
+
+/// Extract the top-level `version` declared in the Compose file, if any.
+/// Accepts both quoted (`"3.8"`) and bare numeric (`3.8`) forms.
+pub fn extract_compose_version(content: &str) -> Option<String> {
+    let root = parse_yaml(content).ok()?;
+    root.get("version").and_then(value_to_string)
+}
+
+/// Extract each service's top-level keys, sorted by service name.
+/// Used by rules that check schema compatibility of service directives.
+pub fn extract_service_keys(content: &str) -> Vec<(String, Vec<String>)> {
+    let root = match parse_yaml(content) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+
+    let services = match root.get("services") {
+        Some(serde_yaml::Value::Mapping(m)) => m,
+        _ => return vec![],
+    };
+
+    let mut out: Vec<(String, Vec<String>)> = services
+        .iter()
+        .filter_map(|(k, v)| {
+            let name = k.as_str()?.to_string();
+            let keys = match v {
+                serde_yaml::Value::Mapping(m) => m
+                    .keys()
+                    .filter_map(|k| k.as_str().map(|s| s.to_string()))
+                    .collect(),
+                _ => vec![],
+            };
+            Some((name, keys))
+        })
+        .collect();
+
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+/// SHA-256 of each service's definition, keyed by service name. Mapping
+/// keys are sorted before hashing, so reordering a service's YAML is not
+/// a change. Used by `stack test --changed-only`.
+pub fn service_config_hashes(content: &str) -> BTreeMap<String, String> {
+    let root = match parse_yaml(content) {
+        Ok(v) => v,
+        Err(_) => return BTreeMap::new(),
+    };
+
+    let services = match root.get("services") {
+        Some(serde_yaml::Value::Mapping(m)) => m,
+        _ => return BTreeMap::new(),
+    };
+
+    services
+        .iter()
+        .filter_map(|(k, v)| {
+            let mut hasher = Sha256::new();
+            hasher.update(canonical_yaml(v).as_bytes());
+            Some((k.as_str()?.to_string(), format!("{:x}", hasher.finalize())))
+        })
+        .collect()
+}
+
+/// Order-independent serialization of a YAML value.
+fn canonical_yaml(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::Mapping(m) => {
+            let mut entries: Vec<(String, String)> = m
+                .iter()
+                .map(|(k, v)| (canonical_yaml(k), canonical_yaml(v)))
+                .collect();
+            entries.sort();
+            let body: Vec<String> = entries.into_iter().map(|(k, v)| format!("{}:{}", k, v)).collect();
+            format!("{{{}}}", body.join(","))
+        }
+        serde_yaml::Value::Sequence(s) => {
+            let items: Vec<String> = s.iter().map(canonical_yaml).collect();
+            format!("[{}]", items.join(","))
+        }
+        serde_yaml::Value::Tagged(t) => format!("!{}{}", t.tag, canonical_yaml(&t.value)),
+        scalar => serde_json::to_string(scalar).unwrap_or_default(),
+    }
+}
+
+/// Names of services whose `depends_on` uses the long form with a `condition`.
+pub fn extract_depends_on_conditions(content: &str) -> Vec<String> {
+    let root = match parse_yaml(content) {
+        Ok(v) => v,
+        Err(_) => return vec![],
+    };
+
+    let services = match root.get("services") {
+        Some(serde_yaml::Value::Mapping(m)) => m,
+        _ => return vec![],
+    };
+
+    let mut out: Vec<String> = services
+        .iter()
+        .filter_map(|(k, v)| {
+            let deps = match v.get("depends_on") {
+                Some(serde_yaml::Value::Mapping(m)) => m,
+                _ => return None,
+            };
+            let has_condition = deps
+                .values()
+                .any(|d| d.get("condition").is_some());
+            if has_condition { k.as_str().map(|s| s.to_string()) } else { None }
+        })
+        .collect();
+
+    out.sort();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = r#"
+services:
+  app:
+    image: app:1.0
+    environment:
+      - A=1
+      - B=2
+    labels:
+      tier: web
+    depends_on:
+      - db
+  db:
+    image: postgres:16
+"#;
+
+    fn merged(overlay: &str) -> ComposeFile {
+        parse_compose(&merge_compose(BASE, overlay).unwrap()).unwrap()
+    }
+
+    fn sorted(items: &Option<Vec<String>>) -> Vec<String> {
+        let mut items = items.clone().unwrap_or_default();
+        items.sort();
+        items
+    }
+
+    fn dependencies(service: &Service) -> Vec<(String, DependsCondition)> {
+        let mut deps: Vec<(String, DependsCondition)> = service.depends_on
+            .iter()
+            .flatten()
+            .map(|d| (d.service.clone(), d.condition))
+            .collect();
+        deps.sort_by(|a, b| a.0.cmp(&b.0));
+        deps
+    }
+
+    #[test]
+    fn overlay_adds_a_service() {
+        let compose = merged("services:\n  cache:\n    image: redis:7\n");
+
+        assert_eq!(compose.services.len(), 3);
+        assert_eq!(compose.services["cache"].image.as_deref(), Some("redis:7"));
+        assert_eq!(compose.services["app"].image.as_deref(), Some("app:1.0"));
+    }
+
+    #[test]
+    fn overlay_replaces_image() {
+        let compose = merged("services:\n  app:\n    image: app:2.0\n");
+
+        assert_eq!(compose.services["app"].image.as_deref(), Some("app:2.0"));
+        assert_eq!(sorted(&compose.services["app"].environment), ["A=1", "B=2"]);
+    }
+
+    #[test]
+    fn keyed_fields_merge_by_key_across_list_and_map_forms() {
+        let compose = merged(r#"
+services:
+  app:
+    environment:
+      B: "3"
+      C: "4"
+    labels:
+      - owner=ops
+    depends_on:
+      cache:
+        condition: service_healthy
+"#);
+        let app = &compose.services["app"];
+
+        assert_eq!(sorted(&app.environment), ["A=1", "B=3", "C=4"]);
+
+        let labels = app.labels.as_ref().unwrap();
+        assert_eq!(labels.get("tier").map(String::as_str), Some("web"));
+        assert_eq!(labels.get("owner").map(String::as_str), Some("ops"));
+
+        assert_eq!(
+            dependencies(app),
+            [
+                ("cache".to_string(), DependsCondition::Healthy),
+                ("db".to_string(), DependsCondition::Started),
+            ]
+        );
+    }
+
+    const ANCHORED: &str = r#"
+x-defaults: &defaults
+  image: app:1.0
+  restart: unless-stopped
+  environment:
+    LOG_LEVEL: info
+    TZ: UTC
+
+services:
+  api:
+    <<: *defaults
+    environment:
+      LOG_LEVEL: debug
+  worker:
+    <<: *defaults
+"#;
+
+    #[test]
+    fn merge_keys_expose_inherited_and_overridden_fields() {
+        let compose = parse_compose(ANCHORED).unwrap();
+
+        let api = &compose.services["api"];
+        assert_eq!(api.image.as_deref(), Some("app:1.0"));
+        assert_eq!(api.restart.as_deref(), Some("unless-stopped"));
+        assert_eq!(sorted(&api.environment), ["LOG_LEVEL=debug"]);
+
+        let worker = &compose.services["worker"];
+        assert_eq!(worker.image.as_deref(), Some("app:1.0"));
+        assert_eq!(sorted(&worker.environment), ["LOG_LEVEL=info", "TZ=UTC"]);
+    }
+
+    #[test]
+    fn override_lands_on_a_merged_service() {
+        let overlay = r#"
+services:
+  worker:
+    image: app:2.0
+    environment:
+      TZ: Europe/Berlin
+"#;
+        let compose = parse_compose(&merge_compose(ANCHORED, overlay).unwrap()).unwrap();
+
+        let worker = &compose.services["worker"];
+        assert_eq!(worker.image.as_deref(), Some("app:2.0"));
+        assert_eq!(worker.restart.as_deref(), Some("unless-stopped"));
+        assert_eq!(sorted(&worker.environment), ["LOG_LEVEL=info", "TZ=Europe/Berlin"]);
+
+        assert_eq!(compose.services["api"].image.as_deref(), Some("app:1.0"));
+    }
+
+    #[test]
+    fn empty_overlay_is_a_no_op() {
+        assert_eq!(merge_compose(BASE, "").unwrap(), BASE);
+
+        let compose = merged("");
+        assert_eq!(compose.services.len(), 2);
+        assert_eq!(sorted(&compose.services["app"].environment), ["A=1", "B=2"]);
+    }
+}
@@ -0,0 +1,104 @@
+use anyhow::{anyhow, Result};
+use bollard::{Docker, API_DEFAULT_VERSION};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+// ======================================================
+// CONTAINER ENGINE CONNECTION
+// ======================================================
+//
+// Every Docker API client is built here, so the engine is chosen in one
+// place. Unset, Rehearsa talks to the local Docker socket. A host URI
+// (from --docker-host or REHEARSA_CONTAINER_HOST, else DOCKER_HOST)
+// points it elsewhere — a Podman socket, or a remote daemon over TCP.
+// DOCKER_TLS_VERIFY / DOCKER_CERT_PATH follow the docker CLI's meaning.
+
+/// Read/write timeout for every API call, as bollard's own defaults use.
+const CONNECT_TIMEOUT_SECS: u64 = 120;
+
+static CONTAINER_HOST: OnceLock<Option<String>> = OnceLock::new();
+
+/// Set the engine URI for this process. First call wins.
+pub fn set_container_host(host: Option<String>) {
+    let _ = CONTAINER_HOST.set(host.filter(|h| !h.trim().is_empty()));
+}
+
+/// The engine URI in effect, or None for the local socket.
+pub fn container_host() -> Option<String> {
+    CONTAINER_HOST.get().cloned().flatten()
+        .or_else(|| std::env::var("DOCKER_HOST").ok().filter(|h| !h.trim().is_empty()))
+}
+
+fn is_tcp(host: &str) -> bool {
+    host.starts_with("tcp://") || host.starts_with("http://") || host.starts_with("https://")
+}
+
+/// Identity of a remote engine, safe for use in a file name
+/// (`tcp://10.0.0.5:2376` → `10.0.0.5_2376`). None for local sockets.
+pub fn remote_host_id() -> Option<String> {
+    let host = container_host().filter(|h| is_tcp(h))?;
+    let bare = host.split_once("://").map(|(_, rest)| rest).unwrap_or(&host);
+    Some(
+        bare.trim_end_matches('/')
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' })
+            .collect(),
+    )
+}
+
+/// DOCKER_TLS_VERIFY set to anything but empty or "0".
+fn tls_requested() -> bool {
+    std::env::var("DOCKER_TLS_VERIFY").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Client key, certificate and CA from DOCKER_CERT_PATH (default ~/.docker).
+fn tls_files() -> Result<(PathBuf, PathBuf, PathBuf)> {
+    let dir = match std::env::var("DOCKER_CERT_PATH") {
+        Ok(p) if !p.is_empty() => PathBuf::from(p),
+        _ => dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not determine home directory for ~/.docker"))?
+            .join(".docker"),
+    };
+
+    let files = (dir.join("key.pem"), dir.join("cert.pem"), dir.join("ca.pem"));
+    for f in [&files.0, &files.1, &files.2] {
+        if !f.exists() {
+            return Err(anyhow!(
+                "TLS requested but {} is missing (set DOCKER_CERT_PATH)",
+                f.display()
+            ));
+        }
+    }
+    Ok(files)
+}
+
+/// Connect to the configured container engine.
+///
+/// Accepts `unix:///path/to.sock`, a bare socket path, `tcp://` /
+/// `http://host:port`, or `https://host:port`. TCP hosts use TLS when
+/// the scheme is https or DOCKER_TLS_VERIFY is set.
+pub fn connect_docker() -> Result<Docker> {
+    let host = container_host();
+
+    let docker = match host.as_deref() {
+        None => Docker::connect_with_local_defaults()?,
+        Some(h) if h.starts_with("unix://") || h.starts_with('/') => {
+            Docker::connect_with_socket(h, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+        }
+        Some(h) if h.starts_with("https://") || (h.starts_with("tcp://") && tls_requested()) => {
+            let (key, cert, ca) = tls_files()?;
+            Docker::connect_with_ssl(h, &key, &cert, &ca, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+        }
+        Some(h) if h.starts_with("tcp://") || h.starts_with("http://") => {
+            Docker::connect_with_http(h, CONNECT_TIMEOUT_SECS, API_DEFAULT_VERSION)?
+        }
+        Some(h) => {
+            return Err(anyhow!(
+                "Unsupported container host '{}'. Use unix:///path/to.sock, tcp://host:port or https://host:port",
+                h
+            ));
+        }
+    };
+
+    Ok(docker)
+}
@@ -0,0 +1,83 @@
+use anyhow::Result;
+use bollard::Docker;
+use bollard::image::CreateImageOptions;
+use futures_util::stream::TryStreamExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+
+// ======================================================
+// PER-RUN IMAGE CACHE
+// ======================================================
+//
+// One rehearsal reaches each image from two places: ImagePullRule pulls
+// every image to prove a fresh host could fetch it, and each service
+// start inspects or pulls its own. A base image shared by five services
+// would otherwise be handled ten times. The cache remembers images
+// already confirmed present, so each unique image is pulled (or
+// inspected) once per run, even when services sharing it start in
+// parallel.
+
+#[derive(Default)]
+pub struct ImageCache {
+    images: Mutex<HashMap<String, Arc<OnceCell<()>>>>,
+}
+
+impl ImageCache {
+    /// Run `confirm` unless the image is already known to be present.
+    /// Concurrent callers for one image share a single attempt; a failed
+    /// attempt is not remembered, so the next caller tries again.
+    pub async fn ensure<F, Fut>(&self, image: &str, confirm: F) -> Result<()>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let cell = self.images
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(image.to_string())
+            .or_default()
+            .clone();
+
+        cell.get_or_try_init(confirm).await.map(|_| ())
+    }
+}
+
+// ======================================================
+// IMAGE PULL
+// ======================================================
+
+/// Split an image reference into the `fromImage` / `tag` pair the pull
+/// API expects. A digest stays in `fromImage`; an untagged image pulls
+/// `latest` rather than every tag.
+fn pull_reference(image: &str) -> (&str, &str) {
+    if image.contains('@') {
+        return (image, "");
+    }
+
+    // A registry port (`host:5000/app`) is not a tag.
+    let name_start = image.rfind('/').map_or(0, |i| i + 1);
+    match image[name_start..].rfind(':') {
+        Some(i) => (&image[..name_start + i], &image[name_start + i + 1..]),
+        None    => (image, "latest"),
+    }
+}
+
+/// Pull exactly the image a service references.
+pub async fn pull_image(docker: &Docker, image: &str) -> Result<()> {
+    let (from_image, tag) = pull_reference(image);
+
+    let options = Some(CreateImageOptions {
+        from_image,
+        tag,
+        ..Default::default()
+    });
+
+    docker
+        .create_image(options, None, None)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    Ok(())
+}
@@ -1,9 +1,8 @@
-use bollard::Docker;
 use bollard::container::ListContainersOptions;
 
 #[allow(dead_code)]
 pub async fn list_containers() -> Result<(), Box<dyn std::error::Error>> {
-    let docker = Docker::connect_with_local_defaults()?;
+    let docker = super::connect::connect_docker()?;
 
     let options = Some(ListContainersOptions::<String> {
         all: true,
@@ -1,2 +1,5 @@
+pub mod clone;
 pub mod compose;
+pub mod connect;
+pub mod images;
 pub mod list;
@@ -4,42 +4,141 @@ pub fn topological_sort(
     services: &HashMap<String, Vec<String>>,
 ) -> Result<Vec<String>, String> {
     let mut visited = HashSet::new();
-    let mut temp = HashSet::new();
+    let mut path = Vec::new();
     let mut result = Vec::new();
 
-    for node in services.keys() {
-        visit(node, services, &mut visited, &mut temp, &mut result)?;
+    // Sorted so a cycle is reported from the same node every run.
+    let mut nodes: Vec<&String> = services.keys().collect();
+    nodes.sort();
+
+    for node in nodes {
+        visit(node, services, &mut visited, &mut path, &mut result)?;
     }
 
     Ok(result)
 }
 
+/// Depth-first visit. `path` holds the chain of services currently being
+/// visited, so re-entering one of them yields the whole cycle.
 fn visit(
     node: &str,
     services: &HashMap<String, Vec<String>>,
     visited: &mut HashSet<String>,
-    temp: &mut HashSet<String>,
+    path: &mut Vec<String>,
     result: &mut Vec<String>,
 ) -> Result<(), String> {
     if visited.contains(node) {
         return Ok(());
     }
 
-    if temp.contains(node) {
-        return Err(format!("Circular dependency detected at {}", node));
+    if let Some(start) = path.iter().position(|n| n == node) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(node.to_string());
+        return Err(format!("Circular dependency detected: {}", cycle.join(" -> ")));
     }
 
-    temp.insert(node.to_string());
+    path.push(node.to_string());
 
     if let Some(deps) = services.get(node) {
         for dep in deps {
-            visit(dep, services, visited, temp, result)?;
+            visit(dep, services, visited, path, result)?;
         }
     }
 
-    temp.remove(node);
+    path.pop();
     visited.insert(node.to_string());
     result.push(node.to_string());
 
     Ok(())
 }
+
+/// Group services into startup levels. Every service's dependencies sit
+/// in earlier levels, so the services within one level can start together.
+pub fn dependency_levels(
+    services: &HashMap<String, Vec<String>>,
+) -> Result<Vec<Vec<String>>, String> {
+    let order = topological_sort(services)?;
+
+    let mut level_of: HashMap<&str, usize> = HashMap::new();
+    let mut levels: Vec<Vec<String>> = Vec::new();
+
+    for node in &order {
+        let level = services
+            .get(node)
+            .into_iter()
+            .flatten()
+            .map(|dep| level_of.get(dep.as_str()).map_or(0, |l| l + 1))
+            .max()
+            .unwrap_or(0);
+
+        level_of.insert(node, level);
+
+        if levels.len() <= level {
+            levels.resize(level + 1, Vec::new());
+        }
+        levels[level].push(node.clone());
+    }
+
+    for level in &mut levels {
+        level.sort();
+    }
+
+    Ok(levels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        edges
+            .iter()
+            .map(|(name, deps)| (name.to_string(), deps.iter().map(|d| d.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn reports_full_three_node_cycle() {
+        let services = graph(&[("a", &["b"]), ("b", &["c"]), ("c", &["a"])]);
+
+        let err = topological_sort(&services).unwrap_err();
+
+        assert_eq!(err, "Circular dependency detected: a -> b -> c -> a");
+    }
+
+    #[test]
+    fn reports_only_the_cycle_not_the_path_into_it() {
+        let services = graph(&[("a", &["b"]), ("b", &["c"]), ("c", &["b"])]);
+
+        let err = topological_sort(&services).unwrap_err();
+
+        assert_eq!(err, "Circular dependency detected: b -> c -> b");
+    }
+
+    #[test]
+    fn reports_self_dependency() {
+        let services = graph(&[("app", &[]), ("worker", &["worker"])]);
+
+        let err = topological_sort(&services).unwrap_err();
+
+        assert_eq!(err, "Circular dependency detected: worker -> worker");
+    }
+
+    #[test]
+    fn acyclic_graph_orders_dependencies_first() {
+        let services = graph(&[
+            ("web", &["app"]),
+            ("app", &["db", "cache"]),
+            ("db", &[]),
+            ("cache", &[]),
+        ]);
+
+        let order = topological_sort(&services).unwrap();
+
+        assert_eq!(order, ["db", "cache", "app", "web"]);
+        assert_eq!(
+            dependency_levels(&services).unwrap(),
+            [vec!["cache", "db"], vec!["app"], vec!["web"]]
+        );
+    }
+}
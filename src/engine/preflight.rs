@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use bollard::Docker;
-use bollard::image::CreateImageOptions;
-use futures_util::stream::TryStreamExt;
-use std::collections::HashMap;
-use std::path::Path;
+use serde::{Serialize, Deserialize};
+use sha2::{Sha256, Digest};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
 
-use crate::docker::compose::ComposeFile;
+use crate::docker::compose::{ComposeFile, UnsetVariable};
+use crate::docker::images::{pull_image, ImageCache};
 
 // ======================================================
 // CONTEXT
@@ -21,13 +23,127 @@ pub struct PreflightContext<'a> {
     /// Used by EnvVarRule to detect variables referenced in compose
     /// but absent from the restore host.
     pub environment:  HashMap<String, String>,
+    /// `${VAR}` references left without a value during interpolation.
+    pub unset_variables: Vec<UnsetVariable>,
+    /// Per-stack preflight settings (penalty caps).
+    pub config:       PreflightConfig,
+    /// Path of the Compose file under test.
+    pub compose_path: PathBuf,
+    /// Where bind sources are cloned to. None skips DiskSpaceRule
+    /// (`--skip-disk-check`, or cloning disabled).
+    pub clone_target: Option<PathBuf>,
+    /// Images pulled this run; service startup reuses it.
+    pub images:       &'a ImageCache,
+}
+
+// ======================================================
+// PER-STACK CONFIG
+// ======================================================
+//
+// Stored at ~/.rehearsa/preflight/<stack>.json. Absent file means
+// defaults: every rule uncapped, nothing suppressed.
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PreflightConfig {
+    /// Maximum total penalty a rule may contribute, keyed by rule name
+    /// (e.g. "EnvVarRule": 30). Rules not listed are uncapped.
+    #[serde(default)]
+    pub penalty_caps: BTreeMap<String, u32>,
+    /// Finding IDs accepted by the operator. Matching findings are
+    /// downgraded to Info with zero penalty and marked acknowledged.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub suppressed: BTreeSet<String>,
+}
+
+fn preflight_config_path(stack: &str) -> Result<PathBuf, String> {
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+
+    let dir = home.join(".rehearsa").join("preflight");
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create preflight dir: {}", e))?;
+    }
+
+    Ok(dir.join(format!("{}.json", stack)))
+}
+
+pub fn load_preflight_config(stack: &str) -> PreflightConfig {
+    let path = match preflight_config_path(stack) {
+        Ok(p) => p,
+        Err(_) => return PreflightConfig::default(),
+    };
+
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_preflight_config(stack: &str, config: &PreflightConfig) -> Result<(), String> {
+    let path = preflight_config_path(stack)?;
+
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| format!("Failed to serialize preflight config: {}", e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write preflight config: {}", e))
+}
+
+/// Set (`Some`) or clear (`None`) the penalty cap for one rule.
+pub fn set_penalty_cap(stack: &str, rule: &str, cap: Option<u32>) -> Result<(), String> {
+    if !rule_names().contains(&rule) {
+        return Err(format!(
+            "Unknown preflight rule '{}'. Known rules: {}",
+            rule,
+            rule_names().join(", ")
+        ));
+    }
+
+    let mut config = load_preflight_config(stack);
+
+    match cap {
+        Some(c) => { config.penalty_caps.insert(rule.to_string(), c); }
+        None    => { config.penalty_caps.remove(rule); }
+    }
+
+    save_preflight_config(stack, &config)
+}
+
+/// Add (`true`) or remove (`false`) a finding ID from the suppression list.
+/// Returns false when the list was already in the requested state.
+pub fn set_suppressed(stack: &str, id: &str, suppressed: bool) -> Result<bool, String> {
+    let id = id.trim().to_lowercase();
+
+    if id.len() != 12 || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Invalid finding ID '{}' — expected the 12-character ID shown next to each finding",
+            id
+        ));
+    }
+
+    let mut config = load_preflight_config(stack);
+
+    let changed = if suppressed {
+        config.suppressed.insert(id)
+    } else {
+        config.suppressed.remove(&id)
+    };
+
+    if changed {
+        save_preflight_config(stack, &config)?;
+    }
+
+    Ok(changed)
 }
 
 // ======================================================
 // SEVERITY
 // ======================================================
 
-#[derive(Debug, Clone)]
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// Informational — no score penalty. Used for advisories.
     Info,
@@ -37,6 +153,16 @@ pub enum Severity {
     Critical,
 }
 
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Info     => write!(f, "INFO"),
+            Severity::Warning  => write!(f, "WARNING"),
+            Severity::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
 // ======================================================
 // FINDING
 // ======================================================
@@ -45,9 +171,25 @@ pub enum Severity {
 pub struct PreflightFinding {
     /// Name of the rule that produced this finding.
     pub rule:     &'static str,
+    /// What the finding is about (service, path, variable, ...).
+    /// Together with `rule` it forms the stable finding ID.
+    pub subject:  String,
     pub severity: Severity,
     pub message:  String,
     pub penalty:  u32,
+    /// True when the finding matched the stack's suppression list.
+    pub acknowledged: bool,
+}
+
+impl PreflightFinding {
+    /// Stable 12-character ID, independent of message wording.
+    pub fn id(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.rule.as_bytes());
+        hasher.update(b"|");
+        hasher.update(self.subject.as_bytes());
+        format!("{:x}", hasher.finalize())[..12].to_string()
+    }
 }
 
 // ======================================================
@@ -105,24 +247,28 @@ impl PreflightRule for BindMountRule {
                         if !Path::new(host_path).exists() {
                             findings.push(PreflightFinding {
                                 rule:     self.name(),
+                                subject:  format!("{}:{}:missing", service_name, host_path),
                                 severity: Severity::Critical,
                                 message:  format!(
                                     "Service '{}' references missing bind path: {}",
                                     service_name, host_path
                                 ),
                                 penalty: 25,
+                                acknowledged: false,
                             });
                         } else {
                             // Path exists but bind mounts are still a restore risk —
                             // the data at that path may not have been restored yet.
                             findings.push(PreflightFinding {
                                 rule:     self.name(),
+                                subject:  format!("{}:{}:present", service_name, host_path),
                                 severity: Severity::Info,
                                 message:  format!(
                                     "Service '{}' uses bind mount '{}' — ensure data is restored before rehearsal",
                                     service_name, host_path
                                 ),
                                 penalty: 0,
+                                acknowledged: false,
                             });
                         }
                     }
@@ -140,6 +286,17 @@ impl PreflightRule for BindMountRule {
 
 pub struct ImagePullRule;
 
+/// Concurrent pulls when REHEARSA_PULL_PARALLELISM is unset or invalid.
+const DEFAULT_PULL_PARALLELISM: usize = 4;
+
+fn pull_parallelism() -> usize {
+    std::env::var("REHEARSA_PULL_PARALLELISM")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_PULL_PARALLELISM)
+}
+
 #[async_trait]
 impl PreflightRule for ImagePullRule {
 
@@ -150,47 +307,60 @@ impl PreflightRule for ImagePullRule {
         ctx: &PreflightContext<'_>,
     ) -> Vec<PreflightFinding> {
 
-        let mut findings = Vec::new();
+        // Sorted so findings come out in the same order every run,
+        // however the pulls interleave.
+        let mut images: Vec<(&String, &String)> = ctx.compose.services
+            .iter()
+            .filter_map(|(name, service)| service.image.as_ref().map(|image| (name, image)))
+            .collect();
+        images.sort();
+
+        // Attempt pulls to simulate fresh host availability, a bounded
+        // number at a time and each unique image once.
+        let unique: BTreeSet<&String> = images.iter().map(|(_, image)| *image).collect();
+        let semaphore = &tokio::sync::Semaphore::new(pull_parallelism());
+
+        let pulls: HashMap<&String, bool> = futures_util::future::join_all(unique.into_iter().map(|image| async move {
+            let _permit = semaphore.acquire().await;
+            let pulled = ctx.images
+                .ensure(image, || pull_image(ctx.docker, image))
+                .await
+                .is_ok();
+            (image, pulled)
+        })).await.into_iter().collect();
 
-        for (service_name, service) in &ctx.compose.services {
-            if let Some(image) = &service.image {
+        let mut findings = Vec::new();
 
-                // Warn if using :latest — non-deterministic across restore hosts
-                if image.ends_with(":latest") || !image.contains(':') {
-                    findings.push(PreflightFinding {
-                        rule:     self.name(),
-                        severity: Severity::Warning,
-                        message:  format!(
-                            "Service '{}' uses unpinned image tag '{}' — restore may produce a different version",
-                            service_name, image
-                        ),
-                        penalty: 5,
-                    });
-                }
+        for (service_name, image) in images {
+            let pulled = pulls[image];
 
-                // Attempt pull to simulate fresh host availability
-                let options = Some(CreateImageOptions::<String> {
-                    from_image: image.clone(),
-                    tag: "latest".to_string(),
-                    ..Default::default()
+            // Warn if using :latest — non-deterministic across restore hosts
+            if image.ends_with(":latest") || !image.contains(':') {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:{}:unpinned", service_name, image),
+                    severity: Severity::Warning,
+                    message:  format!(
+                        "Service '{}' uses unpinned image tag '{}' — restore may produce a different version",
+                        service_name, image
+                    ),
+                    penalty: 5,
+                    acknowledged: false,
                 });
+            }
 
-                let result = ctx.docker
-                    .create_image(options, None, None)
-                    .try_collect::<Vec<_>>()
-                    .await;
-
-                if result.is_err() {
-                    findings.push(PreflightFinding {
-                        rule:     self.name(),
-                        severity: Severity::Critical,
-                        message:  format!(
-                            "Service '{}' image '{}' cannot be pulled — restore will fail on a fresh host",
-                            service_name, image
-                        ),
-                        penalty: 30,
-                    });
-                }
+            if !pulled {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:{}:pull", service_name, image),
+                    severity: Severity::Critical,
+                    message:  format!(
+                        "Service '{}' image '{}' cannot be pulled — restore will fail on a fresh host",
+                        service_name, image
+                    ),
+                    penalty: 30,
+                    acknowledged: false,
+                });
             }
         }
 
@@ -241,6 +411,7 @@ impl PreflightRule for EnvVarRule {
                         // on a different restore host
                         findings.push(PreflightFinding {
                             rule:     self.name(),
+                            subject:  format!("{}:{}:inherited", service_name, key),
                             severity: Severity::Info,
                             message:  format!(
                                 "Service '{}' inherits '{}' from host environment — \
@@ -248,12 +419,14 @@ impl PreflightRule for EnvVarRule {
                                 service_name, key
                             ),
                             penalty: 0,
+                            acknowledged: false,
                         });
                     } else {
                         // Not set on this host — container will start with
                         // this variable unset, which is likely a misconfiguration
                         findings.push(PreflightFinding {
                             rule:     self.name(),
+                            subject:  format!("{}:{}:missing", service_name, key),
                             severity: Severity::Critical,
                             message:  format!(
                                 "Service '{}' requires env var '{}' but it is not set \
@@ -261,6 +434,7 @@ impl PreflightRule for EnvVarRule {
                                 service_name, key
                             ),
                             penalty: 20,
+                            acknowledged: false,
                         });
                     }
                 }
@@ -324,22 +498,26 @@ impl PreflightRule for ExternalNetworkRule {
             if !existing.contains(&network) {
                 findings.push(PreflightFinding {
                     rule:     self.name(),
+                    subject:  format!("{}:missing", network),
                     severity: Severity::Critical,
                     message:  format!(
                         "External network '{}' does not exist on this host — stack will fail to start on restore",
                         network
                     ),
                     penalty: 25,
+                    acknowledged: false,
                 });
             } else {
                 findings.push(PreflightFinding {
                     rule:     self.name(),
+                    subject:  format!("{}:present", network),
                     severity: Severity::Info,
                     message:  format!(
                         "External network '{}' exists — must also be created before restore on any other host",
                         network
                     ),
                     penalty: 0,
+                    acknowledged: false,
                 });
             }
         }
@@ -349,28 +527,868 @@ impl PreflightRule for ExternalNetworkRule {
 }
 
 // ======================================================
-// RULE ENGINE
+// RULE 5: Compose Schema Version
 // ======================================================
+//
+// Files still carry `version: "2"` / `"3.x"`, and some directives
+// changed meaning across those schemas. Modern `docker compose`
+// ignores the version key entirely, but legacy tooling does not —
+// so a file can behave differently depending on what restores it.
+// This rule surfaces the declared version and flags constructs the
+// declared schema does not honour.
+
+/// Service keys that exist in schema v2 but were removed in v3.
+const V2_ONLY_KEYS: &[&str] = &[
+    "cpu_shares", "cpu_quota", "cpuset", "cpus",
+    "mem_limit", "memswap_limit", "mem_reservation",
+    "extends", "volume_driver", "volumes_from",
+];
+
+pub struct ComposeVersionRule;
 
-pub async fn run_preflight(
-    ctx: &PreflightContext<'_>,
-) -> RestoreReadiness {
+#[async_trait]
+impl PreflightRule for ComposeVersionRule {
+
+    fn name(&self) -> &'static str { "ComposeVersionRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        use crate::docker::compose::{
+            extract_compose_version,
+            extract_depends_on_conditions,
+            extract_service_keys,
+        };
+
+        let mut findings = Vec::new();
+
+        let version = match extract_compose_version(&ctx.compose_raw) {
+            Some(v) => v,
+            None    => return findings,
+        };
+
+        findings.push(PreflightFinding {
+            rule:     self.name(),
+            subject:  format!("version:{}", version),
+            severity: Severity::Info,
+            message:  format!(
+                "Compose file declares schema version '{}' — docker compose v2 ignores it, \
+                 legacy docker-compose enforces it",
+                version
+            ),
+            penalty: 0,
+            acknowledged: false,
+        });
+
+        let major = version.split('.').next().unwrap_or("").trim();
+
+        match major {
+            "3" => {
+                for service in extract_depends_on_conditions(&ctx.compose_raw) {
+                    findings.push(PreflightFinding {
+                        rule:     self.name(),
+                        subject:  format!("{}:depends_on", service),
+                        severity: Severity::Warning,
+                        message:  format!(
+                            "Service '{}' uses depends_on conditions, which schema version '{}' \
+                             does not support — legacy tooling ignores them and startup order is not health-gated",
+                            service, version
+                        ),
+                        penalty: 5,
+                        acknowledged: false,
+                    });
+                }
+
+                for (service, keys) in extract_service_keys(&ctx.compose_raw) {
+                    for key in keys.iter().filter(|k| V2_ONLY_KEYS.contains(&k.as_str())) {
+                        findings.push(PreflightFinding {
+                            rule:     self.name(),
+                            subject:  format!("{}:{}", service, key),
+                            severity: Severity::Warning,
+                            message:  format!(
+                                "Service '{}' uses '{}', which was removed in schema version 3 — \
+                                 ignored under version '{}'",
+                                service, key, version
+                            ),
+                            penalty: 5,
+                            acknowledged: false,
+                        });
+                    }
+
+                    if keys.iter().any(|k| k == "deploy") {
+                        findings.push(PreflightFinding {
+                            rule:     self.name(),
+                            subject:  format!("{}:deploy", service),
+                            severity: Severity::Warning,
+                            message:  format!(
+                                "Service '{}' declares 'deploy', which schema version '{}' only \
+                                 honours in Swarm mode — the rehearsal does not apply it",
+                                service, version
+                            ),
+                            penalty: 5,
+                            acknowledged: false,
+                        });
+                    }
+                }
+            }
+            "1" | "2" => {
+                for (service, keys) in extract_service_keys(&ctx.compose_raw) {
+                    if keys.iter().any(|k| k == "deploy") {
+                        findings.push(PreflightFinding {
+                            rule:     self.name(),
+                            subject:  format!("{}:deploy", service),
+                            severity: Severity::Warning,
+                            message:  format!(
+                                "Service '{}' declares 'deploy', which is not part of schema \
+                                 version '{}' — legacy tooling rejects or ignores it",
+                                service, version
+                            ),
+                            penalty: 5,
+                            acknowledged: false,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 6: Restart Policy / Service Classification
+// ======================================================
+//
+// States up front how each service's exit will be scored, so a task
+// container scoring 100 on exit (or a long-running one scoring 0) is
+// never a surprise. Flags a oneshot label that contradicts a
+// `restart: always` policy — Compose would restart it forever.
+
+pub struct RestartPolicyRule;
+
+#[async_trait]
+impl PreflightRule for RestartPolicyRule {
+
+    fn name(&self) -> &'static str { "RestartPolicyRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        use crate::docker::compose::ServiceKind;
+
+        let mut findings = Vec::new();
+
+        let mut names: Vec<&String> = ctx.compose.services.keys().collect();
+        names.sort();
+
+        for name in names {
+            let service = &ctx.compose.services[name];
+            let class = service.classify();
+            let restart = service.restart.as_deref().unwrap_or("");
+
+            if class.kind == ServiceKind::OneshotLabelled
+                && (restart == "always" || restart == "unless-stopped")
+            {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:restart", name),
+                    severity: Severity::Warning,
+                    message:  format!(
+                        "Service '{}' is labelled oneshot but has restart: {} — \
+                         Compose will restart it after every exit",
+                        name, restart
+                    ),
+                    penalty: 5,
+                    acknowledged: false,
+                });
+            } else if class.kind == ServiceKind::OneshotInferred {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:task", name),
+                    severity: Severity::Info,
+                    message:  format!(
+                        "Service '{}' treated as a task container ({}) — a clean exit scores as success",
+                        name, class.reason
+                    ),
+                    penalty: 0,
+                    acknowledged: false,
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 7: Effective Service Set
+// ======================================================
+//
+// Profiles can filter every service out of a stack (as can an empty
+// `services:` map). There is then nothing to rehearse, and a score
+// averaged over zero services means nothing — flag it before the
+// simulation is attempted.
+
+pub struct ServiceSetRule;
+
+#[async_trait]
+impl PreflightRule for ServiceSetRule {
+
+    fn name(&self) -> &'static str { "ServiceSetRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        if !ctx.compose.services.is_empty() {
+            return Vec::new();
+        }
+
+        vec![PreflightFinding {
+            rule:     self.name(),
+            subject:  "services:empty".to_string(),
+            severity: Severity::Critical,
+            message:  "No services to rehearse after filtering — check active profiles \
+                       and the compose file's services block"
+                .to_string(),
+            penalty: 100,
+            acknowledged: false,
+        }]
+    }
+}
+
+// ======================================================
+// RULE 8: Variable Interpolation
+// ======================================================
+//
+// `${VAR}` with no default and no value on this host interpolates to
+// an empty string — an image tag, path or setting silently goes blank.
+// (`${VAR:?msg}` references abort the run before preflight instead.)
+
+pub struct InterpolationRule;
+
+#[async_trait]
+impl PreflightRule for InterpolationRule {
+
+    fn name(&self) -> &'static str { "InterpolationRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        ctx.unset_variables
+            .iter()
+            .map(|var| {
+                let location = match &var.service {
+                    Some(s) => format!("Service '{}'", s),
+                    None    => "Top-level block".to_string(),
+                };
+
+                PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:{}", var.service.as_deref().unwrap_or(""), var.name),
+                    severity: Severity::Warning,
+                    message:  format!(
+                        "{} references ${{{}}} but it is not set on this host and has no default — \
+                         substituted with an empty string",
+                        location, var.name
+                    ),
+                    penalty: 5,
+                    acknowledged: false,
+                }
+            })
+            .collect()
+    }
+}
+
+// ======================================================
+// RULE 9: Dependency Conditions
+// ======================================================
+//
+// `condition: service_healthy` on a dependency with no healthcheck can
+// never be satisfied as written. The engine falls back to
+// `service_started` — the dependent may start before it is ready.
+
+pub struct DependencyConditionRule;
+
+#[async_trait]
+impl PreflightRule for DependencyConditionRule {
+
+    fn name(&self) -> &'static str { "DependencyConditionRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        use crate::docker::compose::DependsCondition;
+
+        let mut findings = Vec::new();
+
+        let mut names: Vec<&String> = ctx.compose.services.keys().collect();
+        names.sort();
+
+        for name in names {
+            for dep in ctx.compose.services[name].depends_on.iter().flatten() {
+                let has_healthcheck = ctx.compose.services
+                    .get(&dep.service)
+                    .is_some_and(|s| s.healthcheck.is_some());
+
+                if dep.condition == DependsCondition::Healthy && !has_healthcheck {
+                    findings.push(PreflightFinding {
+                        rule:     self.name(),
+                        subject:  format!("{}:{}:healthy", name, dep.service),
+                        severity: Severity::Warning,
+                        message:  format!(
+                            "Service '{}' waits for '{}' to be healthy, but '{}' has no healthcheck — \
+                             falling back to service_started",
+                            name, dep.service, dep.service
+                        ),
+                        penalty: 5,
+                        acknowledged: false,
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 10: Port Conflicts
+// ======================================================
+//
+// A published host port already bound on the restore host stops the
+// container from starting. Each published port is probed by binding it
+// briefly; ranges such as `8000-8005:80` are expanded.
+
+pub struct PortConflictRule;
+
+/// Largest range expanded per entry — guards against typos like `1-65535`.
+const MAX_PORT_RANGE: u16 = 1024;
+
+/// Published host ports and protocol of a short-form port entry
+/// (`[ip:]host:container[/proto]`). Container-only entries publish
+/// nothing and yield an empty list.
+fn published_ports(entry: &str) -> (Vec<u16>, &str) {
+    let (spec, proto) = entry.split_once('/').unwrap_or((entry, "tcp"));
+
+    let host = match spec.rsplit_once(':') {
+        Some((rest, _container)) => rest.rsplit_once(':').map_or(rest, |(_ip, h)| h),
+        None => return (Vec::new(), proto),
+    };
+
+    let range = match host.split_once('-') {
+        Some((lo, hi)) => lo.parse::<u16>().ok().zip(hi.parse::<u16>().ok()),
+        None => host.parse::<u16>().ok().map(|p| (p, p)),
+    };
+
+    match range {
+        Some((lo, hi)) if lo > 0 && lo <= hi && hi - lo < MAX_PORT_RANGE => ((lo..=hi).collect(), proto),
+        _ => (Vec::new(), proto),
+    }
+}
+
+fn port_in_use(port: u16, proto: &str) -> bool {
+    match proto {
+        "udp" => std::net::UdpSocket::bind(("0.0.0.0", port)).is_err(),
+        _     => std::net::TcpListener::bind(("0.0.0.0", port)).is_err(),
+    }
+}
+
+#[async_trait]
+impl PreflightRule for PortConflictRule {
+
+    fn name(&self) -> &'static str { "PortConflictRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        let mut findings = Vec::new();
+
+        let mut names: Vec<&String> = ctx.compose.services.keys().collect();
+        names.sort();
+
+        for name in names {
+            for entry in ctx.compose.services[name].ports.iter().flatten() {
+                let (ports, proto) = published_ports(entry);
+
+                for port in ports {
+                    if !port_in_use(port, proto) {
+                        continue;
+                    }
+
+                    findings.push(PreflightFinding {
+                        rule:     self.name(),
+                        subject:  format!("{}:{}", name, port),
+                        severity: Severity::Critical,
+                        message:  format!(
+                            "Service '{}' publishes port {}/{} but it is already in use on this host",
+                            name, port, proto
+                        ),
+                        penalty: 20,
+                        acknowledged: false,
+                    });
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 11: Disk Space
+// ======================================================
+//
+// Every existing bind source is cloned before the rehearsal starts.
+// Its size is summed and compared with the free space on the
+// filesystem holding the clone sandbox. Walking large trees is slow,
+// so `stack test --skip-disk-check` turns this rule off.
+
+pub struct DiskSpaceRule;
+
+/// Projected usage above this share of free space is a warning.
+const DISK_WARN_PERCENT: u64 = 90;
+
+/// Total size of the files under `path`. Symlinks are not followed.
+fn tree_size(path: &Path) -> std::io::Result<u64> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() {
+        return Ok(meta.len());
+    }
+
+    let mut total = 0;
+    for entry in fs::read_dir(path)? {
+        total += tree_size(&entry?.path())?;
+    }
+    Ok(total)
+}
+
+fn free_space(path: &Path) -> Result<u64, String> {
+    let stat = nix::sys::statvfs::statvfs(path).map_err(|e| e.to_string())?;
+    Ok(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+#[async_trait]
+impl PreflightRule for DiskSpaceRule {
+
+    fn name(&self) -> &'static str { "DiskSpaceRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        use crate::docker::clone::parse_bind;
+
+        let target = match &ctx.clone_target {
+            Some(t) => t,
+            None    => return Vec::new(),
+        };
+
+        let mut findings = Vec::new();
+        let mut required: u64 = 0;
+
+        let mut names: Vec<&String> = ctx.compose.services.keys().collect();
+        names.sort();
+
+        for name in names {
+            for entry in ctx.compose.services[name].volumes.iter().flatten() {
+                let base = ctx.compose_path.parent().unwrap_or(Path::new("."));
+                let source = match parse_bind(entry, base) {
+                    Some((source, _, _)) if source.exists() => source,
+                    _ => continue,
+                };
+
+                match tree_size(&source) {
+                    Ok(size) => required += size,
+                    Err(e) => findings.push(PreflightFinding {
+                        rule:     self.name(),
+                        subject:  format!("{}:{}:unreadable", name, source.display()),
+                        severity: Severity::Info,
+                        message:  format!(
+                            "Service '{}' bind source '{}' could not be sized ({}) — excluded from the disk space estimate",
+                            name, source.display(), e
+                        ),
+                        penalty: 0,
+                        acknowledged: false,
+                    }),
+                }
+            }
+        }
+
+        if required == 0 {
+            return findings;
+        }
+
+        let free = match free_space(target) {
+            Ok(f) => f,
+            Err(e) => {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:statvfs", target.display()),
+                    severity: Severity::Info,
+                    message:  format!(
+                        "Could not query free space on '{}' ({}) — disk space not checked",
+                        target.display(), e
+                    ),
+                    penalty: 0,
+                    acknowledged: false,
+                });
+                return findings;
+            }
+        };
+
+        let (severity, penalty, verdict) = if required > free {
+            (Severity::Critical, 30, "does not fit in")
+        } else if required * 100 > free * DISK_WARN_PERCENT {
+            (Severity::Warning, 10, "would use over 90% of")
+        } else {
+            return findings;
+        };
+
+        findings.push(PreflightFinding {
+            rule:     self.name(),
+            subject:  format!("{}:space", target.display()),
+            severity,
+            message:  format!(
+                "Bind sources total {}, which {} the {} free on '{}'",
+                human_bytes(required), verdict, human_bytes(free), target.display()
+            ),
+            penalty,
+            acknowledged: false,
+        });
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 12: Custom Commands
+// ======================================================
+//
+// Operator-defined checks from ~/.rehearsa/rules.json. Each entry runs
+// a shell command once per rehearsal, with the Compose file's path in
+// REHEARSA_COMPOSE_PATH; a non-zero exit (or a timeout) produces a
+// finding at the configured severity and penalty:
+//
+//   [{ "name": "backup-agent", "command": "systemctl is-active restic",
+//      "severity": "critical", "penalty": 20, "timeout_secs": 10 }]
+
+pub struct CustomCommandRule;
+
+/// Default and maximum run time of one custom command.
+const CUSTOM_RULE_TIMEOUT_SECS: u64 = 30;
+const CUSTOM_RULE_MAX_TIMEOUT_SECS: u64 = 300;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CustomRuleDef {
+    pub name:     String,
+    pub command:  String,
+    pub severity: Severity,
+    #[serde(default)]
+    pub penalty:  u32,
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+}
+
+fn load_custom_rules() -> Result<Vec<CustomRuleDef>, String> {
+    let path = match dirs::home_dir() {
+        Some(home) => home.join(".rehearsa").join("rules.json"),
+        None       => return Ok(Vec::new()),
+    };
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
 
-    let rules: Vec<Box<dyn PreflightRule>> = vec![
+/// Run one definition. Returns the failure reason, or None on success.
+async fn run_custom_rule(def: &CustomRuleDef, compose_path: &Path) -> Option<String> {
+    let secs = def.timeout_secs
+        .unwrap_or(CUSTOM_RULE_TIMEOUT_SECS)
+        .min(CUSTOM_RULE_MAX_TIMEOUT_SECS);
+
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(&def.command)
+        .env("REHEARSA_COMPOSE_PATH", compose_path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+
+    let child = match child {
+        Ok(c)  => c,
+        Err(e) => return Some(format!("could not be started: {}", e)),
+    };
+
+    let output = match tokio::time::timeout(
+        std::time::Duration::from_secs(secs),
+        child.wait_with_output(),
+    ).await {
+        Ok(Ok(o))  => o,
+        Ok(Err(e)) => return Some(format!("failed: {}", e)),
+        Err(_)     => return Some(format!("timed out after {}s", secs)),
+    };
+
+    if output.status.success() {
+        return None;
+    }
+
+    let code = output.status.code()
+        .map_or("killed by signal".to_string(), |c| format!("exited with code {}", c));
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    Some(if stderr.is_empty() { code } else { format!("{}: {}", code, stderr) })
+}
+
+#[async_trait]
+impl PreflightRule for CustomCommandRule {
+
+    fn name(&self) -> &'static str { "CustomCommandRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        let defs = match load_custom_rules() {
+            Ok(d) => d,
+            Err(e) => {
+                return vec![PreflightFinding {
+                    rule:     self.name(),
+                    subject:  "rules.json".to_string(),
+                    severity: Severity::Warning,
+                    message:  format!("Custom rules not evaluated — {}", e),
+                    penalty: 0,
+                    acknowledged: false,
+                }];
+            }
+        };
+
+        let mut findings = Vec::new();
+
+        for def in defs {
+            if let Some(reason) = run_custom_rule(&def, &ctx.compose_path).await {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("custom:{}", def.name),
+                    severity: def.severity,
+                    message:  format!("Custom rule '{}' {}", def.name, reason),
+                    penalty:  def.penalty,
+                    acknowledged: false,
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 13: Named Volumes
+// ======================================================
+//
+// A volume declared `external: true` must already exist on the restore
+// host, like an external network — Compose refuses to start without it.
+// Other named volumes are created on demand, but start out empty on a
+// fresh host unless their data is restored, so they are noted as Info.
+
+pub struct NamedVolumeRule;
+
+#[async_trait]
+impl PreflightRule for NamedVolumeRule {
+
+    fn name(&self) -> &'static str { "NamedVolumeRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        use crate::docker::compose::extract_named_volumes;
+        use bollard::volume::ListVolumesOptions;
+
+        let mut findings = Vec::new();
+
+        let volumes = extract_named_volumes(&ctx.compose_raw);
+        if volumes.is_empty() {
+            return findings;
+        }
+
+        // None when Docker cannot be reached — externals are then skipped
+        // rather than false-flagged.
+        let existing: Option<std::collections::HashSet<String>> = ctx.docker
+            .list_volumes(None::<ListVolumesOptions<String>>)
+            .await
+            .ok()
+            .map(|r| r.volumes.unwrap_or_default().into_iter().map(|v| v.name).collect());
+
+        for volume in volumes {
+            if !volume.external {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:on-demand", volume.key),
+                    severity: Severity::Info,
+                    message:  format!(
+                        "Named volume '{}' is created on demand — it starts empty on a fresh host unless its data is restored",
+                        volume.key
+                    ),
+                    penalty: 0,
+                    acknowledged: false,
+                });
+                continue;
+            }
+
+            let Some(existing) = &existing else { continue };
+
+            if !existing.contains(&volume.name) {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:missing", volume.name),
+                    severity: Severity::Critical,
+                    message:  format!(
+                        "External volume '{}' does not exist on this host — stack will fail to start on restore",
+                        volume.name
+                    ),
+                    penalty: 25,
+                    acknowledged: false,
+                });
+            } else {
+                findings.push(PreflightFinding {
+                    rule:     self.name(),
+                    subject:  format!("{}:present", volume.name),
+                    severity: Severity::Info,
+                    message:  format!(
+                        "External volume '{}' exists — must also be created, with its data, on any other restore host",
+                        volume.name
+                    ),
+                    penalty: 0,
+                    acknowledged: false,
+                });
+            }
+        }
+
+        findings
+    }
+}
+
+// ======================================================
+// RULE 14: Undefined Dependencies
+// ======================================================
+//
+// A `depends_on` entry naming a service the file does not define can
+// never be started. Compose rejects the file outright; the rehearsal
+// stops after preflight, before any container or network is created.
+
+pub struct UndefinedDependencyRule;
+
+#[async_trait]
+impl PreflightRule for UndefinedDependencyRule {
+
+    fn name(&self) -> &'static str { "UndefinedDependencyRule" }
+
+    async fn evaluate(
+        &self,
+        ctx: &PreflightContext<'_>,
+    ) -> Vec<PreflightFinding> {
+
+        ctx.compose
+            .undefined_dependencies()
+            .into_iter()
+            .map(|(service, dep)| PreflightFinding {
+                rule:     self.name(),
+                subject:  format!("{}:{}", service, dep),
+                severity: Severity::Critical,
+                message:  format!(
+                    "Service '{}' depends on '{}', which is not defined in the Compose file",
+                    service, dep
+                ),
+                penalty: 25,
+                acknowledged: false,
+            })
+            .collect()
+    }
+}
+
+// ======================================================
+// RULE ENGINE
+// ======================================================
+
+fn rules() -> Vec<Box<dyn PreflightRule>> {
+    vec![
         Box::new(BindMountRule),
         Box::new(ImagePullRule),
         Box::new(EnvVarRule),
         Box::new(ExternalNetworkRule),
-    ];
+        Box::new(NamedVolumeRule),
+        Box::new(ComposeVersionRule),
+        Box::new(RestartPolicyRule),
+        Box::new(ServiceSetRule),
+        Box::new(InterpolationRule),
+        Box::new(DependencyConditionRule),
+        Box::new(UndefinedDependencyRule),
+        Box::new(PortConflictRule),
+        Box::new(DiskSpaceRule),
+        Box::new(CustomCommandRule),
+    ]
+}
+
+/// Names of every registered rule, in evaluation order.
+pub fn rule_names() -> Vec<&'static str> {
+    rules().iter().map(|r| r.name()).collect()
+}
+
+pub async fn run_preflight(
+    ctx: &PreflightContext<'_>,
+) -> RestoreReadiness {
 
     let mut findings = Vec::new();
 
-    for rule in rules {
+    for rule in rules() {
         let mut results = rule.evaluate(ctx).await;
         findings.append(&mut results);
     }
 
-    let score = compute_score(&findings);
+    for finding in findings.iter_mut() {
+        if ctx.config.suppressed.contains(&finding.id()) {
+            finding.severity     = Severity::Info;
+            finding.penalty      = 0;
+            finding.acknowledged = true;
+        }
+    }
+
+    let score = compute_score(&findings, &ctx.config.penalty_caps);
 
     RestoreReadiness { score, findings }
 }
@@ -378,11 +1396,21 @@ pub async fn run_preflight(
 // ======================================================
 // SCORE COMPUTATION
 // ======================================================
+//
+// Penalties are summed per rule, then clamped to that rule's cap (if
+// any) before being subtracted — so one repeated issue cannot pin the
+// score to zero on its own.
 
-fn compute_score(findings: &[PreflightFinding]) -> u32 {
-    let mut score: u32 = 100;
+fn compute_score(findings: &[PreflightFinding], caps: &BTreeMap<String, u32>) -> u32 {
+    let mut per_rule: BTreeMap<&str, u32> = BTreeMap::new();
     for finding in findings {
-        score = score.saturating_sub(finding.penalty);
+        *per_rule.entry(finding.rule).or_insert(0) += finding.penalty;
+    }
+
+    let mut score: u32 = 100;
+    for (rule, total) in per_rule {
+        let penalty = caps.get(rule).map_or(total, |cap| total.min(*cap));
+        score = score.saturating_sub(penalty);
     }
     score
 }
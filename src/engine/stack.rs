@@ -1,29 +1,36 @@
 use anyhow::{Result, anyhow};
 use bollard::Docker;
 use bollard::container::{
-    Config, CreateContainerOptions, StartContainerOptions, NetworkingConfig,
+    Config, CreateContainerOptions, LogsOptions, StartContainerOptions, NetworkingConfig,
 };
 use bollard::network::CreateNetworkOptions;
-use bollard::image::CreateImageOptions;
+use bollard::image::ImportImageOptions;
 use bollard::models::{
     HostConfig, Mount, EndpointSettings,
-    ContainerStateStatusEnum, HealthStatusEnum, HealthConfig,
+    ContainerStateStatusEnum, HealthStatusEnum, HealthConfig, MountTypeEnum,
 };
 use futures_util::stream::TryStreamExt;
 use serde_json::json;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use tokio::time::{sleep, Duration};
 use uuid::Uuid;
 use std::time::Instant;
 
-use crate::docker::compose::HealthCheck;
-use crate::engine::graph::topological_sort;
-use crate::engine::preflight::{PreflightContext, run_preflight, Severity};
-use crate::lock::StackLock;
+use crate::docker::clone::{clone_mounts, cleanup_clone};
+use crate::docker::connect::connect_docker;
+use crate::docker::images::{pull_image, ImageCache};
+use crate::output::{render, OutputFormat};
+use crate::exitcode::ExitCode;
+use crate::scoring::{load_scoring, ScoreBands};
+use crate::docker::compose::{ComposeFile, DependsCondition, HealthCheck, Service, ServiceKind, merge_compose};
+use crate::engine::graph::{dependency_levels, topological_sort};
+use crate::engine::preflight::{PreflightContext, RestoreReadiness, run_preflight, load_preflight_config, Severity};
+use crate::lock::{StackLock, get_hostname};
 use crate::history::{
     RunRecord,
+    FindingRecord,
     CURRENT_SCHEMA_VERSION,
     persist,
     now_timestamp,
@@ -31,19 +38,35 @@ use crate::history::{
     calculate_stability,
     analyze_regression,
 };
-use crate::policy::load_policy;
-use crate::baseline::{load_baseline, compare_to_baseline};
+use crate::policy::{load_policy, PolicyCheck, CheckResult, DEFAULT_DURATION_SPIKE_PERCENT};
+use crate::baseline::{load_baseline, compare_to_baseline, DriftSeverity, StackBaseline};
 // ======================================================
 // PULL POLICY
 // ======================================================
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub enum PullPolicy {
     Always,
+    #[default]
     IfMissing,
     Never,
 }
 
+// ======================================================
+// VERBOSITY
+// ======================================================
+
+/// How much human output a run prints. JSON and YAML output ignore it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only the verdict line and errors.
+    Quiet,
+    #[default]
+    Normal,
+    /// Also per-service wait timing and every start attempt.
+    Verbose,
+}
+
 // ======================================================
 // RUN SUMMARY (NEW)
 // ======================================================
@@ -53,33 +76,62 @@ pub struct StackRunSummary {
     pub stack: String,
     pub readiness: u32,
     pub confidence: u32,
-    pub duration: u64,
-    pub risk: String,
-    pub service_scores: HashMap<String, u32>,
     pub policy_violated: bool,
     pub baseline_drift: bool,
+    /// Drift severity against the baseline; None when no baseline is pinned.
+    pub drift_severity: Option<DriftSeverity>,
+    /// Every policy rule evaluated for this run (empty if no policy).
+    pub policy_checks: Vec<PolicyCheck>,
+    pub exit_code: ExitCode,
+}
+
+impl StackRunSummary {
+    pub fn new(
+        stack: String,
+        readiness: u32,
+        confidence: u32,
+        policy_violated: bool,
+        baseline_drift: bool,
+        drift_severity: Option<DriftSeverity>,
+        policy_checks: Vec<PolicyCheck>,
+    ) -> Self {
+        StackRunSummary {
+            stack,
+            readiness,
+            confidence,
+            policy_violated,
+            baseline_drift,
+            drift_severity,
+            policy_checks,
+            exit_code: ExitCode::for_run(policy_violated, baseline_drift, confidence),
+        }
+    }
+
+    /// History record fields restated from this summary. Its exit_code is
+    /// the one the process exits with.
+    fn history_record(&self) -> RunRecord {
+        RunRecord {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            stack: self.stack.clone(),
+            readiness: Some(self.readiness),
+            confidence: self.confidence,
+            exit_code: self.exit_code as i32,
+            drift_severity: self.drift_severity,
+            ..Default::default()
+        }
+    }
 }
 
 // ======================================================
 // STACK TEST
 // ======================================================
 
-pub async fn test_stack(
-    path: &str,
-    timeout: u64,
-    json_output: bool,
-    inject_failure: Option<String>,
-    strict_integrity: bool,
-    pull_policy: PullPolicy,
-) -> Result<StackRunSummary> {
-
-    let compose_path = Path::new(path);
-
-    // Derive a unique stack name from the parent directory name.
-    // Falls back to file stem if no parent directory is available.
-    // This prevents collisions when multiple stacks are all named
-    // docker-compose.yml in different directories.
-    let stack_name = compose_path
+/// Derive a unique stack name from the parent directory name.
+/// Falls back to file stem if no parent directory is available.
+/// This prevents collisions when multiple stacks are all named
+/// docker-compose.yml in different directories.
+pub fn stack_name_for(compose_path: &Path) -> String {
+    compose_path
         .parent()
         .and_then(|p| p.file_name())
         .map(|n| n.to_string_lossy().to_string())
@@ -90,55 +142,355 @@ pub async fn test_stack(
                 .unwrap_or_default()
                 .to_string_lossy()
                 .to_string()
-        });
+        })
+}
+
+/// Read the Compose files and merge them in order — later files override
+/// earlier ones, as with `docker compose -f a.yml -f b.yml`.
+fn read_compose_files(compose_files: &[String]) -> Result<String> {
+    let (first, rest) = compose_files
+        .split_first()
+        .ok_or_else(|| anyhow!("No Compose file given"))?;
+
+    let mut content = fs::read_to_string(first)?;
+
+    for path in rest {
+        let overlay = fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read {}: {}", path, e))?;
+        content = merge_compose(&content, &overlay)
+            .map_err(|e| anyhow!("{}: {}", path, e))?;
+    }
+
+    Ok(content)
+}
+
+fn print_readiness(readiness: &RestoreReadiness) {
+    println!();
+    println!("Preflight: Fresh Host Readiness");
+    println!("--------------------------------");
+
+    for finding in &readiness.findings {
+        let icon = match finding.severity {
+            Severity::Critical => "❌",
+            Severity::Warning  => "⚠ ",
+            Severity::Info     => "ℹ ",
+        };
+        let ack = if finding.acknowledged { ", acknowledged" } else { "" };
+        println!("{} [{}] {} ({}{})", icon, finding.rule, finding.message, finding.id(), ack);
+    }
+
+    println!("Restore Readiness Score: {}%", readiness.score);
+    println!();
+}
+
+fn finding_records(readiness: &RestoreReadiness) -> Vec<FindingRecord> {
+    readiness.findings.iter().map(|f| FindingRecord {
+        id:           f.id(),
+        rule:         f.rule.to_string(),
+        severity:     f.severity.to_string(),
+        message:      f.message.clone(),
+        penalty:      f.penalty,
+        acknowledged: f.acknowledged,
+    }).collect()
+}
+
+/// Preflight only: parse the Compose file and score fresh-host readiness
+/// without pulling images, creating the network or starting containers.
+/// Takes no lock and records no run. Returns the readiness score.
+pub async fn preflight_stack(
+    compose_files: &[String],
+    stack_name: Option<String>,
+    format: OutputFormat,
+    verbosity: Verbosity,
+    profiles: Vec<String>,
+    disk_check: bool,
+) -> Result<u32> {
+
+    // Bind sources resolve against the first file, as with Compose.
+    let compose_path = Path::new(
+        compose_files.first().ok_or_else(|| anyhow!("No Compose file given"))?,
+    );
+    let stack_name = stack_name.unwrap_or_else(|| stack_name_for(compose_path));
+
+    let docker = connect_docker()?;
+
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let raw = read_compose_files(compose_files)?;
+    let interpolated = crate::docker::compose::interpolate_compose(&raw, &env_map)
+        .map_err(|e| anyhow!(e))?;
+    let content = interpolated.content;
+
+    let mut compose = crate::docker::compose::parse_compose(&content).map_err(|e| anyhow!(e))?;
+    let active_profiles = crate::docker::compose::active_profiles(&profiles);
+    compose.apply_profiles(&active_profiles).map_err(|e| anyhow!(e))?;
+
+    let preflight_ctx = PreflightContext {
+        compose_raw: content.clone(),
+        compose: &compose,
+        docker: &docker,
+        environment: env_map,
+        unset_variables: interpolated.unset,
+        config: load_preflight_config(&stack_name),
+        compose_path: compose_path.to_path_buf(),
+        clone_target: disk_check.then(std::env::temp_dir),
+        images: &ImageCache::default(),
+    };
+
+    let readiness = run_preflight(&preflight_ctx).await;
+
+    if format.is_structured() {
+        println!("{}", render(&json!({
+            "stack": stack_name,
+            "restore_readiness": readiness.score,
+            "findings": finding_records(&readiness),
+        }), format).map_err(anyhow::Error::msg)?);
+    } else if verbosity == Verbosity::Quiet {
+        println!("Restore Readiness Score: {}%", readiness.score);
+    } else {
+        print_readiness(&readiness);
+    }
+
+    Ok(readiness.score)
+}
+
+/// Options for one `stack test` run. `Default` matches the CLI's defaults.
+#[derive(Clone)]
+pub struct TestOptions {
+    /// Defaults to the Compose file's directory name.
+    pub stack_name:       Option<String>,
+    /// Seconds each service gets to become healthy.
+    pub timeout:          u64,
+    /// Seconds a service without a healthcheck must stay RUNNING before
+    /// it is scored (0 = immediately). Callers resolve it from the daemon
+    /// config.
+    pub running_probe_seconds: u64,
+    pub format:           OutputFormat,
+    pub verbosity:        Verbosity,
+    /// Services forced to fail, for testing alerting.
+    pub inject_failure:   Vec<String>,
+    /// Refuse to run when the stack's history fails its per-record hash check.
+    pub strict_integrity: bool,
+    pub pull_policy:      PullPolicy,
+    /// Directory of `docker save` archives to load before rehearsing.
+    pub image_archive:    Option<String>,
+    pub profiles:         Vec<String>,
+    /// Start one service at a time instead of level by level.
+    pub sequential:       bool,
+    /// Mount clones of bind sources; false starts containers with no mounts.
+    pub clone_volumes:    bool,
+    /// Run DiskSpaceRule before cloning.
+    pub disk_check:       bool,
+    /// Wait this long for a concurrent rehearsal's lock; None fails at once.
+    pub lock_wait:        Option<Duration>,
+    /// Leave a failed run's containers, network and clones in place.
+    pub keep_on_failure:  bool,
+    /// Start retries per service; None uses the scoring config.
+    pub start_retries:    Option<u32>,
+    /// Rehearse only services changed since the baseline.
+    pub changed_only:     bool,
+}
+
+impl Default for TestOptions {
+    fn default() -> Self {
+        TestOptions {
+            stack_name:       None,
+            timeout:          30,
+            running_probe_seconds: 0,
+            format:           OutputFormat::default(),
+            verbosity:        Verbosity::default(),
+            inject_failure:   Vec::new(),
+            strict_integrity: false,
+            pull_policy:      PullPolicy::default(),
+            image_archive:    None,
+            profiles:         Vec::new(),
+            sequential:       false,
+            clone_volumes:    true,
+            disk_check:       true,
+            lock_wait:        None,
+            keep_on_failure:  false,
+            start_retries:    None,
+            changed_only:     false,
+        }
+    }
+}
+
+pub async fn test_stack(
+    compose_files: &[String],
+    options: TestOptions,
+) -> Result<StackRunSummary> {
+
+    let TestOptions {
+        stack_name,
+        timeout,
+        running_probe_seconds,
+        format,
+        verbosity,
+        inject_failure,
+        strict_integrity,
+        pull_policy,
+        image_archive,
+        profiles,
+        sequential,
+        clone_volumes,
+        disk_check,
+        lock_wait,
+        keep_on_failure,
+        start_retries,
+        changed_only,
+    } = options;
+
+    // Bind sources resolve against the first file, as with Compose.
+    let compose_path = Path::new(
+        compose_files.first().ok_or_else(|| anyhow!("No Compose file given"))?,
+    );
+    let stack_name = stack_name.unwrap_or_else(|| stack_name_for(compose_path));
 
     if strict_integrity {
         validate_stack_integrity(&stack_name)
             .map_err(|e| anyhow!(e))?;
     }
 
-    let docker = Docker::connect_with_local_defaults()?;
-    let _lock = StackLock::acquire(&stack_name)
-        .map_err(|e| anyhow!(e))?;
+    let docker = connect_docker()?;
+    // Interactive runs may wait for a concurrent rehearsal; the daemon never does.
+    let _lock = match lock_wait {
+        Some(wait) => tokio::task::block_in_place(|| StackLock::acquire_timeout(&stack_name, wait)),
+        None       => StackLock::acquire(&stack_name),
+    }
+    .map_err(|e| anyhow!(e))?;
+
+    // Recorded so an engine upgrade between runs is an attributable variable.
+    let docker_version = docker.version().await.ok().and_then(|v| v.version);
 
     let start_time = Instant::now();
 
-    let content = fs::read_to_string(path)?;
-    let compose = crate::docker::compose::parse_compose(&content).map_err(|e| anyhow!(e))?;
+    // Interpolate ${VAR} references before anything reads the file, so
+    // images, volumes and dependencies are resolved as Compose would.
+    let env_map: HashMap<String, String> = std::env::vars().collect();
+    let raw = read_compose_files(compose_files)?;
+    let interpolated = crate::docker::compose::interpolate_compose(&raw, &env_map)
+        .map_err(|e| anyhow!(e))?;
+    let content = interpolated.content;
+
+    let mut compose = crate::docker::compose::parse_compose(&content).map_err(|e| anyhow!(e))?;
+
+    let active_profiles = crate::docker::compose::active_profiles(&profiles);
+    let excluded = compose.apply_profiles(&active_profiles).map_err(|e| anyhow!(e))?;
+
+    // Progress and detail lines; the verdict has its own rules below.
+    let show = !format.is_structured() && verbosity != Verbosity::Quiet;
+    let verbose = !format.is_structured() && verbosity == Verbosity::Verbose;
+
+    if show && (!active_profiles.is_empty() || !excluded.is_empty()) {
+        println!(
+            "Profiles: {}",
+            if active_profiles.is_empty() { "none".to_string() } else { active_profiles.join(", ") }
+        );
+        if !excluded.is_empty() {
+            println!("  Not started (inactive profile): {}", excluded.join(", "));
+        }
+    }
+
+    for target in &inject_failure {
+        if !compose.services.contains_key(target) {
+            eprintln!("Warning: --inject-failure '{}' matches no service in {}", target, compose_files.join(", "));
+        }
+    }
+
+    // ======================================================
+    // IMAGE ARCHIVE (air-gapped hosts)
+    // ======================================================
+    //
+    // Loaded before preflight so image presence checks and
+    // PullPolicy::Never see the archived images.
+
+    let images_from_archive = match image_archive {
+        Some(ref dir) => {
+            let loaded = load_image_archive(&docker, Path::new(dir)).await?;
+            if show {
+                println!("Loaded {} image(s) from archive {}", loaded.len(), dir);
+                for image in &loaded {
+                    println!("  {}", image);
+                }
+            }
+            loaded
+        }
+        None => vec![],
+    };
 
     // ======================================================
     // PREFLIGHT
     // ======================================================
 
-    let env_map: HashMap<String, String> = std::env::vars().collect();
+    // Shared by preflight and service startup: each unique image is
+    // pulled or inspected once per run.
+    let images = ImageCache::default();
 
     let preflight_ctx = PreflightContext {
         compose_raw: content.clone(),
         compose: &compose,
         docker: &docker,
         environment: env_map,
+        unset_variables: interpolated.unset,
+        config: load_preflight_config(&stack_name),
+        compose_path: compose_path.to_path_buf(),
+        clone_target: (clone_volumes && disk_check).then(std::env::temp_dir),
+        images: &images,
     };
 
     let readiness = run_preflight(&preflight_ctx).await;
 
-    if !json_output {
-        println!();
-        println!("Preflight: Fresh Host Readiness");
-        println!("--------------------------------");
-
-        for finding in &readiness.findings {
-            match finding.severity {
-                Severity::Critical => println!("❌ [{}] {}", finding.rule, finding.message),
-                Severity::Warning  => println!("⚠  [{}] {}", finding.rule, finding.message),
-                Severity::Info     => println!("ℹ  [{}] {}", finding.rule, finding.message),
+    if show {
+        print_readiness(&readiness);
+    }
+
+    // Reported by ServiceSetRule above; stop here rather than persist a
+    // zero-service record.
+    if compose.services.is_empty() {
+        return Err(anyhow!(
+            "No services to rehearse after filtering ({} excluded by inactive profiles)",
+            excluded.len()
+        ));
+    }
+
+    // Reported by UndefinedDependencyRule above; fail before any Docker
+    // resources exist rather than at the dependent's startup.
+    let dangling = compose.undefined_dependencies();
+    if !dangling.is_empty() {
+        return Err(anyhow!(
+            "Undefined dependencies: {}",
+            dangling.iter()
+                .map(|(svc, dep)| format!("{} -> {}", svc, dep))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    // ======================================================
+    // CHANGED-ONLY SELECTION
+    // ======================================================
+
+    let service_hashes = crate::docker::compose::service_config_hashes(&content);
+    let mut reused: HashMap<String, u32> = HashMap::new();
+
+    if changed_only {
+        match load_baseline(&stack_name).filter(|b| !b.service_hashes.is_empty()) {
+            Some(baseline) => {
+                reused = reusable_scores(&compose, &service_hashes, &baseline, &inject_failure);
+                compose.services.retain(|name, _| !reused.contains_key(name));
             }
+            None => eprintln!(
+                "Warning: --changed-only needs a baseline pinned from a run that recorded service hashes — rehearsing every service."
+            ),
         }
-
-        println!("Restore Readiness Score: {}%", readiness.score);
-        println!();
     }
 
-    if !json_output {
+    let mut reused_services: Vec<String> = reused.keys().cloned().collect();
+    reused_services.sort();
+
+    if show {
+        for name in &reused_services {
+            println!("  {} unchanged since baseline — reused score {}", name, reused[name]);
+        }
         println!(
             "Starting restore simulation for '{}' ({} services)...",
             stack_name,
@@ -148,9 +500,17 @@ pub async fn test_stack(
 
     let run_id = Uuid::new_v4().to_string();
     let network_name = format!("rehearsa_stack_{}", run_id);
+    let sandbox = std::env::temp_dir().join(format!("rehearsa_{}", run_id));
+
+    let scoring = load_scoring(&stack_name);
+    let bands = scoring.bands();
+    let start_retries = start_retries.unwrap_or_else(|| scoring.start_retries());
 
     let mut created_containers = Vec::new();
     let mut service_scores: HashMap<String, u32> = HashMap::new();
+    let mut service_classes: HashMap<String, String> = HashMap::new();
+    let mut outcomes: HashMap<String, ServiceOutcome> = HashMap::new();
+    let mut start_attempts: HashMap<String, u32> = HashMap::new();
 
     let execution = async {
 
@@ -159,12 +519,21 @@ pub async fn test_stack(
         for (name, service) in &compose.services {
             dep_map.insert(
                 name.clone(),
-                service.depends_on.clone().unwrap_or_default(),
+                service.depends_on.iter().flatten().map(|d| d.service.clone()).collect(),
             );
         }
 
-        let order = topological_sort(&dep_map)
-            .map_err(|e| anyhow!(e))?;
+        // Services in one level have no dependencies on each other and
+        // start together; --sequential puts each service in its own level.
+        let levels: Vec<Vec<String>> = if sequential {
+            topological_sort(&dep_map)
+                .map_err(|e| anyhow!(e))?
+                .into_iter()
+                .map(|s| vec![s])
+                .collect()
+        } else {
+            dependency_levels(&dep_map).map_err(|e| anyhow!(e))?
+        };
 
         docker.create_network(CreateNetworkOptions {
             name: network_name.clone(),
@@ -173,129 +542,194 @@ pub async fn test_stack(
             ..Default::default()
         }).await?;
 
-        for service_name in order {
+        // Bind sources are cloned into the sandbox and the clones mounted,
+        // so services boot against real data without touching it.
+        let mut mounts: HashMap<String, Vec<Mount>> = HashMap::new();
 
-            let service = compose.services
-                .get(&service_name)
-                .ok_or_else(|| anyhow!("Missing service {}", service_name))?;
+        if clone_volumes {
+            let base = compose_path.parent().unwrap_or(Path::new("."));
 
-            let image = service.image.clone()
-                .ok_or_else(|| anyhow!("Service {} has no image", service_name))?;
+            for (name, service) in &compose.services {
+                let cloned = clone_mounts(
+                    &sandbox,
+                    name,
+                    service.volumes.as_deref().unwrap_or_default(),
+                    base,
+                ).map_err(|e| anyhow!(e))?;
 
-            match pull_policy {
-                PullPolicy::Always => pull_image(&docker, &image).await?,
-                PullPolicy::IfMissing => {
-                    if docker.inspect_image(&image).await.is_err() {
-                        pull_image(&docker, &image).await?;
+                if show {
+                    for m in &cloned {
+                        println!("  {} mounts clone of {} at {}", name, m.source.display(), m.target);
                     }
                 }
-                PullPolicy::Never => {
-                    if docker.inspect_image(&image).await.is_err() {
-                        return Err(anyhow!(
-                            "Image '{}' not present and pull policy = Never",
-                            image
-                        ));
+
+                mounts.insert(name.clone(), cloned.iter().map(|m| Mount {
+                    target:    Some(m.target.clone()),
+                    source:    Some(m.clone.to_string_lossy().to_string()),
+                    typ:       Some(MountTypeEnum::BIND),
+                    read_only: Some(m.read_only),
+                    ..Default::default()
+                }).collect());
+            }
+        }
+
+        for level in levels {
+
+            let mut startable = Vec::new();
+
+            for service_name in &level {
+                let service = compose.services
+                    .get(service_name)
+                    .ok_or_else(|| anyhow!("Missing service {}", service_name))?;
+
+                // Dependencies sit in earlier levels and have already been waited
+                // on; a dependent whose depends_on condition was not met is never
+                // started, as with Compose.
+                if let Some(unmet) = unmet_dependency(
+                    &docker, &compose, service, &run_id, timeout, &mut outcomes,
+                ).await? {
+                    if show {
+                        println!("  {} not started: {}", service_name, unmet);
                     }
+                    outcomes.insert(service_name.clone(), ServiceOutcome::NotStarted);
+                    service_scores.insert(service_name.clone(), bands.failed);
+                    continue;
                 }
+
+                startable.push((service_name, service));
             }
 
-            let container_name =
-                format!("rehearsa_{}_{}", run_id, service_name);
+            if show && startable.len() > 1 {
+                let names: Vec<&str> = startable.iter().map(|(n, _)| n.as_str()).collect();
+                println!("  starting in parallel: {}", names.join(", "));
+            }
 
-            let mut endpoints: HashMap<String, EndpointSettings> = HashMap::new();
+            // Container names are deterministic, so queue them for teardown
+            // up front — a failure part-way through a level still cleans up.
+            for (service_name, _) in &startable {
+                created_containers.push(format!("rehearsa_{}_{}", run_id, service_name));
+            }
 
-            endpoints.insert(
-                network_name.clone(),
-                EndpointSettings {
-                    aliases: Some(vec![service_name.clone()]),
-                    ..Default::default()
-                },
-            );
+            let results = futures_util::future::join_all(
+                startable.iter().map(|(service_name, service)| start_and_score(
+                    &docker,
+                    &images,
+                    service_name,
+                    service,
+                    &run_id,
+                    &network_name,
+                    &pull_policy,
+                    timeout,
+                    running_probe_seconds,
+                    mounts.remove(service_name.as_str()).unwrap_or_default(),
+                    bands,
+                    start_retries,
+                    verbose,
+                ))
+            ).await;
+
+            for ((service_name, service), result) in startable.into_iter().zip(results) {
+                let (mut score, outcome, attempts) = result?;
+                let class = service.classify();
+
+                // Recorded in service_classes; printed only with --verbose.
+                if verbose {
+                    println!("  {} classified {} ({})", service_name, class.kind, class.reason);
+                }
+                if show {
+                    if attempts > 1 {
+                        println!("  {} took {} start attempts: {}", service_name, attempts, outcome);
+                    }
+                    if class.kind != ServiceKind::LongRunning {
+                        println!("  {} scored as oneshot: {} → {}", service_name, outcome, score);
+                    }
+                }
 
-            let health_config = service.healthcheck
-                .as_ref()
-                .map(convert_healthcheck);
-
-            let config = Config {
-                image: Some(image),
-                env: service.environment.clone(),
-                cmd: service.command.clone(),
-                healthcheck: health_config,
-                host_config: Some(HostConfig {
-                    mounts: Some(Vec::<Mount>::new()),
-                    ..Default::default()
-                }),
-                networking_config: Some(NetworkingConfig {
-                    endpoints_config: endpoints,
-                }),
-                ..Default::default()
-            };
+                outcomes.insert(service_name.clone(), outcome);
+                start_attempts.insert(service_name.clone(), attempts);
 
-            docker.create_container(
-                Some(CreateContainerOptions {
-                    name: container_name.clone(),
-                    platform: None,
-                }),
-                config,
-            ).await?;
-
-            docker.start_container(
-                &container_name,
-                None::<StartContainerOptions<String>>,
-            ).await?;
-
-            created_containers.push(container_name.clone());
-
-            let mut score =
-                wait_and_score(&docker, &container_name, timeout).await?;
-
-            // Oneshot services exit cleanly by design — score exit-0 as 100
-            if score == 0 {
-                let is_oneshot = service.labels
-                    .as_ref()
-                    .and_then(|l| l.get("com.rehearsa.oneshot"))
-                    .map(|v| v == "true" || v == "1")
-                    .unwrap_or(false);
-
-                if is_oneshot {
-                    // Oneshot contract: container started and ran — exit code is irrelevant
-                    score = 100;
-                }
-            }
+                service_classes.insert(
+                    service_name.clone(),
+                    format!("{} ({})", class.kind, class.reason),
+                );
 
-            if let Some(ref target) = inject_failure {
-                if target == &service_name {
-                    score = 0;
+                if inject_failure.contains(service_name) {
+                    score = bands.failed;
                 }
-            }
 
-            service_scores.insert(service_name.clone(), score);
+                service_scores.insert(service_name.clone(), score);
+            }
         }
 
         Ok::<(), anyhow::Error>(())
     }.await;
 
-    for container in &created_containers {
-        let _ = docker.remove_container(
-            container,
-            Some(bollard::container::RemoveContainerOptions {
-                force: true,
-                ..Default::default()
-            }),
-        ).await;
+    // Logs of failed services are read before teardown removes the containers.
+    let mut service_logs: HashMap<String, String> = HashMap::new();
+    for (service_name, &score) in &service_scores {
+        let container = format!("rehearsa_{}_{}", run_id, service_name);
+        if !bands.is_failed(score)
+            || inject_failure.contains(service_name)
+            || !created_containers.contains(&container)
+        {
+            continue;
+        }
+        if let Some(logs) = tail_logs(&docker, &container).await {
+            service_logs.insert(service_name.clone(), logs);
+        }
+    }
+
+    if show && !service_logs.is_empty() {
+        let mut names: Vec<&String> = service_logs.keys().collect();
+        names.sort();
+        for name in names {
+            println!();
+            println!("Logs from failed service '{}' (last {} lines):", name, SERVICE_LOG_TAIL_LINES);
+            for line in service_logs[name].lines() {
+                println!("  | {}", line);
+            }
+        }
+        println!();
     }
 
-    let _ = docker.remove_network(&network_name).await;
+    // --keep-on-failure leaves a failed run's containers, network and
+    // cloned data in place for post-mortem. `rehearsa cleanup` reaps the
+    // containers and network; the cloned data has to be removed by hand.
+    let failed = execution.is_err() || service_scores.values().any(|&s| bands.is_failed(s));
+
+    if keep_on_failure && failed && !created_containers.is_empty() {
+        print_kept_resources(&created_containers, &network_name, &sandbox);
+    } else {
+        for container in &created_containers {
+            let _ = docker.remove_container(
+                container,
+                Some(bollard::container::RemoveContainerOptions {
+                    force: true,
+                    ..Default::default()
+                }),
+            ).await;
+        }
+
+        let _ = docker.remove_network(&network_name).await;
+
+        cleanup_clone(&sandbox);
+    }
+
+    execution?;
+
+    // Reused scores join the rehearsed ones so confidence covers the whole stack.
+    for (name, score) in &reused {
+        service_scores.insert(name.clone(), *score);
+    }
 
-    if let Err(e) = execution {
-    return Err(e);
-}
     // ======================================================
     // SCORING
     // ======================================================
 
-    let total: u32 = service_scores.values().sum();
-    let confidence = total / service_scores.len() as u32;
+    // Weighted by the stack's scoring config — a plain average without one.
+    let confidence = scoring
+        .confidence(&service_scores)
+        .ok_or_else(|| anyhow!("No services to rehearse after filtering — nothing was scored"))?;
 
     let risk = match confidence {
         90..=100 => "LOW",
@@ -319,10 +753,11 @@ pub async fn test_stack(
 // ======================================================
 
 let mut baseline_drift_detected = false;
+let mut drift_severity: Option<DriftSeverity> = None;
 
 if let Some(baseline) = load_baseline(&stack_name) {
 
-    let drift = compare_to_baseline(
+    let mut drift = compare_to_baseline(
         &baseline,
         &service_scores,
         confidence,
@@ -330,22 +765,25 @@ if let Some(baseline) = load_baseline(&stack_name) {
         duration,
     );
 
-    let has_drift =
-        !drift.new_services.is_empty()
-        || !drift.missing_services.is_empty()
-        || drift.confidence_delta != 0
-        || drift.readiness_delta.unwrap_or(0) != 0
-        || drift.duration_delta_percent.unwrap_or(0) != 0;
+    // A partial run's duration says nothing about the whole stack's.
+    if !reused.is_empty() {
+        drift.duration_delta_percent = None;
+    }
+
+    let severity = drift.severity();
+    let has_drift = severity != DriftSeverity::None;
+
+    drift_severity = Some(severity);
 
     if has_drift {
         baseline_drift_detected = true;
     }
 
-    if has_drift && !json_output {
+    if has_drift && show {
 
         println!();
-        println!("BASELINE DRIFT DETECTED");
-        println!("-----------------------");
+        println!("BASELINE DRIFT DETECTED ({})", severity);
+        println!("-------------------------------");
 
         for svc in drift.new_services {
             println!("+ New service: {}", svc);
@@ -378,80 +816,138 @@ if let Some(baseline) = load_baseline(&stack_name) {
 // POLICY ENFORCEMENT
 // ======================================================
 
-let mut policy_violation = false;
+let mut policy_checks: Vec<PolicyCheck> = Vec::new();
 
 if let Some(policy) = load_policy(&stack_name) {
 
-    if let Some(min) = policy.min_confidence {
-        if confidence < min {
-            eprintln!(
-                "POLICY VIOLATION: confidence {} below minimum {}",
-                confidence, min
-            );
-            policy_violation = true;
-        }
+    fn pass_fail(pass: bool) -> CheckResult {
+        if pass { CheckResult::Pass } else { CheckResult::Fail }
     }
 
-    if let Some(min_ready) = policy.min_readiness {
-        if readiness.score < min_ready {
-            eprintln!(
-                "POLICY VIOLATION: restore readiness {} below minimum {}",
-                readiness.score, min_ready
-            );
-            policy_violation = true;
+    policy_checks.push(match policy.min_confidence {
+        Some(min) => PolicyCheck {
+            rule:      "min_confidence",
+            threshold: format!(">= {}%", min),
+            observed:  format!("{}%", confidence),
+            result:    pass_fail(confidence >= min),
+        },
+        None => PolicyCheck::skip("min_confidence"),
+    });
+
+    policy_checks.push(match policy.min_readiness {
+        Some(min) => PolicyCheck {
+            rule:      "min_readiness",
+            threshold: format!(">= {}%", min),
+            observed:  format!("{}%", readiness.score),
+            result:    pass_fail(readiness.score >= min),
+        },
+        None => PolicyCheck::skip("min_readiness"),
+    });
+
+    policy_checks.push(if policy.block_on_regression.unwrap_or(false) {
+        let delta = regression.confidence_delta;
+        PolicyCheck {
+            rule:      "block_on_regression",
+            threshold: "delta >= 0".to_string(),
+            observed:  delta.map(|d| format!("{:+}%", d)).unwrap_or_else(|| "no prior run".to_string()),
+            result:    pass_fail(delta.is_none_or(|d| d >= 0)),
         }
-    }
+    } else {
+        PolicyCheck::skip("block_on_regression")
+    });
 
-    if policy.block_on_regression.unwrap_or(false) {
-        if let Some(delta) = regression.confidence_delta {
-            if delta < 0 {
-                eprintln!("POLICY VIOLATION: regression detected");
-                policy_violation = true;
-            }
+    policy_checks.push(if policy.fail_on_new_service_failure.unwrap_or(false) {
+        let mut failed: Vec<&str> = service_scores
+            .iter()
+            .filter(|(_, s)| bands.is_failed(**s))
+            .map(|(n, _)| n.as_str())
+            .collect();
+        failed.sort();
+        PolicyCheck {
+            rule:      "fail_on_new_service_failure",
+            threshold: "no failures".to_string(),
+            observed:  if failed.is_empty() { "none".to_string() } else { failed.join(", ") },
+            result:    pass_fail(failed.is_empty()),
         }
-    }
+    } else {
+        PolicyCheck::skip("fail_on_new_service_failure")
+    });
 
-    if policy.fail_on_new_service_failure.unwrap_or(false) {
-        for (service, score) in &service_scores {
-            if *score == 0 {
-                eprintln!(
-                    "POLICY VIOLATION: service '{}' failed to boot",
-                    service
-                );
-                policy_violation = true;
-            }
+    policy_checks.push(if policy.fail_on_duration_spike.unwrap_or(false) {
+        let limit = policy.duration_spike_percent.unwrap_or(DEFAULT_DURATION_SPIKE_PERCENT);
+        let spike = regression.duration_delta_percent;
+        PolicyCheck {
+            rule:      "fail_on_duration_spike",
+            threshold: format!("<= +{}%", limit),
+            observed:  spike.map(|s| format!("{:+}%", s)).unwrap_or_else(|| "no prior run".to_string()),
+            result:    pass_fail(spike.is_none_or(|s| s <= limit as i32)),
         }
-    }
+    } else {
+        PolicyCheck::skip("fail_on_duration_spike")
+    });
 
-    if policy.fail_on_duration_spike.unwrap_or(false) {
-        if let Some(spike) = regression.duration_delta_percent {
-            if spike > policy.duration_spike_percent.unwrap_or(50) as i32 {
-                eprintln!(
-                    "POLICY VIOLATION: duration spike {}%",
-                    spike
-                );
-                policy_violation = true;
-            }
+    policy_checks.push(if policy.fail_on_baseline_drift.unwrap_or(false) {
+        PolicyCheck {
+            rule:      "fail_on_baseline_drift",
+            threshold: "no drift".to_string(),
+            observed:  if baseline_drift_detected { "drift" } else { "none" }.to_string(),
+            result:    pass_fail(!baseline_drift_detected),
         }
-    }
+    } else {
+        PolicyCheck::skip("fail_on_baseline_drift")
+    });
 
-    // --------------------------------------------------
-    // Baseline Drift Enforcement (NEW)
-    // --------------------------------------------------
-    if policy.fail_on_baseline_drift.unwrap_or(false) {
-        if baseline_drift_detected {
-            eprintln!("POLICY VIOLATION: baseline drift detected");
-            policy_violation = true;
+    policy_checks.push(if policy.fail_on_major_drift.unwrap_or(false) {
+        PolicyCheck {
+            rule:      "fail_on_major_drift",
+            threshold: "no major drift".to_string(),
+            observed:  drift_severity.map_or("no baseline".to_string(), |s| s.to_string()),
+            result:    pass_fail(drift_severity != Some(DriftSeverity::Major)),
         }
+    } else {
+        PolicyCheck::skip("fail_on_major_drift")
+    });
+
+    // Absolute, so enforced even on a stack's first run.
+    policy_checks.push(match policy.max_duration_seconds {
+        Some(max) => PolicyCheck {
+            rule:      "max_duration_seconds",
+            threshold: format!("<= {}s", max),
+            observed:  format!("{}s", duration),
+            result:    pass_fail(duration <= max),
+        },
+        None => PolicyCheck::skip("max_duration_seconds"),
+    });
+
+    // One check per floor; a listed service absent from the run fails.
+    let mut floors: Vec<(&String, &u32)> = policy.min_service_scores.iter().flatten().collect();
+    floors.sort();
+    for (service, &min) in floors {
+        let score = service_scores.get(service).copied();
+        policy_checks.push(PolicyCheck {
+            rule:      "min_service_score",
+            threshold: format!("{} >= {}", service, min),
+            observed:  score.map_or(format!("{} missing", service), |s| format!("{} {}", service, s)),
+            result:    pass_fail(score.is_some_and(|s| s >= min)),
+        });
+    }
+
+    for check in policy_checks.iter().filter(|c| c.result == CheckResult::Fail) {
+        eprintln!(
+            "POLICY VIOLATION: {} — observed {}, required {}",
+            check.rule, check.observed, check.threshold
+        );
     }
 }
 
+let policy_violation = policy_checks.iter().any(|c| c.result == CheckResult::Fail);
+
 // ======================================================
-// JSON OUTPUT
+// STRUCTURED OUTPUT
 // ======================================================
 
-if json_output {
-    println!("{}", serde_json::to_string_pretty(&json!({
+if format.is_structured() {
+    println!("{}", render(&json!({
         "stack": stack_name,
         "restore_readiness": readiness.score,
         "confidence": confidence,
@@ -467,95 +963,183 @@ if json_output {
         "duration_delta_percent": regression.duration_delta_percent,
 
         "baseline_drift_detected": baseline_drift_detected,
+        "drift_severity": drift_severity,
 
         "stability": stability,
         "risk": risk,
-        "services": service_scores
-    }))?);
+        "services": service_scores,
+        "service_logs": service_logs,
+        "start_attempts": start_attempts,
+        "reused_services": reused_services,
+        "policy_checks": policy_checks
+    }), format).map_err(anyhow::Error::msg)?);
 }
 
 // ======================================================
 // EXIT LOGIC
 // ======================================================
 
-let exit_code =
-    if policy_violation {
-        4
-    } else if baseline_drift_detected
-        && load_policy(&stack_name)
-            .and_then(|p| p.fail_on_baseline_drift)
-            .unwrap_or(false)
-    {
-        5
-    } else if confidence >= 70 {
-        0
-    } else if confidence >= 40 {
-        2
-    } else {
-        3
-    };
-
-// Create summary BEFORE moving values
-let summary = StackRunSummary {
-    stack: stack_name.clone(),
-    readiness: readiness.score,
+let summary = StackRunSummary::new(
+    stack_name,
+    readiness.score,
     confidence,
-    duration,
-    risk: risk.to_string(),
-    service_scores: service_scores.clone(),
-    policy_violated: policy_violation,
-    baseline_drift: baseline_drift_detected,
-};
+    policy_violation,
+    baseline_drift_detected,
+    drift_severity,
+    policy_checks,
+);
+let exit_code = summary.exit_code;
 
 let record = RunRecord {
-    schema_version: CURRENT_SCHEMA_VERSION,
-    stack: stack_name,
     timestamp: now_timestamp(),
     duration_seconds: duration,
-    confidence,
-    readiness: Some(readiness.score),
     risk: risk.to_string(),
-    exit_code,
     services: service_scores,
+    images_from_archive,
+    host: Some(get_hostname()),
+    docker_version,
+    service_classes,
+    preflight_findings: finding_records(&readiness),
+    service_logs,
+    service_hashes,
+    reused_services,
     hash: None,
+    ..summary.history_record()
 };
 
 let _ = persist(&record);
 
-if !json_output {
-    println!();
-    println!(
-        "✓ confidence {}%  readiness {}%  risk {}  duration {}s",
-        confidence, readiness.score, risk, duration
-    );
-    println!();
-    if baseline_drift_detected {
-        println!("DRIFT DETECTED");
-    } else if policy_violation {
-        println!("POLICY VIOLATION");
+if !format.is_structured() {
+    // Same precedence as the exit code: policy, then drift.
+    let verdict = match exit_code {
+        ExitCode::PolicyViolation => "POLICY VIOLATION".to_string(),
+        ExitCode::BaselineDrift => match drift_severity.filter(|s| *s != DriftSeverity::None) {
+            Some(severity) => format!("DRIFT DETECTED ({})", severity),
+            None           => "DRIFT DETECTED".to_string(),
+        },
+        _ => "CONTRACT HONOURED".to_string(),
+    };
+
+    if verbosity == Verbosity::Quiet {
+        println!(
+            "{}  confidence {}%  readiness {}%  risk {}  duration {}s",
+            verdict, confidence, readiness.score, risk, duration
+        );
     } else {
-        println!("CONTRACT HONOURED");
+        println!();
+        println!(
+            "✓ confidence {}%  readiness {}%  risk {}  duration {}s",
+            confidence, readiness.score, risk, duration
+        );
+        println!();
+        println!("{}", verdict);
     }
 }
 
 Ok(summary)
 }
 // ======================================================
-// IMAGE PULL
+// CHANGED-ONLY SELECTION
 // ======================================================
 
-async fn pull_image(docker: &Docker, image: &str) -> Result<()> {
-    let options = Some(CreateImageOptions {
-        from_image: image,
-        ..Default::default()
-    });
+/// Baseline scores of the services a `--changed-only` run can skip.
+///
+/// A service has changed when its definition hash differs from the
+/// baseline's (or either side lacks one), or when it is an
+/// --inject-failure target. Changed services and their dependents are
+/// rehearsed, along with the dependencies they need to start. Every other
+/// service with a baseline score is reused.
+fn reusable_scores(
+    compose: &ComposeFile,
+    hashes: &BTreeMap<String, String>,
+    baseline: &StackBaseline,
+    forced: &[String],
+) -> HashMap<String, u32> {
+    let mut rehearse: HashSet<String> = compose.services
+        .keys()
+        .filter(|name| {
+            forced.contains(name)
+                || !baseline.service_scores.contains_key(*name)
+                || hashes.get(*name).is_none_or(|h| baseline.service_hashes.get(*name) != Some(h))
+        })
+        .cloned()
+        .collect();
+
+    // Dependents of a changed service, transitively.
+    loop {
+        let dependents: Vec<String> = compose.services
+            .iter()
+            .filter(|(name, service)| {
+                !rehearse.contains(*name)
+                    && service.depends_on.iter().flatten().any(|d| rehearse.contains(&d.service))
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if dependents.is_empty() {
+            break;
+        }
+        rehearse.extend(dependents);
+    }
+
+    // Dependencies those services need to start, transitively.
+    let mut pending: Vec<String> = rehearse.iter().cloned().collect();
+    while let Some(name) = pending.pop() {
+        for dep in compose.services[&name].depends_on.iter().flatten() {
+            if compose.services.contains_key(&dep.service) && rehearse.insert(dep.service.clone()) {
+                pending.push(dep.service.clone());
+            }
+        }
+    }
 
-    docker
-        .create_image(options, None, None)
-        .try_collect::<Vec<_>>()
-        .await?;
+    compose.services
+        .keys()
+        .filter(|name| !rehearse.contains(*name))
+        .filter_map(|name| baseline.service_scores.get(name).map(|s| (name.clone(), *s)))
+        .collect()
+}
 
-    Ok(())
+// ======================================================
+// IMAGE ARCHIVE LOAD
+// ======================================================
+
+/// `docker load` every `*.tar` in `dir` (sorted by name) and return the
+/// image references the daemon reports as loaded.
+async fn load_image_archive(docker: &Docker, dir: &Path) -> Result<Vec<String>> {
+    let mut archives: Vec<_> = fs::read_dir(dir)
+        .map_err(|e| anyhow!("Failed to read image archive dir {}: {}", dir.display(), e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|x| x == "tar").unwrap_or(false))
+        .collect();
+
+    archives.sort();
+
+    let mut loaded = Vec::new();
+
+    for archive in archives {
+        let bytes = fs::read(&archive)
+            .map_err(|e| anyhow!("Failed to read {}: {}", archive.display(), e))?;
+
+        let output = docker
+            .import_image(ImportImageOptions { quiet: true }, bytes.into(), None)
+            .try_collect::<Vec<_>>()
+            .await
+            .map_err(|e| anyhow!("Failed to load {}: {}", archive.display(), e))?;
+
+        for info in output {
+            let Some(line) = info.stream else { continue };
+            for l in line.lines() {
+                if let Some(image) = l
+                    .strip_prefix("Loaded image: ")
+                    .or_else(|| l.strip_prefix("Loaded image ID: "))
+                {
+                    loaded.push(image.trim().to_string());
+                }
+            }
+        }
+    }
+
+    Ok(loaded)
 }
 
 // ======================================================
@@ -568,33 +1152,313 @@ fn convert_healthcheck(h: &HealthCheck) -> HealthConfig {
         interval: parse_duration(&h.interval),
         timeout: parse_duration(&h.timeout),
         retries: h.retries.map(|r| r as i64),
-        start_period: None,
+        start_period: parse_duration(&h.start_period),
         start_interval: None,
     }
 }
 
+/// Compose (Go-style) duration in nanoseconds, as Docker expects:
+/// `500ms`, `30s`, `2h`, compounds like `1m30s` and fractions like `1.5s`.
+/// A bare `0` is zero, as in Go; other unitless numbers and anything
+/// unparseable give None.
 fn parse_duration(input: &Option<String>) -> Option<i64> {
-    if let Some(val) = input {
-        if let Some(stripped) = val.strip_suffix("s") {
-            if let Ok(secs) = stripped.parse::<i64>() {
-                return Some(secs * 1_000_000_000);
-            }
-        }
+    let mut rest = input.as_deref()?.trim();
+
+    if rest.is_empty() {
+        return None;
+    }
+    if rest == "0" {
+        return Some(0);
+    }
+
+    let mut total = 0f64;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+
+        let units = rest.find(|c: char| c.is_ascii_digit() || c == '.').unwrap_or(rest.len());
+        let unit_ns = match &rest[..units] {
+            "ns"        => 1.0,
+            "us" | "µs" => 1e3,
+            "ms"        => 1e6,
+            "s"         => 1e9,
+            "m"         => 60e9,
+            "h"         => 3600e9,
+            _           => return None,
+        };
+        rest = &rest[units..];
+
+        total += number * unit_ns;
+    }
+
+    (total <= i64::MAX as f64).then_some(total.round() as i64)
+}
+
+// ======================================================
+// CONTAINER CONFIG
+// ======================================================
+
+/// Container config for a rehearsed service: the Compose entrypoint,
+/// command, environment and healthcheck, attached to the rehearsal
+/// network under the service name.
+fn container_config(
+    service_name: &str,
+    service: &Service,
+    image: String,
+    network_name: &str,
+    mounts: Vec<Mount>,
+) -> Config<String> {
+    let mut endpoints: HashMap<String, EndpointSettings> = HashMap::new();
+
+    endpoints.insert(
+        network_name.to_string(),
+        EndpointSettings {
+            aliases: Some(vec![service_name.to_string()]),
+            ..Default::default()
+        },
+    );
+
+    Config {
+        image: Some(image),
+        env: service.environment.clone(),
+        entrypoint: service.entrypoint.clone(),
+        cmd: service.command.clone(),
+        healthcheck: service.healthcheck.as_ref().map(convert_healthcheck),
+        host_config: Some(HostConfig {
+            mounts: Some(mounts),
+            ..Default::default()
+        }),
+        networking_config: Some(NetworkingConfig {
+            endpoints_config: endpoints,
+        }),
+        ..Default::default()
     }
-    None
 }
 
 // ======================================================
 // WAIT + SCORE
 // ======================================================
 
+/// Pull, create and start one service's container, then wait for and score it.
+#[allow(clippy::too_many_arguments)]
+async fn start_and_score(
+    docker: &Docker,
+    images: &ImageCache,
+    service_name: &str,
+    service: &Service,
+    run_id: &str,
+    network_name: &str,
+    pull_policy: &PullPolicy,
+    timeout: u64,
+    probe_window: u64,
+    mounts: Vec<Mount>,
+    bands: ScoreBands,
+    retries: u32,
+    verbose: bool,
+) -> Result<(u32, ServiceOutcome, u32)> {
+
+    let image = service.image.clone()
+        .ok_or_else(|| anyhow!("Service {} has no image", service_name))?;
+
+    // An image preflight already pulled, or another service already
+    // confirmed, is not touched again — even under PullPolicy::Always.
+    match pull_policy {
+        PullPolicy::Always => images.ensure(&image, || pull_image(docker, &image)).await?,
+        PullPolicy::IfMissing => images.ensure(&image, || async {
+            if docker.inspect_image(&image).await.is_err() {
+                pull_image(docker, &image).await?;
+            }
+            Ok(())
+        }).await?,
+        PullPolicy::Never => images.ensure(&image, || async {
+            docker.inspect_image(&image).await.map(|_| ()).map_err(|_| anyhow!(
+                "Image '{}' not present and pull policy = Never",
+                image
+            ))
+        }).await?,
+    }
+
+    let container_name =
+        format!("rehearsa_{}_{}", run_id, service_name);
+
+    let config = container_config(service_name, service, image, network_name, mounts);
+
+    let kind = service.classify().kind;
+    let start_period = service.healthcheck.as_ref()
+        .and_then(|h| parse_duration(&h.start_period))
+        .map_or(0, |ns| (ns / 1_000_000_000) as u64);
+    let started = Instant::now();
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+
+        docker.create_container(
+            Some(CreateContainerOptions {
+                name: container_name.clone(),
+                platform: None,
+            }),
+            config.clone(),
+        ).await?;
+
+        docker.start_container(
+            &container_name,
+            None::<StartContainerOptions<String>>,
+        ).await?;
+
+        // Retries share the service's timeout rather than each getting a fresh one.
+        let remaining = timeout.saturating_sub(started.elapsed().as_secs());
+        let waited = Instant::now();
+        let (score, outcome) = wait_and_score(docker, &container_name, remaining, probe_window, kind, bands, start_period).await?;
+
+        // A clean exit is a oneshot's success, never a transient failure.
+        let clean_oneshot = kind != ServiceKind::LongRunning
+            && outcome == ServiceOutcome::Exited(Some(0));
+
+        let retry = matches!(outcome, ServiceOutcome::Exited(_))
+            && !clean_oneshot
+            && attempts <= retries
+            && started.elapsed().as_secs() < timeout;
+
+        if verbose {
+            println!(
+                "  {} attempt {}: {} after {:.1}s (score {}){}",
+                service_name,
+                attempts,
+                outcome,
+                waited.elapsed().as_secs_f64(),
+                score,
+                if retry { " — retrying" } else { "" }
+            );
+        }
+
+        if !retry {
+            return Ok((score, outcome, attempts));
+        }
+
+        docker.remove_container(
+            &container_name,
+            Some(bollard::container::RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        ).await?;
+    }
+}
+
+/// How a service's wait ended — checked against dependents' `depends_on` conditions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ServiceOutcome {
+    Healthy,
+    Unhealthy,
+    Running,
+    Exited(Option<i64>),
+    TimedOut,
+    NotStarted,
+}
+
+impl std::fmt::Display for ServiceOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ServiceOutcome::Healthy            => write!(f, "healthy"),
+            ServiceOutcome::Unhealthy          => write!(f, "unhealthy"),
+            ServiceOutcome::Running            => write!(f, "running"),
+            ServiceOutcome::Exited(Some(code)) => write!(f, "exited with code {}", code),
+            ServiceOutcome::Exited(None)       => write!(f, "exited"),
+            ServiceOutcome::TimedOut           => write!(f, "timed out"),
+            ServiceOutcome::NotStarted         => write!(f, "not started"),
+        }
+    }
+}
+
+/// First `depends_on` edge of `service` whose condition is not met, as a
+/// reason string. `service_healthy` on a dependency without a healthcheck
+/// falls back to `service_started` (flagged by DependencyConditionRule).
+/// `service_completed_successfully` waits for a still-running dependency to exit.
+async fn unmet_dependency(
+    docker: &Docker,
+    compose: &ComposeFile,
+    service: &Service,
+    run_id: &str,
+    timeout: u64,
+    outcomes: &mut HashMap<String, ServiceOutcome>,
+) -> Result<Option<String>> {
+
+    for dep in service.depends_on.iter().flatten() {
+        let has_healthcheck = compose.services
+            .get(&dep.service)
+            .is_some_and(|s| s.healthcheck.is_some());
+
+        let condition = match dep.condition {
+            DependsCondition::Healthy if !has_healthcheck => DependsCondition::Started,
+            c => c,
+        };
+
+        let mut outcome = outcomes
+            .get(&dep.service)
+            .copied()
+            .unwrap_or(ServiceOutcome::NotStarted);
+
+        if condition == DependsCondition::CompletedSuccessfully
+            && matches!(outcome, ServiceOutcome::Running | ServiceOutcome::Healthy)
+        {
+            let container = format!("rehearsa_{}_{}", run_id, dep.service);
+            outcome = wait_for_exit(docker, &container, timeout).await?;
+            outcomes.insert(dep.service.clone(), outcome);
+        }
+
+        let met = match condition {
+            DependsCondition::Started               => outcome != ServiceOutcome::NotStarted,
+            DependsCondition::Healthy               => outcome == ServiceOutcome::Healthy,
+            DependsCondition::CompletedSuccessfully => outcome == ServiceOutcome::Exited(Some(0)),
+        };
+
+        if !met {
+            return Ok(Some(format!(
+                "dependency '{}' did not reach {} ({})",
+                dep.service, condition, outcome
+            )));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Poll until the container exits or `timeout` seconds pass.
+async fn wait_for_exit(docker: &Docker, container: &str, timeout: u64) -> Result<ServiceOutcome> {
+    for _ in 0..timeout {
+        let inspect = docker.inspect_container(container, None).await?;
+        if let Some(state) = inspect.state {
+            if state.status == Some(ContainerStateStatusEnum::EXITED) {
+                return Ok(ServiceOutcome::Exited(state.exit_code));
+            }
+        }
+        sleep(Duration::from_secs(1)).await;
+    }
+    Ok(ServiceOutcome::TimedOut)
+}
+
+/// A service with no healthcheck scores 85 once RUNNING. With a non-zero
+/// `probe_window` it must still be RUNNING after that many seconds — a container that boots then dies
+/// falls through to the EXITED scoring instead. UNHEALTHY is not final
+/// until the healthcheck's `start_period` (seconds) has elapsed.
 async fn wait_and_score(
     docker: &Docker,
     container: &str,
     timeout: u64,
-) -> Result<u32> {
+    probe_window: u64,
+    kind: ServiceKind,
+    bands: ScoreBands,
+    start_period: u64,
+) -> Result<(u32, ServiceOutcome)> {
 
     let mut elapsed = 0;
+    let mut running_since: Option<u64> = None;
+    let mut unhealthy_in_start_period = false;
 
     while elapsed < timeout {
 
@@ -603,19 +1467,38 @@ async fn wait_and_score(
         if let Some(state) = inspect.state {
             match state.status {
 
+                // A labelled oneshot is judged only by how it exits —
+                // keep waiting while it runs.
+                Some(ContainerStateStatusEnum::RUNNING) if kind == ServiceKind::OneshotLabelled => {}
+
                 Some(ContainerStateStatusEnum::RUNNING) => {
                     if let Some(health) = state.health {
                         match health.status {
-                            Some(HealthStatusEnum::HEALTHY) => return Ok(100),
-                            Some(HealthStatusEnum::UNHEALTHY) => return Ok(40),
+                            Some(HealthStatusEnum::HEALTHY) => return Ok((bands.healthy, ServiceOutcome::Healthy)),
+                            // Still warming up — it may yet turn healthy.
+                            Some(HealthStatusEnum::UNHEALTHY) if elapsed < start_period => {
+                                unhealthy_in_start_period = true;
+                            }
+                            Some(HealthStatusEnum::UNHEALTHY) => return Ok((bands.unhealthy, ServiceOutcome::Unhealthy)),
                             _ => {}
                         }
                     } else {
-                        return Ok(85);
+                        let since = *running_since.get_or_insert(elapsed);
+                        if elapsed - since >= probe_window {
+                            return Ok((bands.running, ServiceOutcome::Running));
+                        }
                     }
                 }
 
-                Some(ContainerStateStatusEnum::EXITED) => return Ok(0),
+                Some(ContainerStateStatusEnum::EXITED) => {
+                    let score = match kind {
+                        // Oneshot (labelled or inferred): only a clean exit counts
+                        ServiceKind::OneshotLabelled | ServiceKind::OneshotInferred
+                            if state.exit_code == Some(0) => bands.healthy,
+                        _ => bands.failed,
+                    };
+                    return Ok((score, ServiceOutcome::Exited(state.exit_code)));
+                }
 
                 _ => {}
             }
@@ -625,18 +1508,108 @@ async fn wait_and_score(
         elapsed += 1;
     }
 
-    Ok(0)
+    // Still running when the timeout cut the probe window short —
+    // it survived as long as we could observe.
+    if running_since.is_some() {
+        return Ok((bands.running, ServiceOutcome::Running));
+    }
+
+    // The timeout ended the start period early; unhealthy is the last word.
+    if unhealthy_in_start_period {
+        return Ok((bands.unhealthy, ServiceOutcome::Unhealthy));
+    }
+
+    // Includes a labelled oneshot that never exited — it did not complete.
+    Ok((bands.failed, ServiceOutcome::TimedOut))
+}
+
+/// Lines of log kept per failed service.
+const SERVICE_LOG_TAIL_LINES: usize = 50;
+/// Byte cap per service so long lines cannot bloat history files.
+const SERVICE_LOG_MAX_BYTES: usize = 8 * 1024;
+
+/// Last SERVICE_LOG_TAIL_LINES lines of a container's stdout and stderr,
+/// trimmed to SERVICE_LOG_MAX_BYTES from the end. None if nothing was logged.
+async fn tail_logs(docker: &Docker, container: &str) -> Option<String> {
+    let chunks: Vec<_> = docker
+        .logs(container, Some(LogsOptions::<String> {
+            stdout: true,
+            stderr: true,
+            tail: SERVICE_LOG_TAIL_LINES.to_string(),
+            ..Default::default()
+        }))
+        .try_collect()
+        .await
+        .ok()?;
+
+    let logs: String = chunks.iter().map(|c| c.to_string()).collect();
+    let logs = logs.trim_end();
+    if logs.is_empty() {
+        return None;
+    }
+
+    let mut start = logs.len().saturating_sub(SERVICE_LOG_MAX_BYTES);
+    while !logs.is_char_boundary(start) {
+        start += 1;
+    }
+    Some(logs[start..].to_string())
+}
+
+/// Tell the operator what a kept failed run left behind. Goes to stderr so
+/// JSON output on stdout stays parseable.
+fn print_kept_resources(containers: &[String], network: &str, sandbox: &Path) {
+    eprintln!();
+    eprintln!("Rehearsal failed — resources kept for inspection (--keep-on-failure):");
+    for container in containers {
+        eprintln!("  container  {}", container);
+    }
+    eprintln!("  network    {}", network);
+    if sandbox.exists() {
+        eprintln!("  clones     {}", sandbox.display());
+    }
+    eprintln!();
+    if let Some(first) = containers.first() {
+        eprintln!("  docker logs {}", first);
+        eprintln!("  docker inspect {}", first);
+    }
+    eprintln!("Remove them with: rehearsa cleanup");
+    if sandbox.exists() {
+        eprintln!("Cloned data is not removed by cleanup: rm -rf {}", sandbox.display());
+    }
+    eprintln!();
 }
 
 // ======================================================
 // ORPHAN CLEANUP
 // ======================================================
 
-/// Remove any containers and networks left behind by crashed rehearsals.
-/// Rehearsa prefixes all temporary resources with "rehearsa_" — anything
-/// matching that prefix that is not currently running a rehearsal is orphaned.
-pub async fn cleanup_orphans() -> Result<()> {
-    let docker = Docker::connect_with_local_defaults()?;
+/// Container names start with this; networks with one of `NETWORK_PREFIXES`.
+const CONTAINER_PREFIX: &str = "rehearsa_";
+const NETWORK_PREFIXES: [&str; 2] = ["rehearsa_stack_", "rehearsa_net_"];
+
+/// Minutes since an RFC 3339 `Created` timestamp, as Docker reports it.
+fn minutes_since(created: Option<&str>) -> Option<i64> {
+    let created = chrono::DateTime::parse_from_rfc3339(created?).ok()?;
+    Some((chrono::Utc::now() - created.with_timezone(&chrono::Utc)).num_minutes())
+}
+
+fn format_age(minutes: Option<i64>) -> String {
+    match minutes {
+        None                 => "age unknown".to_string(),
+        Some(m) if m < 60    => format!("{}m", m),
+        Some(m) if m < 1440  => format!("{}h {}m", m / 60, m % 60),
+        Some(m)              => format!("{}d {}h", m / 1440, (m % 1440) / 60),
+    }
+}
+
+/// Remove containers and networks left behind by crashed or kept
+/// rehearsals. Only resources older than `older_than_minutes` are touched,
+/// so a rehearsal still in progress is never reaped; a resource whose age
+/// cannot be read is kept. With `dry_run` nothing is removed.
+pub async fn cleanup_orphans(older_than_minutes: u64, dry_run: bool) -> Result<()> {
+    let docker = connect_docker()?;
+    let threshold = older_than_minutes as i64;
+    let is_stale = |age: Option<i64>| age.is_some_and(|m| m >= threshold);
 
     let containers = docker.list_containers(
         Some(bollard::container::ListContainersOptions::<String> {
@@ -646,28 +1619,44 @@ pub async fn cleanup_orphans() -> Result<()> {
     ).await?;
 
     let mut removed_containers = 0;
+    let mut kept = 0;
 
     for container in containers {
         let names = container.names.unwrap_or_default();
-        let is_rehearsa = names.iter().any(|n| {
-            n.trim_start_matches('/').starts_with("rehearsa_")
-        });
+        let Some(name) = names
+            .iter()
+            .map(|n| n.trim_start_matches('/'))
+            .find(|n| n.starts_with(CONTAINER_PREFIX))
+        else {
+            continue;
+        };
+        let Some(id) = container.id else { continue };
+
+        let age = docker.inspect_container(&id, None).await
+            .ok()
+            .and_then(|c| minutes_since(c.created.as_deref()));
+
+        if !is_stale(age) {
+            println!("Keeping container {} ({})", name, format_age(age));
+            kept += 1;
+            continue;
+        }
 
-        if is_rehearsa {
-            if let Some(id) = container.id {
-                let name = names.first().cloned().unwrap_or_default();
-                print!("Removing container {} ... ", name.trim_start_matches('/'));
-                match docker.remove_container(
-                    &id,
-                    Some(bollard::container::RemoveContainerOptions {
-                        force: true,
-                        ..Default::default()
-                    }),
-                ).await {
-                    Ok(_) => { println!("✓"); removed_containers += 1; }
-                    Err(e) => println!("✗ ({})", e),
-                }
-            }
+        if dry_run {
+            println!("Would remove container {} ({})", name, format_age(age));
+            removed_containers += 1;
+            continue;
+        }
+        print!("Removing container {} ({}) ... ", name, format_age(age));
+        match docker.remove_container(
+            &id,
+            Some(bollard::container::RemoveContainerOptions {
+                force: true,
+                ..Default::default()
+            }),
+        ).await {
+            Ok(_) => { println!("✓"); removed_containers += 1; }
+            Err(e) => println!("✗ ({})", e),
         }
     }
 
@@ -681,21 +1670,140 @@ pub async fn cleanup_orphans() -> Result<()> {
 
     for network in networks {
         let name = network.name.unwrap_or_default();
-        if name.starts_with("rehearsa_stack_") {
-            print!("Removing network {} ... ", name);
-            match docker.remove_network(&name).await {
-                Ok(_) => { println!("✓"); removed_networks += 1; }
-                Err(e) => println!("✗ ({})", e),
-            }
+        if !NETWORK_PREFIXES.iter().any(|p| name.starts_with(p)) {
+            continue;
+        }
+
+        let age = minutes_since(network.created.as_deref());
+
+        if !is_stale(age) {
+            println!("Keeping network {} ({})", name, format_age(age));
+            kept += 1;
+            continue;
+        }
+
+        if dry_run {
+            println!("Would remove network {} ({})", name, format_age(age));
+            removed_networks += 1;
+            continue;
+        }
+        print!("Removing network {} ({}) ... ", name, format_age(age));
+        match docker.remove_network(&name).await {
+            Ok(_) => { println!("✓"); removed_networks += 1; }
+            Err(e) => println!("✗ ({})", e),
         }
     }
 
-    if removed_containers == 0 && removed_networks == 0 {
+    if removed_containers == 0 && removed_networks == 0 && kept == 0 {
         println!("No orphaned rehearsal resources found.");
-    } else {
-        println!();
-        println!("Cleaned up {} container(s), {} network(s).", removed_containers, removed_networks);
+        return Ok(());
     }
 
+    println!();
+    println!(
+        "{} {} container(s), {} network(s); kept {} newer than {} minute(s).",
+        if dry_run { "Would remove" } else { "Removed" },
+        removed_containers, removed_networks, kept, older_than_minutes
+    );
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::compose::parse_compose;
+
+    fn config_for(yaml: &str) -> Config<String> {
+        let compose = parse_compose(yaml).unwrap();
+        let service = &compose.services["app"];
+        container_config("app", service, "busybox".to_string(), "rehearsa_net_x", Vec::new())
+    }
+
+    #[test]
+    fn container_starts_with_the_compose_entrypoint_and_command() {
+        let config = config_for(
+            "services:\n  app:\n    image: busybox\n    entrypoint: [\"/bin/sh\", \"-c\"]\n    command: [\"echo ok\"]\n",
+        );
+        assert_eq!(config.entrypoint, Some(vec!["/bin/sh".to_string(), "-c".to_string()]));
+        assert_eq!(config.cmd, Some(vec!["echo ok".to_string()]));
+    }
+
+    #[test]
+    fn container_keeps_the_image_entrypoint_when_compose_sets_none() {
+        let config = config_for("services:\n  app:\n    image: busybox\n");
+        assert_eq!(config.entrypoint, None);
+        assert_eq!(config.cmd, None);
+    }
+
+    fn duration(s: &str) -> Option<i64> {
+        parse_duration(&Some(s.to_string()))
+    }
+
+    #[test]
+    fn parses_go_style_durations() {
+        assert_eq!(duration("30s"),   Some(30_000_000_000));
+        assert_eq!(duration("1m"),    Some(60_000_000_000));
+        assert_eq!(duration("1m30s"), Some(90_000_000_000));
+        assert_eq!(duration("500ms"), Some(500_000_000));
+        assert_eq!(duration("2h"),    Some(7_200_000_000_000));
+        assert_eq!(duration("1.5s"),  Some(1_500_000_000));
+    }
+
+    #[test]
+    fn bare_zero_is_zero_but_other_unitless_numbers_are_rejected() {
+        assert_eq!(duration("0"), Some(0));
+        assert_eq!(duration("30"), None);
+    }
+
+    #[test]
+    fn rejects_empty_and_garbage_durations() {
+        assert_eq!(parse_duration(&None), None);
+        assert_eq!(duration(""), None);
+        assert_eq!(duration("   "), None);
+        assert_eq!(duration("soon"), None);
+        assert_eq!(duration("10x"), None);
+        assert_eq!(duration("1m30"), None);
+        assert_eq!(duration("s"), None);
+    }
+
+    fn persisted_exit_code(summary: &StackRunSummary) -> i64 {
+        serde_json::to_value(summary.history_record()).unwrap()["exit_code"]
+            .as_i64()
+            .unwrap()
+    }
+
+    #[test]
+    fn policy_violation_is_persisted_as_exit_code_4() {
+        let summary = StackRunSummary::new(
+            "app".to_string(), 90, 95, true, true, Some(DriftSeverity::Major), Vec::new(),
+        );
+
+        assert_eq!(summary.exit_code, ExitCode::PolicyViolation);
+        assert_eq!(persisted_exit_code(&summary), 4);
+        assert_eq!(persisted_exit_code(&summary), summary.exit_code as i64);
+    }
+
+    #[test]
+    fn baseline_drift_is_persisted_as_exit_code_5() {
+        let summary = StackRunSummary::new(
+            "app".to_string(), 90, 95, false, true, Some(DriftSeverity::Minor), Vec::new(),
+        );
+
+        assert_eq!(summary.exit_code, ExitCode::BaselineDrift);
+        assert_eq!(persisted_exit_code(&summary), 5);
+        assert_eq!(persisted_exit_code(&summary), summary.exit_code as i64);
+    }
+
+    #[test]
+    fn confidence_band_is_persisted_when_no_policy_or_drift() {
+        for (confidence, expected) in [(95, ExitCode::Success), (50, ExitCode::Warning), (10, ExitCode::LowConfidence)] {
+            let summary = StackRunSummary::new(
+                "app".to_string(), 90, confidence, false, false, None, Vec::new(),
+            );
+
+            assert_eq!(summary.exit_code, expected);
+            assert_eq!(persisted_exit_code(&summary), expected as i64);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::Serialize;
+
+use crate::output::{render, OutputFormat};
+
+// ======================================================
+// EXIT CODES
+// ======================================================
+//
+// The `stack test` exit-code contract. Both the process exit and the
+// exit_code recorded in history come from here, and `rehearsa
+// exit-codes` prints the same table for CI authors.
+
+/// Confidence (or readiness, for preflight) at or above this passes.
+pub const PASS_SCORE: u32 = 70;
+/// Below PASS_SCORE but at or above this is a warning; lower is exit 3.
+pub const WARN_SCORE: u32 = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    Success         = 0,
+    Fatal           = 1,
+    Warning         = 2,
+    LowConfidence   = 3,
+    PolicyViolation = 4,
+    BaselineDrift   = 5,
+}
+
+impl ExitCode {
+    pub const ALL: [ExitCode; 6] = [
+        ExitCode::Success,
+        ExitCode::Fatal,
+        ExitCode::Warning,
+        ExitCode::LowConfidence,
+        ExitCode::PolicyViolation,
+        ExitCode::BaselineDrift,
+    ];
+
+    /// Band for a confidence or readiness score.
+    pub fn from_score(score: u32) -> Self {
+        if score >= PASS_SCORE {
+            ExitCode::Success
+        } else if score >= WARN_SCORE {
+            ExitCode::Warning
+        } else {
+            ExitCode::LowConfidence
+        }
+    }
+
+    /// Exit code of a completed rehearsal: policy first, then drift,
+    /// then the confidence band.
+    pub fn for_run(policy_violated: bool, baseline_drift: bool, confidence: u32) -> Self {
+        if policy_violated {
+            ExitCode::PolicyViolation
+        } else if baseline_drift {
+            ExitCode::BaselineDrift
+        } else {
+            ExitCode::from_score(confidence)
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ExitCode::Success         => "success",
+            ExitCode::Fatal           => "fatal",
+            ExitCode::Warning         => "warning",
+            ExitCode::LowConfidence   => "low_confidence",
+            ExitCode::PolicyViolation => "policy_violation",
+            ExitCode::BaselineDrift   => "baseline_drift",
+        }
+    }
+
+    pub fn meaning(self) -> String {
+        match self {
+            ExitCode::Success => format!(
+                "Confidence {}% or higher, no policy violation and no baseline drift",
+                PASS_SCORE
+            ),
+            ExitCode::Fatal =>
+                "The rehearsal could not run (invalid Compose file, engine unreachable, lock held, ...)"
+                    .to_string(),
+            ExitCode::Warning => format!(
+                "Confidence {}-{}% (readiness, with --preflight-only)",
+                WARN_SCORE, PASS_SCORE - 1
+            ),
+            ExitCode::LowConfidence => format!(
+                "Confidence below {}% (readiness, with --preflight-only)",
+                WARN_SCORE
+            ),
+            ExitCode::PolicyViolation =>
+                "A stack policy rule failed".to_string(),
+            ExitCode::BaselineDrift =>
+                "The run drifted from the pinned baseline".to_string(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ExitCodeRow {
+    code:    i32,
+    name:    &'static str,
+    meaning: String,
+}
+
+/// `rehearsa exit-codes`: the full table, as text or structured output.
+pub fn print_exit_codes(format: OutputFormat) -> Result<(), String> {
+    let rows: Vec<ExitCodeRow> = ExitCode::ALL.iter().map(|c| ExitCodeRow {
+        code:    *c as i32,
+        name:    c.name(),
+        meaning: c.meaning(),
+    }).collect();
+
+    if format.is_structured() {
+        println!("{}", render(&rows, format)?);
+        return Ok(());
+    }
+
+    println!("{:<6} {:<18} Meaning", "Code", "Name");
+    println!("{}", "─".repeat(100));
+    for row in &rows {
+        println!("{:<6} {:<18} {}", row.code, row.name, row.meaning);
+    }
+    Ok(())
+}
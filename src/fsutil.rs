@@ -0,0 +1,48 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+// ======================================================
+// ATOMIC WRITE
+// ======================================================
+//
+// Writes go to a sibling temp file which is synced and then renamed
+// over the target. rename(2) within one directory is atomic, so a
+// reader sees either the old file or the new one — never a truncated
+// or half-written file, even if the process dies mid-write.
+
+/// Atomically replace `path` with `contents`. `mode` sets unix
+/// permissions on the new file before it becomes visible.
+pub fn write_atomic(path: &Path, contents: &[u8], mode: Option<u32>) -> io::Result<()> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+
+    let tmp = path.with_file_name(format!(
+        ".{}.tmp.{}",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+
+        #[cfg(unix)]
+        if let Some(m) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&tmp, fs::Permissions::from_mode(m))?;
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+
+        fs::rename(&tmp, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+
+    result
+}
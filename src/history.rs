@@ -2,11 +2,14 @@ use serde::{Serialize, Deserialize};
 use std::fs;
 use std::path::PathBuf;
 use chrono::Utc;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use sha2::{Sha256, Digest};
 use colored::*;
 use colored::control;
 
+use crate::baseline::DriftSeverity;
+use crate::output::{render, OutputFormat};
+
 // ======================================================
 // SCHEMA VERSION
 // ======================================================
@@ -19,7 +22,7 @@ pub const CURRENT_SCHEMA_VERSION: u32 = 1;
 // DATA STRUCTURE
 // ======================================================
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct RunRecord {
     /// Schema version. 0 = pre-1.0 record (no version field on disk).
     #[serde(default)]
@@ -32,9 +35,49 @@ pub struct RunRecord {
     pub risk: String,
     pub exit_code: i32,
     pub services: HashMap<String, u32>,
+    /// Images loaded from an `--image-archive` bundle rather than pulled.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images_from_archive: Vec<String>,
+    /// Host that ran the rehearsal. Absent on records written before hosts were recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Docker engine version the rehearsal ran against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker_version: Option<String>,
+    /// Per-service oneshot / long-running classification and the reason for it.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub service_classes: HashMap<String, String>,
+    /// Preflight findings from this run, including acknowledged ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub preflight_findings: Vec<FindingRecord>,
+    /// Drift from the pinned baseline. Absent when no baseline was pinned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift_severity: Option<DriftSeverity>,
+    /// Tail of the container logs of each service that failed, capped in size.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub service_logs: HashMap<String, String>,
+    /// SHA-256 of each service's Compose definition, for `--changed-only`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub service_hashes: BTreeMap<String, String>,
+    /// Services not rehearsed by a `--changed-only` run; their scores
+    /// were carried over from the baseline.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reused_services: Vec<String>,
     pub hash: Option<String>,
 }
 
+/// A preflight finding as persisted in run history.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FindingRecord {
+    pub id: String,
+    pub rule: String,
+    pub severity: String,
+    pub message: String,
+    pub penalty: u32,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub acknowledged: bool,
+}
+
 // ======================================================
 // HASH
 // ======================================================
@@ -159,10 +202,111 @@ pub fn load_latest(stack: &str) -> Option<RunRecord> {
     serde_json::from_str(&content).ok()
 }
 
+// ======================================================
+// LOAD RECENT
+// ======================================================
+
+/// Load up to `window` most recent runs for a stack, oldest first.
+pub fn load_recent(stack: &str, window: usize) -> Vec<RunRecord> {
+
+    let home = match dirs::home_dir() {
+        Some(h) => h,
+        None => return vec![],
+    };
+
+    let stack_dir = home.join(".rehearsa").join("history").join(stack);
+
+    let mut entries: Vec<PathBuf> = match fs::read_dir(&stack_dir) {
+        Ok(e) => e.filter_map(|e| e.ok().map(|e| e.path())).collect(),
+        Err(_) => return vec![],
+    };
+
+    entries.sort();
+
+    entries
+        .into_iter()
+        .rev()
+        .take(window)
+        .rev()
+        .filter_map(|p| {
+            let content = fs::read_to_string(p).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect()
+}
+
 // ======================================================
 // STABILITY
 // ======================================================
 
+/// How runs inside the stability window are weighted.
+///
+/// - `Flat`: plain average of the last N confidences (default).
+/// - `Linear`: the newest run weighs N, the next N-1, ... the oldest 1.
+/// - `Exponential`: each older run weighs `EXPONENTIAL_DECAY` times the
+///   one after it, so the latest few runs dominate.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StabilityWeighting {
+    #[default]
+    Flat,
+    Linear,
+    Exponential,
+}
+
+/// Per-run decay factor for `StabilityWeighting::Exponential`.
+pub const EXPONENTIAL_DECAY: f64 = 0.7;
+
+impl std::fmt::Display for StabilityWeighting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StabilityWeighting::Flat        => write!(f, "flat"),
+            StabilityWeighting::Linear      => write!(f, "linear"),
+            StabilityWeighting::Exponential => write!(f, "exponential"),
+        }
+    }
+}
+
+impl std::str::FromStr for StabilityWeighting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "flat"        => Ok(StabilityWeighting::Flat),
+            "linear"      => Ok(StabilityWeighting::Linear),
+            "exponential" => Ok(StabilityWeighting::Exponential),
+            other => Err(format!(
+                "Unknown stability weighting '{}'. Use flat, linear or exponential.",
+                other
+            )),
+        }
+    }
+}
+
+/// Weighted average of confidences ordered newest first.
+fn weighted_confidence(newest_first: &[u32], weighting: StabilityWeighting) -> Option<u32> {
+    if newest_first.is_empty() {
+        return None;
+    }
+
+    let n = newest_first.len();
+    let (mut total, mut weights) = (0.0_f64, 0.0_f64);
+
+    for (i, &confidence) in newest_first.iter().enumerate() {
+        let w = match weighting {
+            StabilityWeighting::Flat        => 1.0,
+            StabilityWeighting::Linear      => (n - i) as f64,
+            StabilityWeighting::Exponential => EXPONENTIAL_DECAY.powi(i as i32),
+        };
+        total   += confidence as f64 * w;
+        weights += w;
+    }
+
+    Some((total / weights).round() as u32)
+}
+
+/// Stability over the last `window` runs, weighted per the configured
+/// `stability_weighting` (see `daemon::resolve_config`).
 pub fn calculate_stability(stack: &str, window: usize) -> u32 {
 
     let home = match dirs::home_dir() {
@@ -183,27 +327,46 @@ pub fn calculate_stability(stack: &str, window: usize) -> u32 {
 
     entries.sort();
 
-    let recent = entries.into_iter().rev().take(window);
-
-    let mut total = 0;
-    let mut count = 0;
+    let recent: Vec<u32> = entries
+        .into_iter()
+        .rev()
+        .take(window)
+        .filter_map(|path| {
+            let content = fs::read_to_string(&path).ok()?;
+            serde_json::from_str::<RunRecord>(&content).ok()
+        })
+        .map(|record| record.confidence)
+        .collect();
 
-    for path in recent {
-        if let Ok(content) = fs::read_to_string(&path) {
-            if let Ok(record) = serde_json::from_str::<RunRecord>(&content) {
-                total += record.confidence;
-                count += 1;
-            }
-        }
-    }
+    let weighting = crate::daemon::resolve_config().stability_weighting.value;
 
-    if count == 0 { 100 } else { total / count }
+    weighted_confidence(&recent, weighting).unwrap_or(100)
 }
 
 // ======================================================
 // LIST STACKS
 // ======================================================
 
+/// Names of every stack with recorded history, sorted.
+pub fn stack_names() -> Vec<String> {
+    let history_dir = match dirs::home_dir() {
+        Some(h) => h.join(".rehearsa").join("history"),
+        None    => return vec![],
+    };
+
+    let mut names: Vec<String> = match fs::read_dir(&history_dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .collect(),
+        Err(_) => return vec![],
+    };
+
+    names.sort();
+    names
+}
+
 pub fn list_stacks() -> Result<(), String> {
 
     let home = dirs::home_dir()
@@ -264,13 +427,15 @@ pub fn show_stack(stack: &str) -> Result<(), String> {
             .map_err(|e| format!("Failed to parse history file: {}", e))?;
 
         println!(
-            "{} | Readiness: {}% | Confidence: {}% | Risk: {} | Duration: {}s | Exit: {}",
+            "{} | Readiness: {}% | Confidence: {}% | Risk: {} | Duration: {}s | Exit: {} | Host: {} | Docker: {}",
             record.timestamp,
             record.readiness.unwrap_or(0),
             record.confidence,
             record.risk,
             record.duration_seconds,
-            record.exit_code
+            record.exit_code,
+            record.host.as_deref().unwrap_or("-"),
+            record.docker_version.as_deref().unwrap_or("-")
         );
     }
 
@@ -278,34 +443,541 @@ pub fn show_stack(stack: &str) -> Result<(), String> {
 }
 
 // ======================================================
-// STATUS
+// PER-SERVICE TRENDS
 // ======================================================
+//
+// The stack-level confidence can hide one service slowly degrading.
+// Each service gets its own score series across the window; runs where
+// the service was absent are gaps, not zeros.
+
+/// A slope below this (points per run) flags a service as trending down.
+pub const TREND_DOWN_SLOPE: f64 = -1.0;
+
+#[derive(Serialize, Debug)]
+pub struct ServiceTrend {
+    pub service: String,
+    /// One entry per run in the window, oldest first. None = absent.
+    pub scores:  Vec<Option<u32>>,
+    /// Least-squares slope in score points per run. None with fewer
+    /// than two data points.
+    pub slope:   Option<f64>,
+}
 
-pub fn status_all() -> Result<(), String> {
+impl ServiceTrend {
+    pub fn trending_down(&self) -> bool {
+        self.slope.is_some_and(|s| s < TREND_DOWN_SLOPE)
+    }
+}
 
-    control::set_override(true);
+/// Per-service score series over the last `window` runs, sorted by service.
+pub fn service_trends(stack: &str, window: usize) -> Vec<ServiceTrend> {
+    let runs = load_recent(stack, window);
+
+    let mut names: Vec<&String> = runs.iter().flat_map(|r| r.services.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let scores: Vec<Option<u32>> = runs.iter().map(|r| r.services.get(name).copied()).collect();
+            ServiceTrend { service: name.clone(), slope: slope(&scores), scores }
+        })
+        .collect()
+}
+
+fn slope(scores: &[Option<u32>]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = scores
+        .iter()
+        .enumerate()
+        .filter_map(|(i, s)| s.map(|v| (i as f64, v as f64)))
+        .collect();
+
+    if points.len() < 2 {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let num: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let den: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    Some(num / den)
+}
+
+/// One block character per run, scaled 0–100; gaps print as `·`.
+pub(crate) fn sparkline(scores: &[Option<u32>]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    scores
+        .iter()
+        .map(|s| match s {
+            Some(v) => BLOCKS[(*v.min(&100) as usize * (BLOCKS.len() - 1)) / 100],
+            None    => '·',
+        })
+        .collect()
+}
+
+pub fn show_trends(stack: &str, window: usize) -> Result<(), String> {
+    let trends = service_trends(stack, window);
+
+    if trends.is_empty() {
+        println!("No history for stack '{}'", stack);
+        return Ok(());
+    }
+
+    let runs = trends[0].scores.len();
+    println!("Stack: {} — last {} run(s), oldest first\n", stack, runs);
+
+    for trend in &trends {
+        let latest = trend.scores.iter().rev().flatten().next()
+            .map_or("-".to_string(), |v| v.to_string());
+        let slope = trend.slope
+            .map_or("-".to_string(), |s| format!("{:+.1}/run", s));
+
+        let line = format!(
+            "  {:<24} {}  latest {:>3}  slope {}",
+            trend.service, sparkline(&trend.scores), latest, slope
+        );
+
+        if trend.trending_down() {
+            println!("{}  {}", line.red(), "TRENDING DOWN".red().bold());
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    Ok(())
+}
+
+// ======================================================
+// DIFF TWO RUNS
+// ======================================================
+//
+// Same shape as baseline::compare_to_baseline, but between two
+// recorded runs — for root-causing a drop between nightly runs.
+
+/// A service present in both runs.
+#[derive(Serialize, Debug)]
+pub struct ServiceScoreDelta {
+    pub from:  u32,
+    pub to:    u32,
+    pub delta: i32,
+}
+
+/// A recorded value that differs between the two runs.
+#[derive(Serialize, Debug)]
+pub struct ValueChange {
+    pub from: Option<String>,
+    pub to:   Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RunDiff {
+    pub stack: String,
+    pub from:  String,
+    pub to:    String,
+    pub service_deltas:   std::collections::BTreeMap<String, ServiceScoreDelta>,
+    pub added_services:   Vec<String>,
+    pub removed_services: Vec<String>,
+    pub confidence_delta: i32,
+    pub readiness_delta:  Option<i32>,
+    pub duration_delta_seconds: i64,
+    pub duration_delta_percent: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<ValueChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub docker_version: Option<ValueChange>,
+}
+
+/// Resolve a full or partial timestamp to exactly one history file.
+fn find_run(entries: &[PathBuf], stack: &str, ts: &str) -> Result<PathBuf, String> {
+    let needle = ts.replace(':', "-");
+
+    let matches: Vec<&PathBuf> = entries
+        .iter()
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.contains(&needle))
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [one] => Ok((*one).clone()),
+        [] => Err(format!(
+            "No history entry found matching timestamp '{}' for stack '{}'.\n\
+             Run `rehearsa history show {}` to see available timestamps.",
+            ts, stack, stack
+        )),
+        many => {
+            let candidates: Vec<String> = many
+                .iter()
+                .filter_map(|p| p.file_stem().map(|s| format!("  {}", s.to_string_lossy())))
+                .collect();
+            Err(format!(
+                "Timestamp '{}' matches {} runs of '{}':\n{}",
+                ts, many.len(), stack, candidates.join("\n")
+            ))
+        }
+    }
+}
+
+fn change(from: &Option<String>, to: &Option<String>) -> Option<ValueChange> {
+    (from != to).then(|| ValueChange { from: from.clone(), to: to.clone() })
+}
+
+pub fn diff_runs(stack: &str, ts_a: &str, ts_b: &str) -> Result<RunDiff, String> {
+
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+
+    let stack_dir = home.join(".rehearsa").join("history").join(stack);
+
+    if !stack_dir.exists() {
+        return Err(format!("No history for stack '{}'", stack));
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(&stack_dir)
+        .map_err(|e| format!("Failed to read stack dir: {}", e))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .collect();
+
+    entries.sort();
+
+    let load = |ts: &str| -> Result<RunRecord, String> {
+        let path = find_run(&entries, stack, ts)?;
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+    };
+
+    let a = load(ts_a)?;
+    let b = load(ts_b)?;
+
+    let mut added_services: Vec<String> = b.services.keys()
+        .filter(|s| !a.services.contains_key(*s))
+        .cloned()
+        .collect();
+    added_services.sort();
+
+    let mut removed_services: Vec<String> = a.services.keys()
+        .filter(|s| !b.services.contains_key(*s))
+        .cloned()
+        .collect();
+    removed_services.sort();
+
+    let service_deltas = a.services.iter()
+        .filter_map(|(name, &from)| {
+            let to = *b.services.get(name)?;
+            Some((name.clone(), ServiceScoreDelta { from, to, delta: to as i32 - from as i32 }))
+        })
+        .collect();
+
+    let readiness_delta = match (a.readiness, b.readiness) {
+        (Some(from), Some(to)) => Some(to as i32 - from as i32),
+        _ => None,
+    };
+
+    let duration_delta_seconds = b.duration_seconds as i64 - a.duration_seconds as i64;
+    let duration_delta_percent = (a.duration_seconds > 0)
+        .then(|| ((duration_delta_seconds * 100) / a.duration_seconds as i64) as i32);
+
+    Ok(RunDiff {
+        stack: stack.to_string(),
+        service_deltas,
+        added_services,
+        removed_services,
+        confidence_delta: b.confidence as i32 - a.confidence as i32,
+        readiness_delta,
+        duration_delta_seconds,
+        duration_delta_percent,
+        host: change(&a.host, &b.host),
+        docker_version: change(&a.docker_version, &b.docker_version),
+        from: a.timestamp,
+        to:   b.timestamp,
+    })
+}
+
+fn signed(delta: i32) -> String {
+    if delta > 0 { format!("+{}", delta) } else { delta.to_string() }
+}
+
+pub fn print_run_diff(diff: &RunDiff) {
+    println!("Stack: {}", diff.stack);
+    println!("  {} → {}\n", diff.from, diff.to);
+
+    println!("Confidence: {}%", signed(diff.confidence_delta));
+    match diff.readiness_delta {
+        Some(d) => println!("Readiness:  {}%", signed(d)),
+        None    => println!("Readiness:  -"),
+    }
+    match diff.duration_delta_percent {
+        Some(p) => println!("Duration:   {}s ({}%)", signed(diff.duration_delta_seconds as i32), signed(p)),
+        None    => println!("Duration:   {}s", signed(diff.duration_delta_seconds as i32)),
+    }
+
+    for (label, value) in [("Host", &diff.host), ("Docker", &diff.docker_version)] {
+        if let Some(c) = value {
+            println!(
+                "{}: {} → {}",
+                label,
+                c.from.as_deref().unwrap_or("-"),
+                c.to.as_deref().unwrap_or("-")
+            );
+        }
+    }
+
+    println!("\nServices:");
+    for (name, d) in &diff.service_deltas {
+        let line = format!("  {:<24} {:>3} → {:>3}  ({})", name, d.from, d.to, signed(d.delta));
+        match d.delta {
+            0          => println!("{}", line),
+            x if x < 0 => println!("{}", line.red()),
+            _          => println!("{}", line.green()),
+        }
+    }
+    for name in &diff.added_services {
+        println!("  {}", format!("+ {} (added)", name).green());
+    }
+    for name in &diff.removed_services {
+        println!("  {}", format!("- {} (removed)", name).red());
+    }
+}
+
+// ======================================================
+// EXPORT
+// ======================================================
+//
+// One row per run, for spreadsheets: fixed columns, then one
+// `service:<name>` column per service seen in any exported run. A
+// service absent from a run is an empty cell (null in JSON), never 0.
+// Fleet-wide exports add a leading `stack` column.
+
+const EXPORT_COLUMNS: [&str; 6] =
+    ["timestamp", "confidence", "readiness", "duration_seconds", "risk", "exit_code"];
+
+/// Write history for one stack (or every stack) as `csv` or `json` to
+/// `output`, or stdout when None. Returns the number of runs exported.
+pub fn export_history(
+    stack: Option<&str>,
+    format: &str,
+    output: Option<&std::path::Path>,
+) -> Result<usize, String> {
+
+    let stacks = match stack {
+        Some(s) => vec![s.to_string()],
+        None    => stack_names(),
+    };
+
+    let runs: Vec<RunRecord> = stacks
+        .iter()
+        .flat_map(|s| load_recent(s, usize::MAX))
+        .collect();
+
+    if runs.is_empty() {
+        return Err(match stack {
+            Some(s) => format!("No history for stack '{}'", s),
+            None    => "No history found".to_string(),
+        });
+    }
+
+    let mut services: Vec<&String> = runs.iter().flat_map(|r| r.services.keys()).collect();
+    services.sort();
+    services.dedup();
+
+    let fleet = stack.is_none();
+
+    let mut header: Vec<String> = Vec::new();
+    if fleet {
+        header.push("stack".to_string());
+    }
+    header.extend(EXPORT_COLUMNS.iter().map(|c| c.to_string()));
+    header.extend(services.iter().map(|s| format!("service:{}", s)));
+
+    let rows: Vec<Vec<Option<String>>> = runs
+        .iter()
+        .map(|r| {
+            let mut cells = Vec::new();
+            if fleet {
+                cells.push(Some(r.stack.clone()));
+            }
+            cells.push(Some(r.timestamp.clone()));
+            cells.push(Some(r.confidence.to_string()));
+            cells.push(r.readiness.map(|v| v.to_string()));
+            cells.push(Some(r.duration_seconds.to_string()));
+            cells.push(Some(r.risk.clone()));
+            cells.push(Some(r.exit_code.to_string()));
+            cells.extend(services.iter().map(|s| r.services.get(*s).map(|v| v.to_string())));
+            cells
+        })
+        .collect();
+
+    let content = match format {
+        "json" => {
+            let objects: Vec<serde_json::Map<String, serde_json::Value>> = rows
+                .iter()
+                .map(|cells| {
+                    header.iter().zip(cells).map(|(key, cell)| {
+                        let value = match cell {
+                            // Numeric columns stay numbers in JSON
+                            Some(v) => v.parse::<i64>()
+                                .map(serde_json::Value::from)
+                                .unwrap_or_else(|_| serde_json::Value::from(v.as_str())),
+                            None => serde_json::Value::Null,
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect()
+                })
+                .collect();
+
+            serde_json::to_string_pretty(&objects)
+                .map_err(|e| format!("JSON error: {}", e))? + "\n"
+        }
+        _ => {
+            let mut lines = vec![crate::csv::row(&header)];
+            lines.extend(rows.iter().map(|cells| {
+                let fields: Vec<&str> = cells.iter().map(|c| c.as_deref().unwrap_or("")).collect();
+                crate::csv::row(&fields)
+            }));
+            lines.join("\n") + "\n"
+        }
+    };
+
+    match output {
+        Some(path) => fs::write(path, content)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?,
+        None => print!("{}", content),
+    }
+
+    Ok(runs.len())
+}
+
+// ======================================================
+// PRUNE
+// ======================================================
+//
+// Retention for history files. A run is pruned only when it falls
+// outside every limit given: beyond the newest `keep_last` runs and
+// older than `older_than_days`. The newest run of a stack is always
+// kept — load_latest and baseline drift depend on it.
+
+/// When a run happened: its recorded timestamp, else the file's mtime.
+fn run_time(path: &std::path::Path) -> Option<chrono::DateTime<Utc>> {
+    let recorded = fs::read_to_string(path)
+        .ok()
+        .and_then(|c| serde_json::from_str::<RunRecord>(&c).ok())
+        .and_then(|r| chrono::DateTime::parse_from_rfc3339(&r.timestamp).ok())
+        .map(|t| t.with_timezone(&Utc));
+
+    recorded.or_else(|| {
+        fs::metadata(path).and_then(|m| m.modified()).ok().map(chrono::DateTime::<Utc>::from)
+    })
+}
+
+/// Delete (or with `dry_run`, list) history files outside the retention
+/// limits. Returns the number of files pruned.
+pub fn prune(
+    stack: Option<&str>,
+    keep_last: Option<usize>,
+    older_than_days: Option<u32>,
+    dry_run: bool,
+) -> Result<usize, String> {
+
+    if keep_last.is_none() && older_than_days.is_none() {
+        return Err("Specify --keep-last and/or --older-than".to_string());
+    }
 
     let home = dirs::home_dir()
         .ok_or("Could not determine home directory")?;
 
     let history_dir = home.join(".rehearsa").join("history");
 
-    if !history_dir.exists() {
-        println!("No history found.");
-        return Ok(());
+    let stacks = match stack {
+        Some(s) => vec![s.to_string()],
+        None    => stack_names(),
+    };
+
+    let cutoff = older_than_days.map(|d| Utc::now() - chrono::Duration::days(d as i64));
+    let mut pruned = 0;
+
+    for name in stacks {
+        let stack_dir = history_dir.join(&name);
+
+        if !stack_dir.exists() {
+            if stack.is_some() {
+                return Err(format!("No history for stack '{}'", name));
+            }
+            continue;
+        }
+
+        let mut entries: Vec<PathBuf> = fs::read_dir(&stack_dir)
+            .map_err(|e| format!("Failed to read stack dir: {}", e))?
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+            .collect();
+
+        entries.sort();
+
+        // Newest first; the first entry is never a candidate.
+        for (age_rank, path) in entries.iter().rev().enumerate().skip(1) {
+            let beyond_keep = keep_last.is_none_or(|n| age_rank >= n);
+            let too_old = match cutoff {
+                Some(c) => run_time(path).is_some_and(|t| t < c),
+                None    => true,
+            };
+
+            if !(beyond_keep && too_old) {
+                continue;
+            }
+
+            if dry_run {
+                println!("Would delete {}", path.display());
+            } else {
+                fs::remove_file(path)
+                    .map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+                println!("Deleted {}", path.display());
+            }
+
+            pruned += 1;
+        }
     }
 
-    println!();
-    println!("{}", "Rehearsa Status".bold());
-    println!("{}", "────────────────────────────────────────────────────────────────────".dimmed());
-    println!();
+    Ok(pruned)
+}
 
-    println!(
-        "{:<20} {:<12} {:<12} {:<12} {:<12} {:<6}",
-        "Stack", "Readiness", "Confidence", "Risk", "Stability", "Trend"
-    );
+// ======================================================
+// STATUS
+// ======================================================
 
-    println!("{}", "────────────────────────────────────────────────────────────────────".dimmed());
+/// One row of `rehearsa status`: a stack's latest run and its trend.
+#[derive(Debug, Serialize)]
+pub struct StatusEntry {
+    pub stack:            String,
+    pub last_run:         String,
+    pub readiness:        Option<u32>,
+    pub confidence:       u32,
+    pub risk:             String,
+    pub stability:        u32,
+    pub confidence_trend: Option<String>,
+    pub readiness_trend:  Option<String>,
+    pub drift_severity:   Option<DriftSeverity>,
+}
+
+/// Latest-run status for every stack with history, sorted by name.
+pub fn status_entries() -> Result<Vec<StatusEntry>, String> {
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+
+    let history_dir = home.join(".rehearsa").join("history");
+
+    if !history_dir.exists() {
+        return Ok(vec![]);
+    }
 
     let mut stacks: Vec<PathBuf> = fs::read_dir(&history_dir)
         .map_err(|e| format!("Failed to read history dir: {}", e))?
@@ -315,6 +987,8 @@ pub fn status_all() -> Result<(), String> {
 
     stacks.sort();
 
+    let mut entries = Vec::new();
+
     for stack_path in stacks {
 
         let stack_name = stack_path
@@ -325,8 +999,6 @@ pub fn status_all() -> Result<(), String> {
 
         if let Some(latest) = load_latest(&stack_name) {
 
-            let stability = calculate_stability(&stack_name, 5);
-
             let analysis = analyze_regression(
                 &stack_name,
                 latest.confidence,
@@ -334,22 +1006,70 @@ pub fn status_all() -> Result<(), String> {
                 latest.duration_seconds,
             );
 
-            let readiness_value = latest.readiness.unwrap_or(0);
+            entries.push(StatusEntry {
+                stability:        calculate_stability(&stack_name, 5),
+                stack:            stack_name,
+                last_run:         latest.timestamp,
+                readiness:        latest.readiness,
+                confidence:       latest.confidence,
+                risk:             latest.risk,
+                confidence_trend: analysis.confidence_trend,
+                readiness_trend:  analysis.readiness_trend,
+                drift_severity:   latest.drift_severity,
+            });
+        }
+    }
+
+    Ok(entries)
+}
+
+pub fn status_all(format: OutputFormat) -> Result<(), String> {
+
+    if format.is_structured() {
+        println!("{}", render(&status_entries()?, format)?);
+        return Ok(());
+    }
+
+    control::set_override(true);
+
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+
+    if !home.join(".rehearsa").join("history").exists() {
+        println!("No history found.");
+        return Ok(());
+    }
+
+    println!();
+    println!("{}", "Rehearsa Status".bold());
+    println!("{}", "────────────────────────────────────────────────────────────────────".dimmed());
+    println!();
+
+    println!(
+        "{:<20} {:<12} {:<12} {:<12} {:<12} {:<8} {:<6}",
+        "Stack", "Readiness", "Confidence", "Risk", "Stability", "Trend", "Drift"
+    );
+
+    println!("{}", "────────────────────────────────────────────────────────────────────".dimmed());
 
-            let stack_col = format!("{:<20}", stack_name);
-            let readiness_raw = format!("{:<12}", format!("{}%", readiness_value));
-            let confidence_raw = format!("{:<12}", format!("{}%", latest.confidence));
-            let risk_raw = format!("{:<12}", latest.risk);
-            let stability_col = format!("{:<12}", format!("{}%", stability));
+    for entry in status_entries()? {
 
-            let confidence_arrow = match analysis.confidence_trend.as_deref() {
+        let readiness_value = entry.readiness.unwrap_or(0);
+
+        let stack_col = format!("{:<20}", entry.stack);
+        let readiness_raw = format!("{:<12}", format!("{}%", readiness_value));
+        let confidence_raw = format!("{:<12}", format!("{}%", entry.confidence));
+        let risk_raw = format!("{:<12}", entry.risk);
+        let stability_col = format!("{:<12}", format!("{}%", entry.stability));
+
+        let confidence_arrow = match entry.confidence_trend.as_deref() {
     Some("UP") => "↑",
     Some("DOWN") => "↓",
     Some("SAME") => "→",
     _ => "-",
 };
 
-let readiness_arrow = match analysis.readiness_trend.as_deref() {
+let readiness_arrow = match entry.readiness_trend.as_deref() {
     Some("UP") => "↑",
     Some("DOWN") => "↓",
     Some("SAME") => "→",
@@ -359,45 +1079,56 @@ let readiness_arrow = match analysis.readiness_trend.as_deref() {
 let trend_combined = format!("C:{} R:{}", confidence_arrow, readiness_arrow);
 let trend_raw = format!("{:<6}", trend_combined);
 
-            let readiness_col = match readiness_value {
-                90..=100 => readiness_raw.green(),
-                70..=89 => readiness_raw.yellow(),
-                40..=69 => readiness_raw.bright_red(),
-                _ => readiness_raw.red(),
-            };
+        let readiness_col = match readiness_value {
+            90..=100 => readiness_raw.green(),
+            70..=89 => readiness_raw.yellow(),
+            40..=69 => readiness_raw.bright_red(),
+            _ => readiness_raw.red(),
+        };
 
-            let confidence_col = match latest.confidence {
-                90..=100 => confidence_raw.green(),
-                70..=89 => confidence_raw.yellow(),
-                40..=69 => confidence_raw.bright_red(),
-                _ => confidence_raw.red(),
-            };
+        let confidence_col = match entry.confidence {
+            90..=100 => confidence_raw.green(),
+            70..=89 => confidence_raw.yellow(),
+            40..=69 => confidence_raw.bright_red(),
+            _ => confidence_raw.red(),
+        };
 
-            let risk_col = match latest.risk.as_str() {
-                "LOW" => risk_raw.green(),
-                "MODERATE" => risk_raw.yellow(),
-                "HIGH" => risk_raw.bright_red(),
-                "CRITICAL" => risk_raw.red(),
-                _ => risk_raw.normal(),
-            };
+        let risk_col = match entry.risk.as_str() {
+            "LOW" => risk_raw.green(),
+            "MODERATE" => risk_raw.yellow(),
+            "HIGH" => risk_raw.bright_red(),
+            "CRITICAL" => risk_raw.red(),
+            _ => risk_raw.normal(),
+        };
 
-            let trend_col = match analysis.confidence_trend.as_deref() {
+        let trend_col = match entry.confidence_trend.as_deref() {
     Some("UP") => trend_raw.green(),
     Some("DOWN") => trend_raw.red(),
     Some("SAME") => trend_raw.yellow(),
     _ => trend_raw.normal(),
 };
 
-            println!(
-                "{}{}{}{}{}{}",
-                stack_col,
-                readiness_col,
-                confidence_col,
-                risk_col,
-                stability_col,
-                trend_col
-            );
-        }
+        let drift_raw = format!(
+            " {:<6}",
+            entry.drift_severity.map_or("-".to_string(), |s| s.to_string())
+        );
+        let drift_col = match entry.drift_severity {
+            Some(DriftSeverity::Major) => drift_raw.red(),
+            Some(DriftSeverity::Minor) => drift_raw.yellow(),
+            Some(DriftSeverity::None)  => drift_raw.green(),
+            None                       => drift_raw.normal(),
+        };
+
+        println!(
+            "{}{}{}{}{}{}{}",
+            stack_col,
+            readiness_col,
+            confidence_col,
+            risk_col,
+            stability_col,
+            trend_col,
+            drift_col
+        );
     }
 
     println!();
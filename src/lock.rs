@@ -1,73 +1,136 @@
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
-use chrono::Utc;
+use std::thread;
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
 
 pub struct StackLock {
     path: PathBuf,
 }
 
+/// First and maximum delay between attempts in `acquire_timeout`.
+const WAIT_INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const WAIT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Outcome of one non-blocking acquisition attempt.
+enum Attempt {
+    Acquired(StackLock),
+    /// Held by this live PID.
+    Held(u32),
+}
+
+/// Lock directory as resolved from env / config (see `daemon::resolve_config`).
+fn lock_dir() -> PathBuf {
+    PathBuf::from(crate::daemon::resolve_config().lock_dir.value)
+}
+
+/// Lock file for a stack on an engine: `<stack>.lock` locally,
+/// `<stack>@<host>.lock` for a remote engine, so controllers rehearsing
+/// the same stack name on different hosts don't contend.
+fn lock_file(stack: &str, engine: Option<&str>) -> PathBuf {
+    match engine {
+        Some(host) => lock_dir().join(format!("{}@{}.lock", stack, host)),
+        None       => lock_dir().join(format!("{}.lock", stack)),
+    }
+}
+
+/// Lock file for a stack on the engine this process talks to.
+fn current_lock_file(stack: &str) -> PathBuf {
+    lock_file(stack, crate::docker::connect::remote_host_id().as_deref())
+}
+
 impl StackLock {
+    /// Take the lock, failing immediately if a live process holds it.
     pub fn acquire(stack: &str) -> Result<Self, String> {
-        let lock_dir = PathBuf::from("/var/lock/rehearsa");
+        match Self::try_acquire(stack)? {
+            Attempt::Acquired(lock) => Ok(lock),
+            Attempt::Held(pid) => Err(format!(
+                "Stack '{}' is already being rehearsed (PID {}).",
+                stack, pid
+            )),
+        }
+    }
+
+    /// Take the lock, polling with backoff while a live process holds it,
+    /// for up to `timeout`. Stale locks are reclaimed exactly as in `acquire`.
+    pub fn acquire_timeout(stack: &str, timeout: Duration) -> Result<Self, String> {
+        let deadline = Instant::now() + timeout;
+        let mut backoff = WAIT_INITIAL_BACKOFF;
+        let mut announced = false;
+
+        loop {
+            let pid = match Self::try_acquire(stack)? {
+                Attempt::Acquired(lock) => return Ok(lock),
+                Attempt::Held(pid) => pid,
+            };
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(format!(
+                    "Stack '{}' is still being rehearsed (PID {}) after waiting {}s.",
+                    stack, pid, timeout.as_secs()
+                ));
+            }
+
+            if !announced {
+                eprintln!(
+                    "Stack '{}' is being rehearsed (PID {}) — waiting up to {}s for the lock...",
+                    stack, pid, timeout.as_secs()
+                );
+                announced = true;
+            }
+
+            thread::sleep(backoff.min(deadline - now));
+            backoff = (backoff * 2).min(WAIT_MAX_BACKOFF);
+        }
+    }
+
+    fn try_acquire(stack: &str) -> Result<Attempt, String> {
+        let lock_dir = lock_dir();
 
         fs::create_dir_all(&lock_dir)
             .map_err(|e| format!("Failed to create lock dir: {}", e))?;
 
-        let lock_path = lock_dir.join(format!("{}.lock", stack));
+        let lock_path = current_lock_file(stack);
 
         // Attempt atomic lock creation (O_CREAT | O_EXCL) — eliminates TOCTOU race.
-        match fs::OpenOptions::new()
-            .write(true)
-            .create_new(true)
-            .open(&lock_path)
-        {
-            Ok(mut file) => {
-                let pid = process::id();
-                let hostname = get_hostname();
-                let timestamp = Utc::now().to_rfc3339();
-                writeln!(file, "pid: {}", pid).ok();
-                writeln!(file, "hostname: {}", hostname).ok();
-                writeln!(file, "timestamp: {}", timestamp).ok();
-                Ok(StackLock { path: lock_path })
-            }
+        match open_new(&lock_path) {
+            Ok(file) => Ok(Attempt::Acquired(Self::claim(file, lock_path))),
             Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
                 let content = fs::read_to_string(&lock_path).unwrap_or_default();
-                let mut pid: Option<u32> = None;
-                for line in content.lines() {
-                    if let Some(rest) = line.strip_prefix("pid:") {
-                        pid = rest.trim().parse::<u32>().ok();
-                    }
-                }
-                match pid {
+                match parse_lock(stack, None, &content).pid {
                     Some(existing_pid) if process_alive(existing_pid) => {
-                        Err(format!(
-                            "Stack '{}' is already being rehearsed (PID {}).",
-                            stack, existing_pid
-                        ))
+                        Ok(Attempt::Held(existing_pid))
                     }
                     _ => {
                         // Stale or corrupt lock — remove and retry once
                         let _ = fs::remove_file(&lock_path);
-                        let mut file = fs::OpenOptions::new()
-                            .write(true)
-                            .create_new(true)
-                            .open(&lock_path)
+                        let file = open_new(&lock_path)
                             .map_err(|e| format!("Failed to acquire lock after stale removal: {}", e))?;
-                        let pid = process::id();
-                        let hostname = get_hostname();
-                        let timestamp = Utc::now().to_rfc3339();
-                        writeln!(file, "pid: {}", pid).ok();
-                        writeln!(file, "hostname: {}", hostname).ok();
-                        writeln!(file, "timestamp: {}", timestamp).ok();
-                        Ok(StackLock { path: lock_path })
+                        Ok(Attempt::Acquired(Self::claim(file, lock_path)))
                     }
                 }
             }
             Err(e) => Err(format!("Failed to create lock file: {}", e)),
         }
     }
+
+    /// Record this process as the holder of a freshly created lock file.
+    fn claim(mut file: fs::File, path: PathBuf) -> Self {
+        writeln!(file, "pid: {}", process::id()).ok();
+        writeln!(file, "hostname: {}", get_hostname()).ok();
+        writeln!(file, "timestamp: {}", Utc::now().to_rfc3339()).ok();
+        StackLock { path }
+    }
+}
+
+fn open_new(path: &Path) -> std::io::Result<fs::File> {
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
 }
 
 impl Drop for StackLock {
@@ -76,11 +139,197 @@ impl Drop for StackLock {
     }
 }
 
+// ======================================================
+// LOCK INSPECTION
+// ======================================================
+//
+// A lock held by a live PID is normally a rehearsal in progress. If it
+// has been held longer than the configured max age, the rehearsal is
+// most likely hung — acquire() would keep refusing forever, so the
+// daemon surfaces these as stuck instead of silently skipping.
+
+#[derive(Debug, Clone)]
+pub struct LockInfo {
+    pub stack:    String,
+    /// Remote engine the lock is scoped to; None for the local engine.
+    pub engine:   Option<String>,
+    pub pid:      Option<u32>,
+    pub hostname: String,
+    pub acquired: Option<DateTime<Utc>>,
+}
+
+impl LockInfo {
+    /// Stack name, suffixed with `@host` when scoped to a remote engine.
+    pub fn label(&self) -> String {
+        match &self.engine {
+            Some(host) => format!("{}@{}", self.stack, host),
+            None       => self.stack.clone(),
+        }
+    }
+
+    pub fn alive(&self) -> bool {
+        self.pid.map(process_alive).unwrap_or(false)
+    }
+
+    /// Minutes the lock has been held, if its timestamp is readable.
+    pub fn age_minutes(&self) -> Option<i64> {
+        self.acquired.map(|t| (Utc::now() - t).num_minutes())
+    }
+
+    /// Held by a live process for longer than `max_age_minutes` (0 disables).
+    pub fn is_stuck(&self, max_age_minutes: u64) -> bool {
+        max_age_minutes > 0
+            && self.alive()
+            && self.age_minutes().is_some_and(|m| m >= max_age_minutes as i64)
+    }
+}
+
+fn parse_lock(stack: &str, engine: Option<&str>, content: &str) -> LockInfo {
+    let mut info = LockInfo {
+        stack:    stack.to_string(),
+        engine:   engine.map(str::to_string),
+        pid:      None,
+        hostname: "unknown".to_string(),
+        acquired: None,
+    };
+
+    for line in content.lines() {
+        if let Some(rest) = line.strip_prefix("pid:") {
+            info.pid = rest.trim().parse::<u32>().ok();
+        } else if let Some(rest) = line.strip_prefix("hostname:") {
+            info.hostname = rest.trim().to_string();
+        } else if let Some(rest) = line.strip_prefix("timestamp:") {
+            info.acquired = DateTime::parse_from_rfc3339(rest.trim())
+                .ok()
+                .map(|t| t.with_timezone(&Utc));
+        }
+    }
+
+    info
+}
+
+/// Current lock for a stack, if one exists.
+pub fn read_lock(stack: &str) -> Option<LockInfo> {
+    let engine = crate::docker::connect::remote_host_id();
+    let content = fs::read_to_string(lock_file(stack, engine.as_deref())).ok()?;
+    Some(parse_lock(stack, engine.as_deref(), &content))
+}
+
+/// Every lock file in the lock directory, for every engine, sorted by stack name.
+pub fn list_locks() -> Vec<LockInfo> {
+    let entries = match fs::read_dir(lock_dir()) {
+        Ok(e) => e,
+        Err(_) => return vec![],
+    };
+
+    let mut locks: Vec<LockInfo> = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().map(|x| x == "lock").unwrap_or(false))
+        .filter_map(|p| {
+            let stem = p.file_stem()?.to_string_lossy().to_string();
+            let (stack, engine) = match stem.rsplit_once('@') {
+                Some((stack, host)) => (stack, Some(host)),
+                None                => (stem.as_str(), None),
+            };
+            let content = fs::read_to_string(&p).ok()?;
+            Some(parse_lock(stack, engine, &content))
+        })
+        .collect();
+
+    locks.sort_by_key(|l| l.label());
+    locks
+}
+
+/// Force-remove a stack's lock file. The holding process, if any, is
+/// left running — this only lets the next rehearsal start.
+pub fn break_lock(stack: &str) -> Result<(), String> {
+    fs::remove_file(current_lock_file(stack))
+        .map_err(|e| format!("Failed to remove lock for '{}': {}", stack, e))
+}
+
+// ======================================================
+// LOCK COMMANDS
+// ======================================================
+
+/// `rehearsa lock list`: every lock file with its holder and liveness.
+pub fn show_locks() {
+    let locks = list_locks();
+
+    if locks.is_empty() {
+        println!("No locks held in {}.", lock_dir().display());
+        return;
+    }
+
+    println!(
+        "{:<24} {:<8} {:<20} {:<20} {:>8}  Process",
+        "Stack", "PID", "Host", "Acquired", "Held"
+    );
+    println!("{}", "─".repeat(94));
+
+    for lock in &locks {
+        let pid = lock.pid.map(|p| p.to_string()).unwrap_or_else(|| "?".to_string());
+        let acquired = lock.acquired
+            .map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_else(|| "?".to_string());
+        let held = lock.age_minutes().map(|m| format!("{}m", m)).unwrap_or_else(|| "?".to_string());
+        let state = if lock.alive() { "alive" } else { "dead (stale)" };
+
+        println!(
+            "{:<24} {:<8} {:<20} {:<20} {:>8}  {}",
+            lock.label(), pid, lock.hostname, acquired, held, state
+        );
+    }
+}
+
+/// Remove one stack's lock. A lock whose process is still alive is only
+/// removed with `force`.
+pub fn clear_lock(stack: &str, force: bool) -> Result<(), String> {
+    let lock = read_lock(stack)
+        .ok_or_else(|| format!("No lock held for stack '{}'.", stack))?;
+
+    if lock.alive() && !force {
+        return Err(format!(
+            "Lock for '{}' is held by a running process (PID {}). Use --force to remove it anyway.",
+            stack,
+            lock.pid.map(|p| p.to_string()).unwrap_or_default()
+        ));
+    }
+
+    break_lock(stack)?;
+    println!("Lock cleared for '{}'.", stack);
+    Ok(())
+}
+
+/// Remove every stale lock (and, with `force`, live ones too).
+/// Returns the number removed.
+pub fn clear_all_locks(force: bool) -> Result<usize, String> {
+    let mut cleared = 0;
+    let mut skipped = 0;
+
+    for lock in list_locks() {
+        if lock.alive() && !force {
+            skipped += 1;
+            continue;
+        }
+        fs::remove_file(lock_file(&lock.stack, lock.engine.as_deref()))
+            .map_err(|e| format!("Failed to remove lock for '{}': {}", lock.label(), e))?;
+        println!("Lock cleared for '{}'.", lock.label());
+        cleared += 1;
+    }
+
+    if skipped > 0 {
+        println!("{} lock(s) held by running processes left in place (use --force to remove).", skipped);
+    }
+
+    Ok(cleared)
+}
+
 fn process_alive(pid: u32) -> bool {
     PathBuf::from(format!("/proc/{}", pid)).exists()
 }
 
-fn get_hostname() -> String {
+/// This host's name from /etc/hostname, or "unknown".
+pub fn get_hostname() -> String {
     if let Ok(contents) = fs::read_to_string("/etc/hostname") {
         contents.trim().to_string()
     } else {
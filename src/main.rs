@@ -8,20 +8,29 @@ mod daemon;
 mod provider;
 mod notify;
 mod report;
+mod scoring;
 mod coverage;
-
-use clap::{Parser, Subcommand};
-use chrono;
+mod csv;
+mod fsutil;
+mod config;
+mod output;
+mod exitcode;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use std::process::exit;
 
-use engine::stack::{test_stack, PullPolicy, cleanup_orphans};
-use policy::{StackPolicy, save_policy, show_policy, delete_policy};
+use engine::stack::{test_stack, PullPolicy, TestOptions, Verbosity, cleanup_orphans};
+use output::OutputFormat;
+use exitcode::ExitCode;
+use policy::{StackPolicy, save_policy, show_policy, delete_policy, policy_exists, apply_policy, glob_match};
 use baseline::{
     StackBaseline,
     save_baseline,
     load_baseline,
     delete_baseline,
     compare_to_baseline,
+    DriftSeverity,
 };
 
 // ======================================================
@@ -35,21 +44,57 @@ struct Cli {
     #[arg(long)]
     json: bool,
 
+    /// Output for status, coverage and stack test: text | json | yaml.
+    /// --json is shorthand for --format json.
+    #[arg(long, default_value = "text", conflicts_with = "json",
+          value_parser = ["text", "json", "yaml"])]
+    format: String,
+
     #[arg(long)]
     ci: bool,
 
-    #[arg(long, default_value_t = 30)]
+    #[arg(long, default_value_t = 30, env = "REHEARSA_TIMEOUT")]
     timeout: u64,
 
-    #[arg(long)]
-    inject_failure: Option<String>,
+    /// Force a service's score to 0. Repeat (or comma-separate) to fail several.
+    #[arg(long, value_delimiter = ',')]
+    inject_failure: Vec<String>,
+
+    /// Activate a compose profile. Repeatable; adds to COMPOSE_PROFILES.
+    #[arg(long = "profile")]
+    profiles: Vec<String>,
 
+    /// After `stack test`, print every policy rule with its threshold,
+    /// the observed value and PASS/FAIL.
     #[arg(long)]
+    explain_policy: bool,
+
+    #[arg(long, env = "REHEARSA_STRICT_INTEGRITY")]
     strict_integrity: bool,
 
-    #[arg(long, default_value = "if-missing")]
+    #[arg(long, default_value = "if-missing", env = "REHEARSA_PULL",
+          value_parser = ["always", "if-missing", "never"])]
     pull: String,
 
+    /// Directory of `docker save` *.tar archives to load before the
+    /// rehearsal (for air-gapped hosts; pair with --pull never).
+    #[arg(long, env = "REHEARSA_IMAGE_ARCHIVE")]
+    image_archive: Option<String>,
+
+    /// Container engine API to use instead of the local Docker socket,
+    /// e.g. unix:///run/podman/podman.sock or tcp://host:2376.
+    /// Overrides DOCKER_HOST; TLS follows DOCKER_TLS_VERIFY / DOCKER_CERT_PATH.
+    #[arg(long, env = "REHEARSA_CONTAINER_HOST")]
+    docker_host: Option<String>,
+
+    /// Print only the verdict line and errors (no effect with --json/--ci).
+    #[arg(long, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also print per-service wait timing and every start attempt.
+    #[arg(long)]
+    verbose: bool,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -68,6 +113,11 @@ enum Commands {
         #[command(subcommand)]
         command: PolicyCommands,
     },
+    /// Per-service weights and score bands used to compute confidence
+    Scoring {
+        #[command(subcommand)]
+        command: ScoringCommands,
+    },
     Baseline {
         #[command(subcommand)]
         command: BaselineCommands,
@@ -84,27 +134,78 @@ enum Commands {
         #[command(subcommand)]
         command: NotifyCommands,
     },
-    Status,
+    /// Per-stack preflight scoring settings
+    Preflight {
+        #[command(subcommand)]
+        command: PreflightCommands,
+    },
+    Status {
+        /// Print only the fleet rollup counts (watched, honouring, drifting, ...).
+        #[arg(long)]
+        summary: bool,
+    },
     /// Show fleet-wide restore contract coverage across all watched stacks.
+    #[command(args_conflicts_with_subcommands = true)]
     Coverage {
+        #[command(subcommand)]
+        command: Option<CoverageCommands>,
+
         /// Output raw JSON instead of the formatted table.
         #[arg(long)]
         json: bool,
+        /// Output CSV (one row per stack plus a TOTAL row).
+        #[arg(long, conflicts_with = "json")]
+        csv: bool,
+        /// Exit 1 only when coverage is below this percentage [default: daemon min_coverage_pct, 100]
+        #[arg(long, value_parser = clap::value_parser!(u32).range(0..=100))]
+        min_coverage: Option<u32>,
+        /// Also exit 1 when any stack has drifted from its baseline.
+        #[arg(long)]
+        fail_on_drift: bool,
     },
     Version,
+    /// Manage CLI defaults in ~/.rehearsa/config.toml
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Print a shell completion script, e.g. `rehearsa completions zsh > ~/.zfunc/_rehearsa`
+    #[command(hide = true)]
+    Completions {
+        shell: clap_complete::Shell,
+    },
+    /// List `stack test` exit codes with their meaning (text, --json or --format yaml).
+    ExitCodes,
     /// Remove orphaned containers and networks left by crashed rehearsals.
-    Cleanup,
+    Cleanup {
+        /// Only remove resources at least this many minutes old, so an
+        /// in-progress rehearsal is never reaped
+        #[arg(long, value_name = "MINUTES", default_value_t = 60)]
+        older_than: u64,
+        /// List what would be removed without removing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Inspect and clear per-stack rehearsal locks
+    Lock {
+        #[command(subcommand)]
+        command: LockCommands,
+    },
     /// Generate a compliance report for a stack or the full fleet.
+    #[command(args_conflicts_with_subcommands = true)]
     Report {
+        #[command(subcommand)]
+        command: Option<ReportCommands>,
+
         /// Stack name. Omit for a fleet-wide report covering all stacks.
         #[arg(long)]
         stack: Option<String>,
 
-        /// Output format: json | pdf | both  [default: both]
+        /// Output format: json | pdf | html | md | both (json + pdf)  [default: both]
         #[arg(long, default_value = "both")]
         format: String,
 
-        /// Output path or directory. Defaults to stdout (JSON) or ./<stack>-report.pdf (PDF).
+        /// Output path or directory ("-" for stdout). Defaults to stdout (JSON, HTML, Markdown) or ./<stack>-report.pdf (PDF).
         #[arg(long)]
         output: Option<String>,
 
@@ -115,13 +216,154 @@ enum Commands {
         /// Number of historical runs to include in the trend section.
         #[arg(long, default_value = "10")]
         window: usize,
+
+        /// Exit 1 when the (aggregate) verdict is at or worse than this: fail | warn
+        #[arg(long)]
+        fail_on: Option<String>,
+
+        /// Embed a SHA-256 content hash so the report can be checked with `report verify`.
+        #[arg(long)]
+        sign: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum LockCommands {
+    /// List lock files with their PID, host, age and whether the process is alive
+    List,
+    /// Remove a stale lock (one whose process has exited)
+    Clear {
+        /// Stack whose lock to remove
+        #[arg(required_unless_present = "all", conflicts_with = "all")]
+        stack: Option<String>,
+        /// Remove every stale lock
+        #[arg(long)]
+        all: bool,
+        /// Also remove locks held by a running process
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum CoverageCommands {
+    /// Show fleet coverage over the recorded snapshots
+    Trend {
+        /// Number of most recent snapshots to include
+        #[arg(long, default_value_t = 30)]
+        last: usize,
+        /// Output the series as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write a shields-style SVG badge showing fleet coverage
+    Badge {
+        /// Path to write the SVG to
+        #[arg(long, default_value = "coverage.svg")]
+        output: String,
+        /// Left-hand badge text
+        #[arg(long, default_value = "restore coverage")]
+        label: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommands {
+    /// Check a signed JSON report for modification since it was generated
+    Verify {
+        /// Path to a JSON report (single-stack or fleet)
+        file: String,
+    },
+    /// Show what changed between two saved JSON reports for a stack
+    Compare {
+        stack: String,
+        /// Older report (single-stack or fleet JSON)
+        #[arg(long)]
+        from: String,
+        /// Newer report (single-stack or fleet JSON)
+        #[arg(long)]
+        to: String,
+        /// Output the diff as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PreflightCommands {
+    /// Cap the total penalty one rule can contribute to readiness
+    SetCap {
+        stack: String,
+        /// Rule name (e.g. EnvVarRule)
+        rule: String,
+        /// Maximum total penalty for this rule. Omit to remove the cap.
+        #[arg(long)]
+        max: Option<u32>,
+    },
+    /// Show preflight settings for a stack
+    Show {
+        stack: String,
+    },
+    /// Accept a finding: downgrade it to Info with no penalty
+    Suppress {
+        stack: String,
+        /// Finding ID shown next to the finding in `stack test` output
+        finding: String,
+    },
+    /// Remove a finding from the suppression list
+    Unsuppress {
+        stack: String,
+        finding: String,
     },
 }
 
 #[derive(Subcommand)]
 enum StackCommands {
     Test {
-        compose_file: String,
+        #[arg(required_unless_present = "file")]
+        compose_file: Option<String>,
+        /// Compose file to merge on top, in order (repeat or comma-separate).
+        /// Later files override earlier ones, as with `docker compose -f`;
+        /// a positional file comes first.
+        #[arg(short = 'f', long = "file", value_name = "FILE", value_delimiter = ',')]
+        file: Vec<String>,
+        /// Stack name for history, baselines and policy. Defaults to the
+        /// first file's directory name.
+        #[arg(long)]
+        name: Option<String>,
+        /// Start services one at a time in dependency order instead of
+        /// starting independent services in parallel (for debugging ordering).
+        #[arg(long)]
+        sequential: bool,
+        /// Do not clone bind-mount sources; start containers with no mounts.
+        #[arg(long)]
+        no_clone: bool,
+        /// Skip the disk space preflight check, which walks every bind
+        /// source and can be slow on large directories.
+        #[arg(long)]
+        skip_disk_check: bool,
+        /// If another rehearsal holds the stack's lock, wait up to this many
+        /// seconds for it instead of failing immediately.
+        #[arg(long, value_name = "SECS")]
+        wait: Option<u64>,
+        /// Only run preflight checks and print readiness — no images are
+        /// pulled, no containers started, no run recorded. Exits 3 below
+        /// 40% readiness and 2 below 70%.
+        #[arg(long, conflicts_with_all = ["sequential", "no_clone", "wait", "keep_on_failure", "retries", "changed_only"])]
+        preflight_only: bool,
+        /// When a service fails (or the run errors), leave its containers,
+        /// network and cloned data in place for inspection. Reap them later
+        /// with `rehearsa cleanup`.
+        #[arg(long)]
+        keep_on_failure: bool,
+        /// Recreate a container that exits before the timeout up to N times
+        /// before scoring it. Overrides the stack's `scoring set --start-retries`.
+        #[arg(long, value_name = "N")]
+        retries: Option<u32>,
+        /// Rehearse only services whose definition changed since the pinned
+        /// baseline, plus their dependents; the rest reuse their baseline score.
+        #[arg(long)]
+        changed_only: bool,
     },
 }
 
@@ -131,6 +373,83 @@ enum HistoryCommands {
     Show {
         stack: String,
     },
+    /// Per-service score sparklines and trend slopes
+    Trends {
+        stack: String,
+        /// Number of most recent runs to include
+        #[arg(long, default_value_t = 20)]
+        window: usize,
+        /// Output the series as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare two recorded runs of a stack (timestamps may be partial)
+    Diff {
+        stack: String,
+        from: String,
+        to: String,
+        /// Output the diff as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export run history for spreadsheets (all stacks when none is given)
+    Export {
+        stack: Option<String>,
+        #[arg(long, default_value = "csv", value_parser = ["csv", "json"])]
+        format: String,
+        /// File to write (default: stdout)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Delete old run records. The newest run of each stack is always kept.
+    Prune {
+        /// Keep the N most recent runs per stack
+        #[arg(long)]
+        keep_last: Option<usize>,
+        /// Only prune runs older than this many days
+        #[arg(long, value_name = "DAYS")]
+        older_than: Option<u32>,
+        /// Prune one stack (default: all stacks)
+        #[arg(long)]
+        stack: Option<String>,
+        /// List what would be deleted without deleting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScoringCommands {
+    /// Show effective score bands and service weights
+    Show {
+        stack: String,
+    },
+    /// Set a service weight and/or override score bands for a stack
+    Set {
+        stack: String,
+        /// Service to weight (requires --weight)
+        #[arg(long, requires = "weight")]
+        service: Option<String>,
+        /// Weight multiplier for --service (default 1; 0 excludes it)
+        #[arg(long, requires = "service")]
+        weight: Option<u32>,
+        /// Score for a healthy service  [default: 100]
+        #[arg(long)]
+        healthy: Option<u32>,
+        /// Score for a running service without a healthcheck  [default: 85]
+        #[arg(long)]
+        running: Option<u32>,
+        /// Score for an unhealthy service  [default: 40]
+        #[arg(long)]
+        unhealthy: Option<u32>,
+        /// Score for an exited, timed-out or unstarted service  [default: 0]
+        #[arg(long)]
+        failed: Option<u32>,
+        /// Times a container that exits early is recreated before it is
+        /// scored  [default: 0]
+        #[arg(long)]
+        start_retries: Option<u32>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -156,14 +475,132 @@ enum PolicyCommands {
         #[arg(long)]
         duration_spike_percent: Option<u32>,
 
+        /// Fail on any drift from the baseline
+        #[arg(long)]
+        fail_on_baseline_drift: Option<bool>,
+
+        /// Fail only on major drift (services added/missing, large score drop)
+        #[arg(long)]
+        fail_on_major_drift: Option<bool>,
+
+        /// Fail when a run takes longer than this many seconds
+        #[arg(long = "max-duration", value_name = "SECONDS")]
+        max_duration_seconds: Option<u64>,
+
+        /// Service to set a score floor for (repeatable; pair each with --min-score)
+        #[arg(long = "service", requires = "min_score")]
+        services: Vec<String>,
+
+        /// Minimum score for the matching --service
+        #[arg(long = "min-score", requires = "services")]
+        min_score: Vec<u32>,
+    },
+    /// Set the fleet default policy, applied to every stack. Stack
+    /// policies override it field by field.
+    SetDefault {
+        #[arg(long)]
+        min_confidence: Option<u32>,
+
+        #[arg(long)]
+        min_readiness: Option<u32>,
+
+        #[arg(long)]
+        block_on_regression: Option<bool>,
+
+        #[arg(long)]
+        fail_on_new_service_failure: Option<bool>,
+
+        #[arg(long)]
+        fail_on_duration_spike: Option<bool>,
+
+        #[arg(long)]
+        duration_spike_percent: Option<u32>,
+
+        /// Fail on any drift from the baseline
+        #[arg(long)]
+        fail_on_baseline_drift: Option<bool>,
+
+        /// Fail only on major drift (services added/missing, large score drop)
+        #[arg(long)]
+        fail_on_major_drift: Option<bool>,
+
+        /// Fail when a run takes longer than this many seconds
+        #[arg(long = "max-duration", value_name = "SECONDS")]
+        max_duration_seconds: Option<u64>,
+    },
+    /// Show the fleet default policy
+    ShowDefault,
+    /// Write the given policy fields to many stacks at once
+    Apply {
+        /// Apply to every stack with history.
+        #[arg(long, required_unless_present = "pattern")]
+        all: bool,
+
+        /// Only stacks whose name matches this glob (e.g. "media-*").
+        #[arg(long = "match")]
+        pattern: Option<String>,
+
+        /// Replace conflicting values in existing explicit policies.
+        #[arg(long)]
+        overwrite: bool,
+
+        #[arg(long)]
+        min_confidence: Option<u32>,
+
+        #[arg(long)]
+        min_readiness: Option<u32>,
+
+        #[arg(long)]
+        block_on_regression: Option<bool>,
+
+        #[arg(long)]
+        fail_on_new_service_failure: Option<bool>,
+
+        #[arg(long)]
+        fail_on_duration_spike: Option<bool>,
+
+        #[arg(long)]
+        duration_spike_percent: Option<u32>,
+
+        /// Fail on any drift from the baseline
         #[arg(long)]
         fail_on_baseline_drift: Option<bool>,
+
+        /// Fail only on major drift (services added/missing, large score drop)
+        #[arg(long)]
+        fail_on_major_drift: Option<bool>,
+
+        /// Fail when a run takes longer than this many seconds
+        #[arg(long = "max-duration", value_name = "SECONDS")]
+        max_duration_seconds: Option<u64>,
+
+        /// Service to set a score floor for (repeatable; pair each with --min-score)
+        #[arg(long = "service", requires = "min_score")]
+        services: Vec<String>,
+
+        /// Minimum score for the matching --service
+        #[arg(long = "min-score", requires = "services")]
+        min_score: Vec<u32>,
     },
     Show {
         stack: String,
     },
+    /// Check policy files for contradictory or impossible settings
+    Validate {
+        #[arg(required_unless_present = "all")]
+        stack: Option<String>,
+        /// Validate every stack with a policy file
+        #[arg(long, conflicts_with = "stack")]
+        all: bool,
+    },
     Delete {
         stack: String,
+        /// Skip the confirmation prompt.
+        #[arg(long)]
+        yes: bool,
+        /// Show what would be deleted without removing anything.
+        #[arg(long)]
+        dry_run: bool,
     },
 }
 
@@ -171,15 +608,64 @@ enum PolicyCommands {
 enum BaselineCommands {
     Set {
         compose_file: String,
+        /// Derive expected duration as this percentile (1-100) over recent runs
+        /// instead of the latest run's duration.
+        #[arg(long)]
+        duration_percentile: Option<u32>,
+        /// Number of recent runs used with --duration-percentile.
+        #[arg(long, default_value_t = 10)]
+        window: usize,
     },
     Show {
         stack: String,
+        /// Output the full baseline as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write the pinned baseline as JSON (to stdout, or --output) for
+    /// moving between hosts or committing to Git.
+    Export {
+        stack: String,
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Pin a baseline from an exported JSON file. Recorded as a new version.
+    Import {
+        stack: String,
+        #[arg(required_unless_present = "input")]
+        file: Option<String>,
+        /// Exported baseline file (alternative to the positional FILE)
+        #[arg(long, conflicts_with = "file")]
+        input: Option<String>,
+        /// Replace an existing baseline for the stack
+        #[arg(long)]
+        force: bool,
     },
     Diff {
         stack: String,
     },
+    /// Set how far a run may move from the baseline before it counts as drift.
+    SetTolerance {
+        stack: String,
+        /// Confidence points a run may fall below the baseline (default 5)
+        #[arg(long)]
+        confidence: Option<u32>,
+        /// Readiness points a run may fall below the baseline (default 5)
+        #[arg(long)]
+        readiness: Option<u32>,
+        /// Slowdown in percent of the expected duration (default 20)
+        #[arg(long)]
+        duration_pct: Option<u32>,
+    },
+    /// Delete the pinned baseline and its version history.
     Delete {
         stack: String,
+        /// Skip the confirmation prompt.
+        #[arg(long)]
+        yes: bool,
+        /// Show what would be deleted, including the history log, without removing anything.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Promote a historical run to the current baseline.
     /// Defaults to the latest run if --timestamp is not specified.
@@ -189,6 +675,33 @@ enum BaselineCommands {
         /// Run `rehearsa history show <stack>` to list available timestamps.
         #[arg(long)]
         timestamp: Option<String>,
+        /// Derive expected duration as this percentile (1-100) over recent runs
+        /// instead of the promoted run's duration.
+        #[arg(long)]
+        duration_percentile: Option<u32>,
+        /// Number of recent runs used with --duration-percentile.
+        #[arg(long, default_value_t = 10)]
+        window: usize,
+    },
+    /// Re-pin an earlier baseline version. Defaults to the previous version.
+    Rollback {
+        stack: String,
+        /// Version number as listed by `rehearsa baseline history --stack <stack>`.
+        #[arg(long, conflicts_with = "to")]
+        version: Option<usize>,
+        /// Promotion timestamp of the version (partial match supported).
+        #[arg(long)]
+        to: Option<String>,
+    },
+    /// Recompute a baseline's expected duration as a percentile over recent runs.
+    RecomputeDuration {
+        stack: String,
+        /// Percentile to use (1-100).
+        #[arg(long, default_value_t = 75)]
+        percentile: u32,
+        /// Number of recent runs to compute over.
+        #[arg(long, default_value_t = 10)]
+        window: usize,
     },
     /// Show baseline version history.
     /// Omit --stack to see all stacks; provide --stack for per-version diffs.
@@ -201,38 +714,143 @@ enum BaselineCommands {
     AutoInit,
 }
 
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Write a commented template to ~/.rehearsa/config.toml
+    Init {
+        /// Overwrite an existing file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
 #[derive(Subcommand)]
 enum DaemonCommands {
     Install,
     Uninstall,
     Status,
-    Run,
+    /// Stop triggering rehearsals (e.g. during host maintenance) without uninstalling.
+    Pause,
+    /// Resume triggering rehearsals after `daemon pause`.
+    Resume,
+    Run {
+        /// Force-remove locks held past the max lock age so the stack
+        /// is rehearsed again (the hung process is left running).
+        #[arg(long)]
+        break_stuck: bool,
+        /// Make a single pass instead of staying resident: rehearse every
+        /// stack whose schedule is due, wait for them, then exit. For hosts
+        /// driven by their own cron or a Kubernetes CronJob.
+        #[arg(long)]
+        once: bool,
+        /// With --once, rehearse every watched stack, due or not.
+        #[arg(long, requires = "once")]
+        all: bool,
+    },
     Watch {
         stack: String,
         compose_file: String,
         /// Cron expression for scheduled rehearsals, e.g. "0 3 * * *"
         #[arg(long)]
         schedule: Option<String>,
+        /// IANA timezone the schedule is evaluated in, e.g. "Europe/London" (default UTC)
+        #[arg(long, requires = "schedule")]
+        timezone: Option<String>,
         /// Run a rehearsal on daemon start if a scheduled window was missed
         #[arg(long, default_value_t = false)]
         catch_up: bool,
-        /// Named backup provider to associate with this stack (see: rehearsa provider list)
-        #[arg(long)]
-        provider: Option<String>,
+        /// Named backup provider to verify before each rehearsal (see: rehearsa provider list).
+        /// Repeat to attach several; all must pass.
+        #[arg(long = "provider", value_name = "NAME")]
+        providers: Vec<String>,
         /// Named notify channel override for this stack (see: rehearsa notify list)
         #[arg(long)]
         notify: Option<String>,
+        /// Promote the latest run to baseline after N consecutive clean scheduled rehearsals
+        #[arg(long, value_name = "N")]
+        auto_promote_after: Option<u32>,
+        /// Delay each scheduled rehearsal by a random 0..SECS seconds so stacks
+        /// sharing a schedule do not all start at once (default: daemon setting)
+        #[arg(long, value_name = "SECS", requires = "schedule")]
+        jitter: Option<u64>,
+        /// Concurrency group this stack belongs to (see: rehearsa daemon set-group)
+        #[arg(long)]
+        group: Option<String>,
     },
     Unwatch {
         stack: String,
     },
-    List,
+    List {
+        /// Output the watches, with each next scheduled run, as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Set the maximum number of simultaneous rehearsals.
     /// Restart the daemon after changing.
     SetConcurrency {
         /// Maximum concurrent rehearsals (minimum: 1, recommended: 1 for low-power hardware)
         limit: usize,
     },
+    /// Set how runs are weighted when computing stability.
+    SetStabilityWeighting {
+        /// flat (default), linear, or exponential — the latter two favour recent runs
+        weighting: String,
+    },
+    /// Set the directory used for per-stack lock files.
+    SetLockDir {
+        dir: String,
+    },
+    /// Set the writable directory for daemon state (for read-only /etc hosts).
+    SetStateDir {
+        dir: String,
+    },
+    /// Set how long a live rehearsal may hold its lock before it is reported as stuck.
+    SetLockTimeout {
+        /// Maximum lock-hold age in minutes (0 disables stuck detection)
+        minutes: u64,
+    },
+    /// Require services without a healthcheck to stay running this long before scoring.
+    SetRunningProbe {
+        /// Seconds (0 = score as soon as running; the default)
+        seconds: u64,
+    },
+    /// Suppress repeat notifications for the same stack and event within a window.
+    SetNotifyDedup {
+        /// Window in minutes (0 disables; default 30)
+        minutes: u64,
+    },
+    /// Set the coverage percentage below which `rehearsa coverage` exits 1.
+    SetMinCoverage {
+        /// Percentage 0–100 (default 100)
+        pct: u32,
+    },
+    /// Create or resize a concurrency group that watches can join with --group.
+    /// Restart the daemon after changing.
+    SetGroup {
+        name: String,
+        /// Rehearsals of the group's stacks allowed at once (0 removes the group)
+        limit: usize,
+    },
+    /// Choose the daemon's log output: human (default) or json.
+    SetLogFormat {
+        /// human (default) or json — one object per event with ts, level, stack, event, msg
+        format: String,
+    },
+    /// Serve /healthz and /metrics (Prometheus) from the daemon.
+    SetMetricsAddr {
+        /// Listen address, e.g. 127.0.0.1:9464 ("off" disables; the default)
+        addr: String,
+    },
+    /// Spread scheduled rehearsals by a random delay (per-watch --jitter wins).
+    SetScheduleJitter {
+        /// Maximum delay in seconds (0 disables; the default)
+        seconds: u64,
+    },
+    /// Coalesce rapid Compose file changes into one rehearsal.
+    SetWatchDebounce {
+        /// Quiet period in seconds before a change triggers (0 = immediately; default 2)
+        seconds: u64,
+    },
     /// Show current daemon configuration and resolved settings.
     Config,
 }
@@ -242,11 +860,27 @@ enum NotifyCommands {
     /// Register a new webhook notification channel
     Add {
         /// Unique name for this channel (e.g. slack-ops, discord-alerts)
+        name: String,
+        /// Webhook URL to POST notifications to (repeatable to fan out)
+        #[arg(long, required = true)]
+        url: Vec<String>,
+        /// Payload format for every URL: json | slack | discord  [default: json]
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Optional secret sent as X-Rehearsa-Secret header
+        #[arg(long)]
+        secret: Option<String>,
+    },
+    /// Add a webhook target to a channel (creates the channel if it doesn't exist)
+    AddWebhook {
         name: String,
         /// Webhook URL to POST notifications to
         #[arg(long)]
         url: String,
-        /// Optional secret sent as X-Rehearsa-Secret header
+        /// Payload format: json | slack | discord  [default: json]
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// Secret for this target only; overrides the channel secret
         #[arg(long)]
         secret: Option<String>,
     },
@@ -259,6 +893,12 @@ enum NotifyCommands {
     /// Remove a notify channel
     Delete {
         name: String,
+        /// Skip the confirmation prompt.
+        #[arg(long)]
+        yes: bool,
+        /// Show what would be deleted without removing anything.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Set the global default notify channel
     Default {
@@ -267,6 +907,32 @@ enum NotifyCommands {
     /// Send a test notification to verify delivery
     Test {
         name: String,
+        /// Send even if a test was sent to this channel within the dedup window
+        #[arg(long)]
+        force: bool,
+    },
+    /// Only deliver events at or above a severity on a channel
+    /// (critical > warning > recovery; recovery is always delivered)
+    SetThreshold {
+        name: String,
+        /// critical | warning | recovery
+        #[arg(long, required_unless_present = "clear")]
+        min_severity: Option<String>,
+        /// Remove the threshold and deliver every event
+        #[arg(long, conflicts_with = "min_severity")]
+        clear: bool,
+    },
+    /// Route one event type to a channel, ahead of per-stack and default channels
+    Route {
+        /// Event: rehearsal_fatal_error, provider_verification_failed, policy_violation,
+        /// baseline_drift, rehearsal_recovered, rehearsal_stuck, baseline_auto_promoted
+        event: String,
+        /// Channel to receive this event
+        #[arg(required_unless_present = "clear")]
+        channel: Option<String>,
+        /// Remove the route for this event
+        #[arg(long, conflicts_with = "channel")]
+        clear: bool,
     },
     /// Add or update the email transport on a channel
     AddEmail {
@@ -315,19 +981,21 @@ enum ProviderCommands {
         /// Unique name for this provider (e.g. restic-main, client-a-restic)
         name: String,
 
-        /// Provider type. Supported: restic
+        /// Provider type. Supported: restic, borg, rclone
         #[arg(long)]
         kind: String,
 
-        /// Repository path or URI (e.g. /mnt/backups or s3:bucket/path)
+        /// Repository path or URI (e.g. /mnt/backups, s3:bucket/path, or remote:bucket/path for rclone)
         #[arg(long)]
         repo: String,
 
         /// Environment variable that holds the repository password
+        /// (for rclone: the config password)
         #[arg(long)]
         password_env: Option<String>,
 
         /// Path to a file containing the repository password
+        /// (for rclone: the rclone config file)
         #[arg(long)]
         password_file: Option<String>,
     },
@@ -340,6 +1008,12 @@ enum ProviderCommands {
     /// Remove a provider
     Delete {
         name: String,
+        /// Skip the confirmation prompt.
+        #[arg(long)]
+        yes: bool,
+        /// Show what would be deleted without removing anything.
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Verify a provider's repository is reachable and has snapshots
     Verify {
@@ -354,6 +1028,9 @@ enum ProviderCommands {
         /// Require a test restore before each rehearsal
         #[arg(long, default_value_t = false)]
         test_restore: bool,
+        /// Time limit in seconds for each test-restore step (default 300)
+        #[arg(long)]
+        restore_timeout: Option<u64>,
     },
 }
 
@@ -363,7 +1040,19 @@ enum ProviderCommands {
 
 #[tokio::main]
 async fn main() {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // `config init --force` must still work when the file is broken.
+    if !matches!(cli.command, Commands::Config { .. }) {
+        match config::load_cli_defaults() {
+            Ok(defaults) => apply_cli_defaults(&mut cli, &matches, defaults),
+            Err(e) => {
+                eprintln!("Config error: {}", e);
+                exit(1);
+            }
+        }
+    }
 
     let pull_policy = match cli.pull.as_str() {
         "always" => PullPolicy::Always,
@@ -371,46 +1060,105 @@ async fn main() {
         _        => PullPolicy::IfMissing,
     };
 
-    match cli.command {
+    docker::connect::set_container_host(cli.docker_host.clone());
+
+    // --json and --format json are the same switch; every other --json
+    // check keeps working off cli.json.
+    let output_format = if cli.json {
+        OutputFormat::Json
+    } else {
+        cli.format.parse().unwrap_or_default()
+    };
+    cli.json = output_format == OutputFormat::Json;
+
+    let verbosity = if cli.quiet {
+        Verbosity::Quiet
+    } else if cli.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Normal
+    };
+
+    match cli.command {
 
         // ==================================================
         // STACK
         // ==================================================
 
         Commands::Stack { command } => match command {
-            StackCommands::Test { compose_file } => {
-                let json_mode = cli.json || cli.ci;
-
-                match test_stack(
-                    &compose_file,
-                    cli.timeout,
-                    json_mode,
-                    cli.inject_failure.clone(),
-                    cli.strict_integrity,
-                    pull_policy,
+            StackCommands::Test { compose_file, file, name, preflight_only: true, skip_disk_check, .. } => {
+                let format = if cli.ci && !output_format.is_structured() {
+                    OutputFormat::Json
+                } else {
+                    output_format
+                };
+                let compose_files: Vec<String> = compose_file.into_iter().chain(file).collect();
+
+                match engine::stack::preflight_stack(
+                    &compose_files,
+                    name,
+                    format,
+                    verbosity,
+                    cli.profiles.clone(),
+                    !skip_disk_check,
                 ).await {
+                    Ok(score) => match ExitCode::from_score(score) {
+                        ExitCode::Success => {}
+                        code => exit(code as i32),
+                    },
+                    Err(e) => {
+                        if format.is_structured() {
+                            print_fatal_structured(&compose_files.join(","), &e.to_string(), format);
+                        } else {
+                            eprintln!("Preflight: FAILED");
+                            eprintln!("Fatal Error: {}", e);
+                        }
+                        exit(ExitCode::Fatal as i32);
+                    }
+                }
+            }
+            StackCommands::Test { compose_file, file, name, sequential, no_clone, skip_disk_check, wait, keep_on_failure, retries, changed_only, .. } => {
+                let format = if cli.ci && !output_format.is_structured() {
+                    OutputFormat::Json
+                } else {
+                    output_format
+                };
+                let compose_files: Vec<String> = compose_file.into_iter().chain(file).collect();
+
+                match test_stack(&compose_files, TestOptions {
+                    stack_name:       name,
+                    timeout:          cli.timeout,
+                    running_probe_seconds: daemon::resolve_config().running_probe_seconds.value,
+                    format,
+                    verbosity,
+                    inject_failure:   cli.inject_failure.clone(),
+                    strict_integrity: cli.strict_integrity,
+                    pull_policy,
+                    image_archive:    cli.image_archive.clone(),
+                    profiles:         cli.profiles.clone(),
+                    sequential,
+                    clone_volumes:    !no_clone,
+                    disk_check:       !skip_disk_check,
+                    lock_wait:        wait.map(std::time::Duration::from_secs),
+                    keep_on_failure,
+                    start_retries:    retries,
+                    changed_only,
+                }).await {
                     Ok(summary) => {
-                        if summary.policy_violated {
-                            exit(4);
-                        } else if summary.baseline_drift {
-                            exit(5);
-                        } else if summary.confidence < 40 {
-                            exit(3);
-                        } else if summary.confidence < 70 {
-                            exit(2);
+                        if cli.explain_policy && !format.is_structured() {
+                            policy::print_policy_checks(&summary.stack, &summary.policy_checks);
                         }
+
+                        exit(summary.exit_code as i32);
                     }
                     Err(e) => {
-                        if json_mode {
-                            println!(
-                                r#"{{"stack":"{}","fatal_error":"{}"}}"#,
-                                compose_file, e
-                            );
+                        if format.is_structured() {
+                            print_fatal_structured(&compose_files.join(","), &e.to_string(), format);
                         } else {
                             eprintln!("Stack Restore Simulation: FAILED");
                             eprintln!("Fatal Error: {}", e);
                         }
-                        exit(1);
+                        exit(ExitCode::Fatal as i32);
                     }
                 }
             }
@@ -433,6 +1181,58 @@ async fn main() {
                     exit(1);
                 }
             }
+            HistoryCommands::Trends { stack, window, json } => {
+                if json || cli.json {
+                    match serde_json::to_string_pretty(&history::service_trends(&stack, window)) {
+                        Ok(out) => println!("{}", out),
+                        Err(e) => {
+                            eprintln!("JSON error: {}", e);
+                            exit(1);
+                        }
+                    }
+                } else if let Err(e) = history::show_trends(&stack, window) {
+                    eprintln!("History error: {}", e);
+                    exit(1);
+                }
+            }
+            HistoryCommands::Diff { stack, from, to, json } => {
+                match history::diff_runs(&stack, &from, &to) {
+                    Ok(diff) if json || cli.json => match serde_json::to_string_pretty(&diff) {
+                        Ok(out) => println!("{}", out),
+                        Err(e) => {
+                            eprintln!("JSON error: {}", e);
+                            exit(1);
+                        }
+                    },
+                    Ok(diff) => history::print_run_diff(&diff),
+                    Err(e) => {
+                        eprintln!("History error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+            HistoryCommands::Export { stack, format, output } => {
+                let path = output.as_deref().map(std::path::Path::new);
+                match history::export_history(stack.as_deref(), &format, path) {
+                    Ok(count) => if let Some(p) = &output {
+                        println!("Exported {} run(s) to {}", count, p);
+                    },
+                    Err(e) => {
+                        eprintln!("History error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+            HistoryCommands::Prune { keep_last, older_than, stack, dry_run } => {
+                match history::prune(stack.as_deref(), keep_last, older_than, dry_run) {
+                    Ok(count) if dry_run => println!("{} file(s) would be pruned", count),
+                    Ok(count) => println!("Pruned {} file(s)", count),
+                    Err(e) => {
+                        eprintln!("History error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
         },
 
         // ==================================================
@@ -449,7 +1249,19 @@ async fn main() {
                 fail_on_duration_spike,
                 duration_spike_percent,
                 fail_on_baseline_drift,
+                fail_on_major_drift,
+                max_duration_seconds,
+                services,
+                min_score,
             } => {
+                let min_service_scores = match service_floors(services, min_score) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("Policy error: {}", e);
+                        exit(1);
+                    }
+                };
+
                 let policy = StackPolicy {
                     min_confidence,
                     min_readiness,
@@ -458,6 +1270,9 @@ async fn main() {
                     fail_on_duration_spike,
                     duration_spike_percent,
                     fail_on_baseline_drift,
+                    fail_on_major_drift,
+                    max_duration_seconds,
+                    min_service_scores,
                 };
 
                 if let Err(e) = save_policy(&stack, &policy) {
@@ -468,6 +1283,124 @@ async fn main() {
                 println!("Policy saved for stack '{}'", stack);
             }
 
+            PolicyCommands::SetDefault {
+                min_confidence,
+                min_readiness,
+                block_on_regression,
+                fail_on_new_service_failure,
+                fail_on_duration_spike,
+                duration_spike_percent,
+                fail_on_baseline_drift,
+                fail_on_major_drift,
+                max_duration_seconds,
+            } => {
+                let policy = StackPolicy {
+                    min_confidence,
+                    min_readiness,
+                    block_on_regression,
+                    fail_on_new_service_failure,
+                    fail_on_duration_spike,
+                    duration_spike_percent,
+                    fail_on_baseline_drift,
+                    fail_on_major_drift,
+                    max_duration_seconds,
+                    min_service_scores: None,
+                };
+
+                if let Err(e) = save_policy(policy::DEFAULT_POLICY_NAME, &policy) {
+                    eprintln!("Policy error: {}", e);
+                    exit(1);
+                }
+
+                println!("Default policy saved");
+            }
+
+            PolicyCommands::ShowDefault => {
+                if let Err(e) = policy::show_default_policy() {
+                    eprintln!("Policy error: {}", e);
+                    exit(1);
+                }
+            }
+
+            PolicyCommands::Apply {
+                all: _,
+                pattern,
+                overwrite,
+                min_confidence,
+                min_readiness,
+                block_on_regression,
+                fail_on_new_service_failure,
+                fail_on_duration_spike,
+                duration_spike_percent,
+                fail_on_baseline_drift,
+                fail_on_major_drift,
+                max_duration_seconds,
+                services,
+                min_score,
+            } => {
+                let min_service_scores = match service_floors(services, min_score) {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("Policy error: {}", e);
+                        exit(1);
+                    }
+                };
+
+                let fields = StackPolicy {
+                    min_confidence,
+                    min_readiness,
+                    block_on_regression,
+                    fail_on_new_service_failure,
+                    fail_on_duration_spike,
+                    duration_spike_percent,
+                    fail_on_baseline_drift,
+                    fail_on_major_drift,
+                    max_duration_seconds,
+                    min_service_scores,
+                };
+
+                if fields.is_empty() {
+                    eprintln!("Policy error: no policy fields given — nothing to apply.");
+                    exit(1);
+                }
+
+                let stacks: Vec<String> = history::stack_names()
+                    .into_iter()
+                    .filter(|s| pattern.as_deref().is_none_or(|p| glob_match(p, s)))
+                    .collect();
+
+                if stacks.is_empty() {
+                    println!("No matching stacks with history — nothing to apply.");
+                    return;
+                }
+
+                let summary = match apply_policy(&stacks, &fields, overwrite) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        eprintln!("Policy error: {}", e);
+                        exit(1);
+                    }
+                };
+
+                for stack in &summary.applied {
+                    println!("  applied   {}", stack);
+                }
+                for (stack, conflicts) in &summary.skipped {
+                    println!("  skipped   {}  (conflicts: {})", stack, conflicts.join(", "));
+                }
+
+                println!();
+                println!(
+                    "Policy applied to {} stack(s), skipped {}.",
+                    summary.applied.len(),
+                    summary.skipped.len()
+                );
+
+                if !summary.skipped.is_empty() {
+                    println!("Re-run with --overwrite to replace conflicting values.");
+                }
+            }
+
             PolicyCommands::Show { stack } => {
                 if let Err(e) = show_policy(&stack) {
                     eprintln!("Policy error: {}", e);
@@ -475,12 +1408,74 @@ async fn main() {
                 }
             }
 
-            PolicyCommands::Delete { stack } => {
-                if let Err(e) = delete_policy(&stack) {
-                    eprintln!("Policy error: {}", e);
+            PolicyCommands::Validate { stack, all: _ } => {
+                let stacks = match stack {
+                    Some(s) => vec![s],
+                    None    => policy::policy_stacks(),
+                };
+
+                if stacks.is_empty() {
+                    println!("No policies found.");
+                    return;
+                }
+
+                let mut errors = 0;
+
+                for stack in &stacks {
+                    match policy::validate_policy(stack) {
+                        Ok(issues) if issues.is_empty() => println!("{}: OK", stack),
+                        Ok(issues) => {
+                            println!("{}:", stack);
+                            for issue in &issues {
+                                println!("  {:<8} {}", issue.level, issue.message);
+                            }
+                            errors += issues.iter().filter(|i| i.level == policy::IssueLevel::Error).count();
+                        }
+                        Err(e) => {
+                            eprintln!("Policy error: {}", e);
+                            errors += 1;
+                        }
+                    }
+                }
+
+                if errors > 0 {
+                    eprintln!("{} error(s) found.", errors);
+                    exit(1);
+                }
+            }
+
+            PolicyCommands::Delete { stack, yes, dry_run } => {
+                // A policy that no longer parses must still be deletable
+                match policy_exists(&stack) {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        println!("No policy found for '{}' — nothing to delete.", stack);
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("Policy error: {}", e);
+                        exit(1);
+                    }
+                }
+
+                if dry_run {
+                    println!("Would delete policy for stack '{}'.", stack);
+                    return;
+                }
+
+                if !confirm(&format!("Delete policy for stack '{}'?", stack), yes) {
+                    println!("Aborted.");
                     exit(1);
                 }
-                println!("Policy deleted for stack '{}'", stack);
+
+                match delete_policy(&stack) {
+                    Ok(true)  => println!("Policy deleted for stack '{}'", stack),
+                    Ok(false) => println!("No policy found for '{}' — nothing to delete.", stack),
+                    Err(e) => {
+                        eprintln!("Policy error: {}", e);
+                        exit(1);
+                    }
+                }
             }
         },
 
@@ -490,7 +1485,7 @@ async fn main() {
 
         Commands::Baseline { command } => match command {
 
-            BaselineCommands::Set { compose_file } => {
+            BaselineCommands::Set { compose_file, duration_percentile, window } => {
                 let compose_path = std::path::Path::new(&compose_file);
                 let stack_name = compose_path
                     .parent()
@@ -506,7 +1501,15 @@ async fn main() {
                     });
 
                 if let Some(latest) = history::load_latest(&stack_name) {
-                    let baseline = StackBaseline {
+                    if !latest.reused_services.is_empty() {
+                        eprintln!(
+                            "Baseline error: the latest run of '{}' was a --changed-only run. Run a full `stack test` first.",
+                            stack_name
+                        );
+                        exit(1);
+                    }
+
+                    let mut baseline = StackBaseline {
                         schema_version: history::CURRENT_SCHEMA_VERSION,
                         stack: stack_name.clone(),
                         expected_services: latest.services.keys().cloned().collect(),
@@ -516,8 +1519,18 @@ async fn main() {
                         service_scores: latest.services,
                         pinned_at: Some(latest.timestamp.clone()),
                         promoted_at: Some(chrono::Utc::now().to_rfc3339()),
+                        duration_method: Some(baseline::DurationMethod::PinnedRun),
+                        service_hashes: latest.service_hashes,
+                        tolerance: load_baseline(&stack_name).map(|b| b.tolerance).unwrap_or_default(),
                     };
 
+                    if let Some(p) = duration_percentile {
+                        if let Err(e) = baseline::apply_duration_percentile(&mut baseline, p, window) {
+                            eprintln!("Baseline error: {}", e);
+                            exit(1);
+                        }
+                    }
+
                     if let Err(e) = save_baseline(&stack_name, &baseline) {
                         eprintln!("Baseline error: {}", e);
                         exit(1);
@@ -533,7 +1546,17 @@ async fn main() {
                 }
             }
 
-            BaselineCommands::Show { stack } => {
+            BaselineCommands::Show { stack, json } if json || cli.json => {
+                match baseline::export_baseline(&stack) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+
+            BaselineCommands::Show { stack, .. } => {
                 if let Some(b) = load_baseline(&stack) {
                     println!("Restore Contract: {}", b.stack);
                     println!("{}", "─".repeat(50));
@@ -541,7 +1564,11 @@ async fn main() {
                     if let Some(r) = b.expected_readiness {
                         println!("Readiness floor  : {}%", r);
                     }
-                    println!("Duration ceiling : {}s", b.expected_duration);
+                    println!(
+                        "Duration ceiling : {}s ({})",
+                        b.expected_duration,
+                        b.duration_method.as_ref().unwrap_or(&baseline::DurationMethod::PinnedRun),
+                    );
                     println!(
                         "Services         : {}",
                         b.expected_services.join(", ")
@@ -556,6 +1583,85 @@ async fn main() {
                 }
             }
 
+            BaselineCommands::Export { stack, output } => {
+                let out = match baseline::export_baseline(&stack) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                };
+
+                match output {
+                    Some(path) => {
+                        if let Err(e) = std::fs::write(&path, out) {
+                            eprintln!("Baseline error: Failed to write {}: {}", path, e);
+                            exit(1);
+                        }
+                        println!("Baseline for '{}' exported to {}", stack, path);
+                    }
+                    None => println!("{}", out),
+                }
+            }
+
+            BaselineCommands::Import { stack, file, input, force } => {
+                let file = file.or(input).unwrap_or_default();
+                let content = match std::fs::read_to_string(&file) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("Baseline error: Failed to read {}: {}", file, e);
+                        exit(1);
+                    }
+                };
+
+                match baseline::import_baseline(&stack, &content, force) {
+                    Ok(b) => {
+                        println!("Baseline imported for '{}' from {}", stack, file);
+                        println!(
+                            "  confidence {}%  readiness {}  duration {}s  services {}",
+                            b.expected_confidence,
+                            b.expected_readiness.map(|r| format!("{}%", r)).unwrap_or_else(|| "-".to_string()),
+                            b.expected_duration,
+                            b.expected_services.len(),
+                        );
+                    }
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+
+            BaselineCommands::SetTolerance { stack, confidence, readiness, duration_pct } => {
+                if confidence.is_none() && readiness.is_none() && duration_pct.is_none() {
+                    eprintln!("Baseline error: give at least one of --confidence, --readiness, --duration-pct");
+                    exit(1);
+                }
+
+                let update = baseline::DriftTolerance {
+                    confidence_tolerance:   confidence,
+                    readiness_tolerance:    readiness,
+                    duration_tolerance_pct: duration_pct,
+                };
+
+                match baseline::set_tolerance(&stack, update) {
+                    Ok(t) => {
+                        let show = |set: Option<u32>, value: u32| match set {
+                            Some(_) => format!("±{}%", value),
+                            None    => format!("±{}% (default)", value),
+                        };
+                        println!("Drift tolerance for '{}':", stack);
+                        println!("  Confidence : {}", show(t.confidence_tolerance, t.confidence()));
+                        println!("  Readiness  : {}", show(t.readiness_tolerance, t.readiness()));
+                        println!("  Duration   : {}", show(t.duration_tolerance_pct, t.duration_pct()));
+                    }
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+
             BaselineCommands::Diff { stack } => {
                 let baseline = match load_baseline(&stack) {
                     Some(b) => b,
@@ -587,15 +1693,20 @@ async fn main() {
                 println!("Baseline Diff: {}", stack);
                 println!("{}", "─".repeat(60));
                 println!(
-                    "{:<20} {:<12} {:<12} {}",
-                    "Metric", "Contract", "Current", "Delta"
+                    "{:<20} {:<12} {:<12} Delta",
+                    "Metric", "Contract", "Current"
                 );
                 println!("{}", "─".repeat(60));
 
-                let conf_delta_str = if drift.confidence_delta > 0 {
+                // Rows are flagged against the baseline's tolerances, as severity() judges them
+                let tolerance = &drift.tolerance;
+
+                let conf_delta_str = if drift.confidence_delta < -(tolerance.confidence() as i32) {
+                    format!("{}%  ⚠", drift.confidence_delta)
+                } else if drift.confidence_delta > 0 {
                     format!("+{}%  ✓", drift.confidence_delta)
                 } else if drift.confidence_delta < 0 {
-                    format!("{}%  ⚠", drift.confidence_delta)
+                    format!("{}%  ✓", drift.confidence_delta)
                 } else {
                     "0      ✓".to_string()
                 };
@@ -612,8 +1723,10 @@ async fn main() {
                     drift.readiness_delta,
                     baseline.expected_readiness,
                 ) {
-                    let r_str = if rd < 0 {
+                    let r_str = if rd < -(tolerance.readiness() as i32) {
                         format!("{}%  ⚠", rd)
+                    } else if rd < 0 {
+                        format!("{}%  ✓", rd)
                     } else {
                         format!("+{}%  ✓", rd)
                     };
@@ -627,10 +1740,11 @@ async fn main() {
                 }
 
                 if let Some(dd) = drift.duration_delta_percent {
-                    let d_str = if dd > 20 {
-                        format!("+{}%  ⚠", dd)
+                    let sign = if dd >= 0 { "+" } else { "" };
+                    let d_str = if dd > tolerance.duration_pct() as i32 {
+                        format!("{}{}%  ⚠", sign, dd)
                     } else {
-                        format!("+{}%  ✓", dd)
+                        format!("{}{}%  ✓", sign, dd)
                     };
                     println!(
                         "{:<20} {:<12} {:<12} {}",
@@ -648,30 +1762,86 @@ async fn main() {
                     println!("⚠  Missing services: {}", drift.missing_services.join(", "));
                 }
 
-                let has_drift = drift.confidence_delta < 0
-                    || !drift.new_services.is_empty()
-                    || !drift.missing_services.is_empty()
-                    || drift.duration_delta_percent.unwrap_or(0) > 20;
+                let severity = drift.severity();
 
                 println!();
-                if has_drift {
-                    println!("Status: DRIFT DETECTED");
-                    exit(2);
+                if severity != DriftSeverity::None {
+                    println!("Status: DRIFT DETECTED ({})", severity);
+                    exit(ExitCode::BaselineDrift as i32);
                 } else {
                     println!("Status: CONTRACT HONOURED");
                 }
             }
 
-            BaselineCommands::Delete { stack } => {
-                if let Err(e) = delete_baseline(&stack) {
+            BaselineCommands::Delete { stack, yes, dry_run } => {
+                let plan = match baseline::plan_baseline_delete(&stack) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                };
+
+                if plan.is_empty() {
+                    println!("No baseline found for '{}' — nothing to delete.", stack);
+                    return;
+                }
+
+                if dry_run || !yes {
+                    println!("{} baseline for '{}':", if dry_run { "Would delete" } else { "Deleting" }, stack);
+                    if let Some(ref path) = plan.baseline_file {
+                        println!("  baseline file : {}", path.display());
+                    }
+                    if let Some(ref dir) = plan.history_dir {
+                        println!(
+                            "  history log   : {} ({} version{})",
+                            dir.display(),
+                            plan.history_versions,
+                            if plan.history_versions == 1 { "" } else { "s" },
+                        );
+                    }
+                }
+
+                if dry_run {
+                    return;
+                }
+
+                if !confirm(&format!("Delete baseline for '{}'?", stack), yes) {
+                    println!("Aborted.");
+                    exit(1);
+                }
+
+                match delete_baseline(&stack) {
+                    Ok(true)  => println!("Baseline deleted for '{}'", stack),
+                    Ok(false) => println!("No baseline found for '{}' — nothing to delete.", stack),
+                    Err(e) => {
+                        eprintln!("Baseline error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+
+            BaselineCommands::Promote { stack, timestamp, duration_percentile, window } => {
+                if let Err(e) = baseline::promote_baseline(
+                    &stack,
+                    timestamp.as_deref(),
+                    duration_percentile,
+                    window,
+                ) {
+                    eprintln!("Baseline error: {}", e);
+                    exit(1);
+                }
+            }
+
+            BaselineCommands::Rollback { stack, version, to } => {
+                if let Err(e) = baseline::rollback_baseline(&stack, version, to.as_deref()) {
                     eprintln!("Baseline error: {}", e);
                     exit(1);
                 }
-                println!("Baseline deleted for '{}'", stack);
             }
 
-            BaselineCommands::Promote { stack, timestamp } => {
-                if let Err(e) = baseline::promote_baseline(&stack, timestamp.as_deref()) {
+            BaselineCommands::RecomputeDuration { stack, percentile, window } => {
+                if let Err(e) = baseline::recompute_duration(&stack, percentile, window) {
                     eprintln!("Baseline error: {}", e);
                     exit(1);
                 }
@@ -716,12 +1886,8 @@ async fn main() {
                     print!("  {} ... ", watch.stack);
 
                     match test_stack(
-                        &watch.compose_path,
-                        120,
-                        false,
-                        None,
-                        false,
-                        PullPolicy::IfMissing,
+                        std::slice::from_ref(&watch.compose_path),
+                        daemon::unattended_test_options(),
                     ).await {
                         Ok(summary) => {
                             // Pin whatever came back as the initial baseline
@@ -736,6 +1902,9 @@ async fn main() {
                                     service_scores: latest.services,
                                     pinned_at: Some(latest.timestamp.clone()),
                                     promoted_at: Some(chrono::Utc::now().to_rfc3339()),
+                                    duration_method: Some(baseline::DurationMethod::PinnedRun),
+                                    service_hashes: latest.service_hashes,
+                                    tolerance: Default::default(),
                                 };
                                 match baseline::save_baseline(&watch.stack, &b) {
                                     Ok(_) => {
@@ -795,23 +1964,45 @@ async fn main() {
                     exit(1);
                 }
             }
-            DaemonCommands::Run => {
-                if let Err(e) = daemon::run_daemon().await {
+            DaemonCommands::Pause => {
+                if let Err(e) = daemon::pause_daemon() {
                     eprintln!("Daemon error: {}", e);
                     exit(1);
                 }
             }
-            DaemonCommands::Watch { stack, compose_file, schedule, catch_up, provider, notify } => {
-                // Validate the provider name exists before registering the watch
-                if let Some(ref pname) = provider {
-                    if provider::load_provider(pname).is_none() {
-                        eprintln!(
-                            "Provider '{}' not found. Register it first with: rehearsa provider add {}",
-                            pname, pname
-                        );
-                        exit(1);
-                    }
-                }
+            DaemonCommands::Resume => {
+                if let Err(e) = daemon::resume_daemon() {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::Run { break_stuck, once: true, all } => {
+                match daemon::run_once(all, break_stuck).await {
+                    Ok(0) => println!("No stacks due — nothing to rehearse."),
+                    Ok(n) => println!("Single pass complete: {} rehearsal(s) run.", n),
+                    Err(e) => {
+                        eprintln!("Daemon error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+            DaemonCommands::Run { break_stuck, .. } => {
+                if let Err(e) = daemon::run_daemon(break_stuck).await {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::Watch { stack, compose_file, schedule, timezone, catch_up, providers, notify, auto_promote_after, jitter, group } => {
+                // Validate every provider name exists before registering the watch
+                for pname in &providers {
+                    if provider::load_provider(pname).is_none() {
+                        eprintln!(
+                            "Provider '{}' not found. Register it first with: rehearsa provider add {}",
+                            pname, pname
+                        );
+                        exit(1);
+                    }
+                }
                 // Validate the notify channel exists before registering the watch
                 if let Some(ref nchan) = notify {
                     if notify::resolve_channel(Some(nchan)).is_none() {
@@ -826,9 +2017,13 @@ async fn main() {
                     &stack,
                     &compose_file,
                     schedule.as_deref(),
+                    timezone.as_deref(),
                     catch_up,
-                    provider.as_deref(),
+                    &providers,
                     notify.as_deref(),
+                    auto_promote_after,
+                    jitter,
+                    group.as_deref(),
                 ) {
                     eprintln!("Daemon error: {}", e);
                     exit(1);
@@ -840,8 +2035,8 @@ async fn main() {
                     exit(1);
                 }
             }
-            DaemonCommands::List => {
-                if let Err(e) = daemon::list_watches() {
+            DaemonCommands::List { json } => {
+                if let Err(e) = daemon::list_watches(json || cli.json) {
                     eprintln!("Daemon error: {}", e);
                     exit(1);
                 }
@@ -852,6 +2047,84 @@ async fn main() {
                     exit(1);
                 }
             }
+            DaemonCommands::SetStabilityWeighting { weighting } => {
+                let result = weighting
+                    .parse::<history::StabilityWeighting>()
+                    .and_then(daemon::set_stability_weighting);
+                if let Err(e) = result {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetLockDir { dir } => {
+                if let Err(e) = daemon::set_lock_dir(&dir) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetStateDir { dir } => {
+                if let Err(e) = daemon::set_state_dir(&dir) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetLockTimeout { minutes } => {
+                if let Err(e) = daemon::set_lock_timeout(minutes) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetRunningProbe { seconds } => {
+                if let Err(e) = daemon::set_running_probe(seconds) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetNotifyDedup { minutes } => {
+                if let Err(e) = daemon::set_notify_dedup(minutes) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetMinCoverage { pct } => {
+                if let Err(e) = daemon::set_min_coverage(pct) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetGroup { name, limit } => {
+                if let Err(e) = daemon::set_concurrency_group(&name, limit) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetLogFormat { format } => {
+                let result = format
+                    .parse::<daemon::LogFormat>()
+                    .and_then(daemon::set_log_format);
+                if let Err(e) = result {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetMetricsAddr { addr } => {
+                if let Err(e) = daemon::set_metrics_addr(&addr) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetScheduleJitter { seconds } => {
+                if let Err(e) = daemon::set_schedule_jitter(seconds) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
+            DaemonCommands::SetWatchDebounce { seconds } => {
+                if let Err(e) = daemon::set_watch_debounce(seconds) {
+                    eprintln!("Daemon error: {}", e);
+                    exit(1);
+                }
+            }
             DaemonCommands::Config => {
                 if let Err(e) = daemon::show_config() {
                     eprintln!("Daemon error: {}", e);
@@ -865,8 +2138,28 @@ async fn main() {
         // ==================================================
 
         Commands::Notify { command } => match command {
-            NotifyCommands::Add { name, url, secret } => {
-                if let Err(e) = notify::add_channel(&name, &url, secret.as_deref()) {
+            NotifyCommands::Add { name, url, format, secret } => {
+                let fmt = match format.parse::<notify::WebhookFormat>() {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("Notify error: {}", e);
+                        exit(1);
+                    }
+                };
+                if let Err(e) = notify::add_channel(&name, &url, fmt, secret.as_deref()) {
+                    eprintln!("Notify error: {}", e);
+                    exit(1);
+                }
+            }
+            NotifyCommands::AddWebhook { name, url, format, secret } => {
+                let fmt = match format.parse::<notify::WebhookFormat>() {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("Notify error: {}", e);
+                        exit(1);
+                    }
+                };
+                if let Err(e) = notify::add_webhook_target(&name, &url, fmt, secret.as_deref()) {
                     eprintln!("Notify error: {}", e);
                     exit(1);
                 }
@@ -883,7 +2176,19 @@ async fn main() {
                     exit(1);
                 }
             }
-            NotifyCommands::Delete { name } => {
+            NotifyCommands::Delete { name, yes, dry_run } => {
+                if notify::load_channel(&name).is_none() {
+                    eprintln!("Notify error: No notify channel found with name '{}'.", name);
+                    exit(1);
+                }
+                if dry_run {
+                    println!("Would delete notify channel '{}'.", name);
+                    return;
+                }
+                if !confirm(&format!("Delete notify channel '{}'?", name), yes) {
+                    println!("Aborted.");
+                    exit(1);
+                }
                 if let Err(e) = notify::delete_channel(&name) {
                     eprintln!("Notify error: {}", e);
                     exit(1);
@@ -895,8 +2200,34 @@ async fn main() {
                     exit(1);
                 }
             }
-            NotifyCommands::Test { name } => {
-                if let Err(e) = notify::test_channel(&name) {
+            NotifyCommands::SetThreshold { name, min_severity, clear: _ } => {
+                let min = match min_severity.map(|s| s.parse::<notify::Severity>()).transpose() {
+                    Ok(m) => m,
+                    Err(e) => {
+                        eprintln!("Notify error: {}", e);
+                        exit(1);
+                    }
+                };
+                if let Err(e) = notify::set_threshold(&name, min) {
+                    eprintln!("Notify error: {}", e);
+                    exit(1);
+                }
+            }
+            NotifyCommands::Route { event, channel, clear: _ } => {
+                let event = match event.parse::<notify::NotifyEvent>() {
+                    Ok(e) => e,
+                    Err(e) => {
+                        eprintln!("Notify error: {}", e);
+                        exit(1);
+                    }
+                };
+                if let Err(e) = notify::set_route(event, channel.as_deref()) {
+                    eprintln!("Notify error: {}", e);
+                    exit(1);
+                }
+            }
+            NotifyCommands::Test { name, force } => {
+                if let Err(e) = notify::test_channel(&name, force) {
                     eprintln!("Notify error: {}", e);
                     exit(1);
                 }
@@ -968,7 +2299,19 @@ async fn main() {
                     exit(1);
                 }
             }
-            ProviderCommands::Delete { name } => {
+            ProviderCommands::Delete { name, yes, dry_run } => {
+                if provider::load_provider(&name).is_none() {
+                    eprintln!("Provider error: No provider found with name '{}'.", name);
+                    exit(1);
+                }
+                if dry_run {
+                    println!("Would delete provider '{}'.", name);
+                    return;
+                }
+                if !confirm(&format!("Delete provider '{}'?", name), yes) {
+                    println!("Aborted.");
+                    exit(1);
+                }
                 if let Err(e) = provider::delete_provider(&name) {
                     eprintln!("Provider error: {}", e);
                     exit(1);
@@ -980,8 +2323,8 @@ async fn main() {
                     exit(1);
                 }
             }
-            ProviderCommands::VerifySet { name, max_age_hours, test_restore } => {
-                if let Err(e) = provider::set_provider_verify(&name, max_age_hours, test_restore) {
+            ProviderCommands::VerifySet { name, max_age_hours, test_restore, restore_timeout } => {
+                if let Err(e) = provider::set_provider_verify(&name, max_age_hours, test_restore, restore_timeout) {
                     eprintln!("Provider error: {}", e);
                     exit(1);
                 }
@@ -992,8 +2335,23 @@ async fn main() {
         // STATUS
         // ==================================================
 
-        Commands::Status => {
-            if let Err(e) = history::status_all() {
+        Commands::Status { summary: true } => {
+            let result = coverage::build_coverage().and_then(|summary| {
+                if output_format.is_structured() {
+                    coverage::print_coverage_rollup_structured(&summary, output_format)
+                } else {
+                    coverage::print_coverage_rollup(&summary);
+                    Ok(())
+                }
+            });
+            if let Err(e) = result {
+                eprintln!("Status error: {}", e);
+                exit(1);
+            }
+        }
+
+        Commands::Status { summary: false } => {
+            if let Err(e) = history::status_all(output_format) {
                 eprintln!("Status error: {}", e);
                 exit(1);
             }
@@ -1003,17 +2361,156 @@ async fn main() {
         // COVERAGE
         // ==================================================
 
-        Commands::Coverage { json } => {
+        // ==================================================
+        // SCORING
+        // ==================================================
+
+        Commands::Scoring { command } => match command {
+            ScoringCommands::Show { stack } => {
+                let services: Vec<String> = history::load_latest(&stack)
+                    .map(|r| r.services.into_keys().collect())
+                    .unwrap_or_default();
+                scoring::show_scoring(&stack, &services);
+            }
+
+            ScoringCommands::Set { stack, service, weight, healthy, running, unhealthy, failed, start_retries } => {
+                let bands = scoring::ScoringConfig {
+                    weights: Default::default(),
+                    healthy,
+                    running,
+                    unhealthy,
+                    failed,
+                    start_retries,
+                };
+                let service = service.as_deref().zip(weight);
+
+                if service.is_none() && healthy.is_none() && running.is_none()
+                    && unhealthy.is_none() && failed.is_none() && start_retries.is_none()
+                {
+                    eprintln!("Scoring error: nothing to set — give --service/--weight, a band value or --start-retries.");
+                    exit(1);
+                }
+
+                if let Err(e) = scoring::set_scoring(&stack, service, &bands) {
+                    eprintln!("Scoring error: {}", e);
+                    exit(1);
+                }
+
+                println!("Scoring updated for stack '{}'", stack);
+            }
+        },
+
+        // ==================================================
+        // PREFLIGHT
+        // ==================================================
+
+        Commands::Preflight { command } => match command {
+            PreflightCommands::SetCap { stack, rule, max } => {
+                if let Err(e) = engine::preflight::set_penalty_cap(&stack, &rule, max) {
+                    eprintln!("Preflight error: {}", e);
+                    exit(1);
+                }
+                match max {
+                    Some(m) => println!("{} penalty capped at {} for stack '{}'", rule, m, stack),
+                    None    => println!("{} penalty cap removed for stack '{}'", rule, stack),
+                }
+            }
+
+            PreflightCommands::Show { stack } => {
+                let config = engine::preflight::load_preflight_config(&stack);
+                println!("Preflight penalty caps for '{}':", stack);
+                for rule in engine::preflight::rule_names() {
+                    match config.penalty_caps.get(rule) {
+                        Some(cap) => println!("  {:<24} {}", rule, cap),
+                        None      => println!("  {:<24} uncapped", rule),
+                    }
+                }
+                println!();
+                if config.suppressed.is_empty() {
+                    println!("No suppressed findings.");
+                } else {
+                    println!("Suppressed findings:");
+                    for id in &config.suppressed {
+                        println!("  {}", id);
+                    }
+                }
+            }
+
+            PreflightCommands::Suppress { stack, finding } => {
+                match engine::preflight::set_suppressed(&stack, &finding, true) {
+                    Ok(true)  => println!("Finding {} suppressed for stack '{}'", finding, stack),
+                    Ok(false) => println!("Finding {} is already suppressed for stack '{}'", finding, stack),
+                    Err(e) => {
+                        eprintln!("Preflight error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+
+            PreflightCommands::Unsuppress { stack, finding } => {
+                match engine::preflight::set_suppressed(&stack, &finding, false) {
+                    Ok(true)  => println!("Finding {} no longer suppressed for stack '{}'", finding, stack),
+                    Ok(false) => println!("Finding {} was not suppressed for stack '{}'", finding, stack),
+                    Err(e) => {
+                        eprintln!("Preflight error: {}", e);
+                        exit(1);
+                    }
+                }
+            }
+        },
+
+        Commands::Coverage { command: Some(CoverageCommands::Trend { last, json }), .. } => {
+            match coverage::coverage_trend(last) {
+                Ok(trend) if json || cli.json => match serde_json::to_string_pretty(&trend) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("JSON error: {}", e);
+                        exit(1);
+                    }
+                },
+                Ok(trend) => coverage::print_coverage_trend(&trend),
+                Err(e) => {
+                    eprintln!("Coverage error: {}", e);
+                    exit(1);
+                }
+            }
+        }
+
+        Commands::Coverage { command: Some(CoverageCommands::Badge { output, label }), .. } => {
+            let result = coverage::build_coverage()
+                .and_then(|summary| coverage::write_badge(&summary, &label, &output));
+            match result {
+                Ok(()) => println!("Badge written: {}", output),
+                Err(e) => {
+                    eprintln!("Coverage error: {}", e);
+                    exit(1);
+                }
+            }
+        }
+
+        Commands::Coverage { command: None, json, csv, min_coverage, fail_on_drift } => {
             match coverage::build_coverage() {
-                Ok(summary) => {
-                    if json {
-                        if let Err(e) = coverage::print_coverage_json(&summary) {
+                Ok(mut summary) => {
+                    if let Err(e) = coverage::record_snapshot(&summary) {
+                        eprintln!("Warning: coverage history not recorded: {}", e);
+                    }
+                    summary.apply_gate(
+                        min_coverage.unwrap_or(summary.min_coverage_pct),
+                        fail_on_drift,
+                    );
+                    let format = if json { OutputFormat::Json } else { output_format };
+                    if format.is_structured() {
+                        if let Err(e) = coverage::print_coverage_structured(&summary, format) {
                             eprintln!("Coverage error: {}", e);
                             exit(1);
                         }
                     } else {
-                        coverage::print_coverage(&summary);
-                        if summary.coverage_pct < 100 {
+                        if csv {
+                            coverage::print_coverage_csv(&summary);
+                        } else {
+                            coverage::print_coverage(&summary);
+                        }
+                        if !summary.gate_passed {
                             exit(1);
                         }
                     }
@@ -1029,10 +2526,49 @@ async fn main() {
         // REPORT
         // ==================================================
 
-        Commands::Report { stack, format, output, provider, window } => {
+        Commands::Report { command: Some(ReportCommands::Verify { file }), .. } => {
+            match report::verify_report_file(&file) {
+                Ok(true) => {}
+                Ok(false) => exit(1),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    exit(1);
+                }
+            }
+        }
+
+        Commands::Report { command: Some(ReportCommands::Compare { stack, from, to, json }), .. } => {
+            match report::compare_reports(&stack, &from, &to) {
+                Ok(diff) if json || cli.json => match serde_json::to_string_pretty(&diff) {
+                    Ok(out) => println!("{}", out),
+                    Err(e) => {
+                        eprintln!("JSON error: {}", e);
+                        exit(1);
+                    }
+                },
+                Ok(diff) => report::print_report_diff(&diff),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    exit(1);
+                }
+            }
+        }
+
+        Commands::Report { command: None, stack, format, output, provider, window, fail_on, sign } => {
+            let threshold = match fail_on.as_deref().map(str::parse::<report::ReportVerdict>) {
+                Some(Ok(v))  => Some(v),
+                Some(Err(e)) => {
+                    eprintln!("Error: {}", e);
+                    exit(1);
+                }
+                None => None,
+            };
+
             let fmt = match format.as_str() {
                 "json" => report::ReportFormat::Json,
                 "pdf"  => report::ReportFormat::Pdf,
+                "html" => report::ReportFormat::Html,
+                "md" | "markdown" => report::ReportFormat::Markdown,
                 _      => report::ReportFormat::Both,
             };
 
@@ -1042,11 +2578,19 @@ async fn main() {
                 output,
                 provider,
                 window,
+                sign,
             };
 
-            if let Err(e) = report::run_report(&args) {
-                eprintln!("Error: {}", e);
-                exit(1);
+            match report::run_report(&args) {
+                Ok(verdict) => {
+                    if threshold.is_some_and(|t| verdict >= t) {
+                        exit(1);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    exit(1);
+                }
             }
         }
 
@@ -1054,15 +2598,38 @@ async fn main() {
         // CLEANUP
         // ==================================================
 
-        Commands::Cleanup => {
+        Commands::Cleanup { older_than, dry_run } => {
             println!("Scanning for orphaned rehearsal resources...");
             println!();
-            if let Err(e) = cleanup_orphans().await {
+            if let Err(e) = cleanup_orphans(older_than, dry_run).await {
                 eprintln!("Cleanup error: {}", e);
                 exit(1);
             }
         }
 
+        // ==================================================
+        // LOCK
+        // ==================================================
+
+        Commands::Lock { command } => match command {
+            LockCommands::List => lock::show_locks(),
+            LockCommands::Clear { stack, all, force } => {
+                let result = match stack {
+                    Some(s) => lock::clear_lock(&s, force),
+                    None if all => lock::clear_all_locks(force).map(|n| {
+                        if n == 0 {
+                            println!("No stale locks to clear.");
+                        }
+                    }),
+                    None => unreachable!("clap requires a stack or --all"),
+                };
+                if let Err(e) = result {
+                    eprintln!("Lock error: {}", e);
+                    exit(1);
+                }
+            }
+        },
+
         // ==================================================
         // VERSION
         // ==================================================
@@ -1070,5 +2637,133 @@ async fn main() {
         Commands::Version => {
             println!("rehearsa {}", env!("CARGO_PKG_VERSION"));
         }
+
+        // ==================================================
+        // CONFIG
+        // ==================================================
+
+        Commands::Config { command } => match command {
+            ConfigCommands::Init { force } => {
+                if let Err(e) = config::init_config(force) {
+                    eprintln!("Config error: {}", e);
+                    exit(1);
+                }
+            }
+        },
+
+        // ==================================================
+        // COMPLETIONS
+        // ==================================================
+
+        Commands::ExitCodes => {
+            if let Err(e) = exitcode::print_exit_codes(output_format) {
+                eprintln!("Error: {}", e);
+                exit(1);
+            }
+        }
+
+        Commands::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "rehearsa", &mut std::io::stdout());
+        }
+    }
+}
+
+// ======================================================
+// HELPERS
+// ======================================================
+
+/// Fill flags not given on the command line or via the environment from
+/// ~/.rehearsa/config.toml.
+fn apply_cli_defaults(cli: &mut Cli, matches: &ArgMatches, defaults: config::CliDefaults) {
+    let unset = |id: &str| !matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    );
+
+    // --json and --format are one setting: either flag overrides both keys.
+    if unset("json") && unset("format") {
+        cli.json = defaults.json.unwrap_or(cli.json);
+        if let Some(v) = defaults.format {
+            cli.format = v;
+        }
+    }
+    if let Some(v) = defaults.ci.filter(|_| unset("ci")) {
+        cli.ci = v;
+    }
+    if let Some(v) = defaults.timeout.filter(|_| unset("timeout")) {
+        cli.timeout = v;
+    }
+    if let Some(v) = defaults.profiles.filter(|_| unset("profiles")) {
+        cli.profiles = v;
+    }
+    if let Some(v) = defaults.explain_policy.filter(|_| unset("explain_policy")) {
+        cli.explain_policy = v;
+    }
+    if let Some(v) = defaults.strict_integrity.filter(|_| unset("strict_integrity")) {
+        cli.strict_integrity = v;
+    }
+    if let Some(v) = defaults.pull.filter(|_| unset("pull")) {
+        cli.pull = v;
+    }
+    if let Some(v) = defaults.image_archive.filter(|_| unset("image_archive")) {
+        cli.image_archive = Some(v);
+    }
+    if let Some(v) = defaults.docker_host.filter(|_| unset("docker_host")) {
+        cli.docker_host = Some(v);
+    }
+    // --quiet and --verbose are one setting: either flag overrides both keys.
+    if unset("quiet") && unset("verbose") {
+        cli.quiet = defaults.quiet.unwrap_or(cli.quiet);
+        cli.verbose = defaults.verbose.unwrap_or(cli.verbose) && !cli.quiet;
+    }
+}
+
+/// A fatal `stack test` error as a JSON or YAML document.
+fn print_fatal_structured(stack: &str, error: &str, format: OutputFormat) {
+    let value = serde_json::json!({ "stack": stack, "fatal_error": error });
+    match output::render(&value, format) {
+        Ok(out) => println!("{}", out),
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// Ask the operator to confirm a destructive action.
+/// Returns true without prompting when `yes` is set or stdin is not a
+/// terminal, so scripted and CI usage is unaffected.
+fn confirm(prompt: &str, yes: bool) -> bool {
+    use std::io::{IsTerminal, Write};
+
+    if yes || !std::io::stdin().is_terminal() {
+        return true;
     }
+
+    print!("{} [y/N] ", prompt);
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+/// Pair up repeated `--service` / `--min-score` flags into score floors.
+fn service_floors(
+    services: Vec<String>,
+    scores: Vec<u32>,
+) -> Result<Option<std::collections::HashMap<String, u32>>, String> {
+    if services.len() != scores.len() {
+        return Err("each --service needs exactly one matching --min-score".to_string());
+    }
+
+    if let Some(bad) = scores.iter().find(|&&s| s > 100) {
+        return Err(format!("--min-score must be 0-100 (got {})", bad));
+    }
+
+    if services.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(services.into_iter().zip(scores).collect()))
 }
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io;
 use std::path::Path;
@@ -10,6 +10,7 @@ use std::path::Path;
 
 const NOTIFY_PATH: &str = "/etc/rehearsa/notify.json";
 const NOTIFY_DEFAULT_KEY: &str = "__default__";
+const DEDUP_STATE_FILE: &str = "notify_state.json";
 
 // ======================================================
 // TYPES
@@ -24,6 +25,41 @@ pub enum Severity {
     Recovery,
 }
 
+impl Severity {
+    /// Ordering used by channel thresholds: Critical > Warning > Recovery.
+    fn rank(&self) -> u8 {
+        match self {
+            Severity::Critical => 2,
+            Severity::Warning  => 1,
+            Severity::Recovery => 0,
+        }
+    }
+
+    /// Whether an event of this severity passes a channel's `min_severity`.
+    /// Recovery always passes so the all-clear reaches everyone who was paged.
+    pub fn passes(&self, min: Option<&Severity>) -> bool {
+        match min {
+            Some(min) => *self == Severity::Recovery || self.rank() >= min.rank(),
+            None      => true,
+        }
+    }
+}
+
+impl std::str::FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "critical" => Ok(Severity::Critical),
+            "warning"  => Ok(Severity::Warning),
+            "recovery" => Ok(Severity::Recovery),
+            other => Err(format!(
+                "Unknown severity '{}'. Supported: critical, warning, recovery", other
+            )),
+        }
+    }
+}
+
 impl std::fmt::Display for Severity {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -35,7 +71,7 @@ impl std::fmt::Display for Severity {
 }
 
 /// The event that triggered the notification.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NotifyEvent {
     RehearsalFatalError,
@@ -43,9 +79,40 @@ pub enum NotifyEvent {
     PolicyViolation,
     BaselineDrift,
     RehearsalRecovered,
+    RehearsalStuck,
+    BaselineAutoPromoted,
+    DaemonStarted,
+    DaemonStopped,
 }
 
 impl NotifyEvent {
+    pub const ALL: [NotifyEvent; 9] = [
+        NotifyEvent::RehearsalFatalError,
+        NotifyEvent::ProviderVerificationFailed,
+        NotifyEvent::PolicyViolation,
+        NotifyEvent::BaselineDrift,
+        NotifyEvent::RehearsalRecovered,
+        NotifyEvent::RehearsalStuck,
+        NotifyEvent::BaselineAutoPromoted,
+        NotifyEvent::DaemonStarted,
+        NotifyEvent::DaemonStopped,
+    ];
+
+    /// The snake_case name used on the command line and in notify.json.
+    pub fn key(&self) -> &'static str {
+        match self {
+            NotifyEvent::RehearsalFatalError         => "rehearsal_fatal_error",
+            NotifyEvent::ProviderVerificationFailed  => "provider_verification_failed",
+            NotifyEvent::PolicyViolation             => "policy_violation",
+            NotifyEvent::BaselineDrift               => "baseline_drift",
+            NotifyEvent::RehearsalRecovered          => "rehearsal_recovered",
+            NotifyEvent::RehearsalStuck              => "rehearsal_stuck",
+            NotifyEvent::BaselineAutoPromoted        => "baseline_auto_promoted",
+            NotifyEvent::DaemonStarted               => "daemon_started",
+            NotifyEvent::DaemonStopped               => "daemon_stopped",
+        }
+    }
+
     pub fn severity(&self) -> Severity {
         match self {
             NotifyEvent::RehearsalFatalError         => Severity::Critical,
@@ -53,6 +120,10 @@ impl NotifyEvent {
             NotifyEvent::PolicyViolation             => Severity::Warning,
             NotifyEvent::BaselineDrift               => Severity::Warning,
             NotifyEvent::RehearsalRecovered          => Severity::Recovery,
+            NotifyEvent::RehearsalStuck              => Severity::Warning,
+            NotifyEvent::BaselineAutoPromoted        => Severity::Recovery,
+            NotifyEvent::DaemonStarted               => Severity::Recovery,
+            NotifyEvent::DaemonStopped               => Severity::Warning,
         }
     }
 
@@ -63,10 +134,31 @@ impl NotifyEvent {
             NotifyEvent::PolicyViolation             => "Policy Violation",
             NotifyEvent::BaselineDrift               => "Baseline Drift Detected",
             NotifyEvent::RehearsalRecovered          => "Rehearsal Recovered",
+            NotifyEvent::RehearsalStuck              => "Rehearsal Stuck",
+            NotifyEvent::BaselineAutoPromoted        => "Baseline Auto-Promoted",
+            NotifyEvent::DaemonStarted               => "Daemon Started",
+            NotifyEvent::DaemonStopped               => "Daemon Stopped",
         }
     }
 }
 
+impl std::str::FromStr for NotifyEvent {
+    type Err = String;
+
+    /// Accepts the snake_case key or its kebab-case spelling.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.replace('-', "_");
+        NotifyEvent::ALL
+            .into_iter()
+            .find(|e| e.key() == wanted)
+            .ok_or_else(|| format!(
+                "Unknown event '{}'. Supported: {}",
+                s,
+                NotifyEvent::ALL.iter().map(|e| e.key()).collect::<Vec<_>>().join(", "),
+            ))
+    }
+}
+
 // ======================================================
 // EMAIL TYPES
 // ======================================================
@@ -77,7 +169,7 @@ impl NotifyEvent {
 pub enum EmailProvider {
     /// Direct SMTP delivery via lettre.
     Smtp,
-    /// Sendgrid HTTP API (scaffolded — not yet implemented).
+    /// Sendgrid v3 HTTP API.
     Sendgrid,
 }
 
@@ -148,6 +240,106 @@ pub struct EmailConfig {
 
 fn default_true() -> bool { true }
 
+// ======================================================
+// WEBHOOK TYPES
+// ======================================================
+
+/// Body format posted to a webhook target.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookFormat {
+    /// Rehearsa's own JSON payload (WebhookPayload) for custom consumers.
+    #[default]
+    Json,
+    /// Slack incoming-webhook message with a severity-coloured attachment.
+    Slack,
+    /// Discord webhook message with a severity-coloured embed.
+    Discord,
+}
+
+impl std::fmt::Display for WebhookFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WebhookFormat::Json    => write!(f, "json"),
+            WebhookFormat::Slack   => write!(f, "slack"),
+            WebhookFormat::Discord => write!(f, "discord"),
+        }
+    }
+}
+
+impl std::str::FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" | "generic" => Ok(WebhookFormat::Json),
+            "slack"            => Ok(WebhookFormat::Slack),
+            "discord"          => Ok(WebhookFormat::Discord),
+            other => Err(format!(
+                "Unknown webhook format '{}'. Supported: json, slack, discord", other
+            )),
+        }
+    }
+}
+
+/// A single webhook endpoint on a channel.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookTarget {
+    pub url: String,
+
+    #[serde(default)]
+    pub format: WebhookFormat,
+
+    /// Secret sent as X-Rehearsa-Secret. Falls back to the channel secret if absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+impl WebhookTarget {
+    pub fn new(url: &str) -> Self {
+        WebhookTarget {
+            url:    url.to_owned(),
+            format: WebhookFormat::default(),
+            secret: None,
+        }
+    }
+}
+
+/// Accept the pre-fan-out `"url": "<string>"` form as well as a list whose
+/// entries are either bare URL strings or full target objects.
+fn deserialize_webhooks<'de, D>(deserializer: D) -> Result<Vec<WebhookTarget>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Url(String),
+        Target(WebhookTarget),
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<Entry>),
+    }
+
+    let targets = match Option::<OneOrMany>::deserialize(deserializer)? {
+        None                           => vec![],
+        Some(OneOrMany::One(url))      => vec![WebhookTarget::new(&url)],
+        Some(OneOrMany::Many(entries)) => entries
+            .into_iter()
+            .map(|e| match e {
+                Entry::Url(url)  => WebhookTarget::new(&url),
+                Entry::Target(t) => t,
+            })
+            .collect(),
+    };
+
+    Ok(targets)
+}
+
 // ======================================================
 // CHANNEL TYPE
 // ======================================================
@@ -159,25 +351,42 @@ pub struct NotifyChannel {
     /// Unique name used to reference this channel.
     pub name: String,
 
-    /// Webhook URL. If present, a JSON POST is sent on every event.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub url: Option<String>,
-
-    /// Optional secret added as X-Rehearsa-Secret header.
+    /// Webhook targets. Every event is delivered to each of them.
+    /// Channels written before fan-out support stored a single `url` string.
+    #[serde(
+        rename = "webhooks",
+        alias = "url",
+        default,
+        deserialize_with = "deserialize_webhooks",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub webhooks: Vec<WebhookTarget>,
+
+    /// Default secret added as X-Rehearsa-Secret header for targets without their own.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub secret: Option<String>,
 
     /// Email delivery config. If present, an email is sent on every event.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub email: Option<EmailConfig>,
+
+    /// Events below this severity are dropped for this channel.
+    /// Recovery events are always delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_severity: Option<Severity>,
 }
 
 impl NotifyChannel {
     /// Human-readable transport summary for display.
     pub fn transport_label(&self) -> String {
-        match (&self.url, &self.email) {
-            (Some(_), Some(e)) => format!("webhook + email ({})", e.provider),
-            (Some(_), None)    => "webhook".to_string(),
+        let webhook = match self.webhooks.len() {
+            0 => None,
+            1 => Some("webhook".to_string()),
+            n => Some(format!("{} webhooks", n)),
+        };
+        match (webhook, &self.email) {
+            (Some(w), Some(e)) => format!("{} + email ({})", w, e.provider),
+            (Some(w), None)    => w,
             (None, Some(e))    => format!("email ({})", e.provider),
             (None, None)       => "none".to_string(),
         }
@@ -206,6 +415,11 @@ struct NotifyRegistry {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     default_channel: Option<String>,
+
+    /// Per-event channel overrides. Take precedence over the per-stack
+    /// and default channels.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    routes: BTreeMap<NotifyEvent, String>,
 }
 
 fn load_registry() -> io::Result<NotifyRegistry> {
@@ -237,23 +451,76 @@ fn save_registry(registry: &NotifyRegistry) -> io::Result<()> {
 // PUBLIC API — CHANNEL MANAGEMENT
 // ======================================================
 
-/// Add or update a webhook-only channel. For email channels use add_email_channel.
-pub fn add_channel(name: &str, url: &str, secret: Option<&str>) -> io::Result<()> {
+/// Add or update a webhook channel, replacing its webhook targets with `urls`,
+/// all posted in `format`. For email channels use add_email_channel.
+pub fn add_channel(
+    name:   &str,
+    urls:   &[String],
+    format: WebhookFormat,
+    secret: Option<&str>,
+) -> io::Result<()> {
+    if urls.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "At least one webhook URL (--url) is required.",
+        ));
+    }
+
     let mut registry = load_registry()?;
-    // Preserve existing email config if the channel already exists
-    let existing_email = registry.channels.get(name).and_then(|c| c.email.clone());
+    // Preserve existing email config and threshold if the channel already exists
+    let existing = registry.channels.get(name).cloned();
     registry.channels.insert(name.to_owned(), NotifyChannel {
-        name:   name.to_owned(),
-        url:    Some(url.to_owned()),
+        name:     name.to_owned(),
+        webhooks: urls
+            .iter()
+            .map(|u| WebhookTarget { format: format.clone(), ..WebhookTarget::new(u) })
+            .collect(),
+        secret:   secret.map(str::to_owned),
+        email:    existing.as_ref().and_then(|c| c.email.clone()),
+        min_severity: existing.and_then(|c| c.min_severity),
+    });
+    save_registry(&registry)?;
+    println!(
+        "Notify channel '{}' registered ({} webhook{}).",
+        name,
+        urls.len(),
+        if urls.len() == 1 { "" } else { "s" },
+    );
+    Ok(())
+}
+
+/// Append a webhook target to a channel, creating the channel if needed.
+/// Replaces any existing target with the same URL.
+pub fn add_webhook_target(
+    name:   &str,
+    url:    &str,
+    format: WebhookFormat,
+    secret: Option<&str>,
+) -> io::Result<()> {
+    let mut registry = load_registry()?;
+    let channel = registry.channels.entry(name.to_owned()).or_insert_with(|| NotifyChannel {
+        name:     name.to_owned(),
+        webhooks: vec![],
+        secret:   None,
+        email:    None,
+        min_severity: None,
+    });
+
+    channel.webhooks.retain(|t| t.url != url);
+    channel.webhooks.push(WebhookTarget {
+        url:    url.to_owned(),
+        format,
         secret: secret.map(str::to_owned),
-        email:  existing_email,
     });
+    let count = channel.webhooks.len();
+
     save_registry(&registry)?;
-    println!("Notify channel '{}' registered (webhook).", name);
+    println!("Webhook added to notify channel '{}' ({} total).", name, count);
     Ok(())
 }
 
 /// Add or update the email transport on a channel.
+#[allow(clippy::too_many_arguments)]
 pub fn add_email_channel(
     name:              &str,
     provider:          EmailProvider,
@@ -323,10 +590,11 @@ pub fn add_email_channel(
     let mut registry = load_registry()?;
     let existing = registry.channels.get(name).cloned();
     let channel = NotifyChannel {
-        name:   name.to_owned(),
-        url:    existing.as_ref().and_then(|c| c.url.clone()),
-        secret: existing.as_ref().and_then(|c| c.secret.clone()),
-        email:  Some(email_config),
+        name:     name.to_owned(),
+        webhooks: existing.as_ref().map(|c| c.webhooks.clone()).unwrap_or_default(),
+        secret:   existing.as_ref().and_then(|c| c.secret.clone()),
+        email:    Some(email_config),
+        min_severity: existing.and_then(|c| c.min_severity),
     };
     registry.channels.insert(name.to_owned(), channel);
     save_registry(&registry)?;
@@ -342,12 +610,27 @@ pub fn show_channel(name: &str) -> io::Result<()> {
             println!("Channel   : {}{}", c.name, if is_default { "  [default]" } else { "" });
             println!("{}", "─".repeat(50));
             println!("Transport : {}", c.transport_label());
+            println!(
+                "Threshold : {}",
+                c.min_severity
+                    .as_ref()
+                    .map(|s| format!("{} and above (recovery always sent)", s))
+                    .unwrap_or_else(|| "all events".to_string()),
+            );
             println!();
 
-            if let Some(ref url) = c.url {
-                println!("Webhook");
-                println!("  URL    : {}", url);
-                println!("  Secret : {}", c.secret.as_deref().map(|_| "set").unwrap_or("not set"));
+            for (i, target) in c.webhooks.iter().enumerate() {
+                let secret = if target.secret.is_some() {
+                    "set"
+                } else if c.secret.is_some() {
+                    "set (channel default)"
+                } else {
+                    "not set"
+                };
+                println!("Webhook {}", i + 1);
+                println!("  URL    : {}", target.url);
+                println!("  Format : {}", target.format);
+                println!("  Secret : {}", secret);
                 println!();
             }
 
@@ -375,7 +658,6 @@ pub fn show_channel(name: &str) -> io::Result<()> {
                             _            => "not set",
                         };
                         println!("  API Key: {}", key);
-                        println!("  Note   : Sendgrid delivery is scaffolded — not yet active.");
                     }
                 }
             }
@@ -399,17 +681,18 @@ pub fn list_channels() -> io::Result<()> {
         return Ok(());
     }
 
-    println!("{:<20} {:<6} {:<24} {}", "Name", "Default", "Transport", "Destination");
+    println!("{:<20} {:<6} {:<24} Destination", "Name", "Default", "Transport");
     println!("{}", "─".repeat(80));
     let mut channels: Vec<&NotifyChannel> = registry.channels.values().collect();
     channels.sort_by(|a, b| a.name.cmp(&b.name));
     for c in channels {
         let is_default = registry.default_channel.as_deref() == Some(&c.name);
-        let dest = match (&c.url, &c.email) {
-            (Some(u), Some(e)) => format!("{}  +  {}", u, e.to.join(", ")),
-            (Some(u), None)    => u.clone(),
-            (None, Some(e))    => e.to.join(", "),
-            (None, None)       => "—".to_string(),
+        let urls: Vec<&str> = c.webhooks.iter().map(|t| t.url.as_str()).collect();
+        let dest = match (urls.is_empty(), &c.email) {
+            (false, Some(e)) => format!("{}  +  {}", urls.join(", "), e.to.join(", ")),
+            (false, None)    => urls.join(", "),
+            (true, Some(e))  => e.to.join(", "),
+            (true, None)     => "—".to_string(),
         };
         println!(
             "{:<20} {:<6} {:<24} {}",
@@ -419,6 +702,14 @@ pub fn list_channels() -> io::Result<()> {
             dest,
         );
     }
+
+    if !registry.routes.is_empty() {
+        println!();
+        println!("Event routes");
+        for (event, channel) in &registry.routes {
+            println!("  {:<30} → {}", event.key(), channel);
+        }
+    }
     Ok(())
 }
 
@@ -434,6 +725,15 @@ pub fn delete_channel(name: &str) -> io::Result<()> {
         registry.default_channel = None;
         println!("Note: default channel cleared (was '{}')", name);
     }
+    let orphaned: Vec<NotifyEvent> = registry.routes
+        .iter()
+        .filter(|(_, ch)| ch.as_str() == name)
+        .map(|(ev, _)| *ev)
+        .collect();
+    for event in orphaned {
+        registry.routes.remove(&event);
+        println!("Note: route for '{}' cleared (was '{}')", event.key(), name);
+    }
     save_registry(&registry)?;
     println!("Notify channel '{}' deleted.", name);
     Ok(())
@@ -453,7 +753,54 @@ pub fn set_default(name: &str) -> io::Result<()> {
     Ok(())
 }
 
-pub fn test_channel(name: &str) -> io::Result<()> {
+/// Set or clear (None) the minimum severity delivered on a channel.
+pub fn set_threshold(name: &str, min_severity: Option<Severity>) -> io::Result<()> {
+    let mut registry = load_registry()?;
+    let channel = registry.channels.get_mut(name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("No notify channel found with name '{}'.", name),
+        )
+    })?;
+
+    match &min_severity {
+        Some(s) => println!("Channel '{}' now receives {} and above (plus recovery).", name, s),
+        None    => println!("Channel '{}' now receives all events.", name),
+    }
+    channel.min_severity = min_severity;
+
+    save_registry(&registry)
+}
+
+/// Route an event to a channel, or clear its route when `channel` is None.
+pub fn set_route(event: NotifyEvent, channel: Option<&str>) -> io::Result<()> {
+    let mut registry = load_registry()?;
+
+    match channel {
+        Some(name) => {
+            if !registry.channels.contains_key(name) {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("No notify channel found with name '{}'. Register it first.", name),
+                ));
+            }
+            registry.routes.insert(event, name.to_owned());
+            save_registry(&registry)?;
+            println!("'{}' events now route to '{}'.", event.key(), name);
+        }
+        None => {
+            if registry.routes.remove(&event).is_none() {
+                println!("No route set for '{}'.", event.key());
+                return Ok(());
+            }
+            save_registry(&registry)?;
+            println!("Route for '{}' cleared — per-stack/default channel applies.", event.key());
+        }
+    }
+    Ok(())
+}
+
+pub fn test_channel(name: &str, force: bool) -> io::Result<()> {
     let registry = load_registry()?;
     let channel = match registry.channels.get(name) {
         Some(c) => c.clone(),
@@ -465,6 +812,17 @@ pub fn test_channel(name: &str) -> io::Result<()> {
         }
     };
 
+    // Test sends are deduplicated per channel, like any other event.
+    if !force && !dedup_allows(&format!("test:{}", name), "test_notification", false) {
+        println!(
+            "A test notification was sent to '{}' within the last {} minute(s) — skipped. \
+             Use --force to send anyway.",
+            name,
+            crate::daemon::resolve_config().notify_dedup_minutes.value,
+        );
+        return Ok(());
+    }
+
     println!("Sending test notification to '{}' ({})...", name, channel.transport_label());
 
     let payload = WebhookPayload {
@@ -477,11 +835,12 @@ pub fn test_channel(name: &str) -> io::Result<()> {
     };
 
     let mut any_error = false;
+    let mut delivered = false;
 
-    if channel.url.is_some() {
-        match send_webhook_sync(&channel, &payload) {
-            Ok(_)  => println!("  ✓ Webhook delivered."),
-            Err(e) => { eprintln!("  ✗ Webhook failed: {}", e); any_error = true; }
+    for target in &channel.webhooks {
+        match send_webhook_target(&channel, target, &payload) {
+            Ok(_)  => { println!("  ✓ Webhook delivered: {}", target.url); delivered = true; }
+            Err(e) => { eprintln!("  ✗ Webhook failed: {}: {}", target.url, e); any_error = true; }
         }
     }
 
@@ -489,11 +848,15 @@ pub fn test_channel(name: &str) -> io::Result<()> {
         let subject = "Rehearsa Test Notification";
         let body    = &payload.message;
         match send_email_sync(&channel, subject, body) {
-            Ok(_)  => println!("  ✓ Email delivered."),
+            Ok(_)  => { println!("  ✓ Email delivered."); delivered = true; }
             Err(e) => { eprintln!("  ✗ Email failed: {}", e); any_error = true; }
         }
     }
 
+    if delivered && !force {
+        dedup_record(&format!("test:{}", name), "test_notification");
+    }
+
     if any_error {
         std::process::exit(1);
     }
@@ -505,6 +868,11 @@ pub fn test_channel(name: &str) -> io::Result<()> {
 // PUBLIC LOADER (used by daemon)
 // ======================================================
 
+/// Load a single channel by name. Returns None if not found or registry missing.
+pub fn load_channel(name: &str) -> Option<NotifyChannel> {
+    load_registry().ok()?.channels.remove(name)
+}
+
 pub fn resolve_channel(per_stack: Option<&str>) -> Option<NotifyChannel> {
     let registry = load_registry().ok()?;
     let name = per_stack
@@ -512,6 +880,19 @@ pub fn resolve_channel(per_stack: Option<&str>) -> Option<NotifyChannel> {
     registry.channels.get(name).cloned()
 }
 
+/// Channel for a specific event: its route if one is set (and the channel
+/// still exists), otherwise the per-stack or default channel.
+fn resolve_event_channel(event: NotifyEvent, per_stack: Option<&str>) -> Option<NotifyChannel> {
+    let registry = load_registry().ok()?;
+    let name = registry.routes
+        .get(&event)
+        .filter(|name| registry.channels.contains_key(*name))
+        .map(String::as_str)
+        .or(per_stack)
+        .or(registry.default_channel.as_deref())?;
+    registry.channels.get(name).cloned()
+}
+
 // ======================================================
 // DELIVERY — PUBLIC ENTRY POINT
 // ======================================================
@@ -520,11 +901,23 @@ pub fn resolve_channel(per_stack: Option<&str>) -> Option<NotifyChannel> {
 /// resolved channel. Errors are logged but never propagated — a notification
 /// failure must never block or crash the daemon.
 pub fn notify(stack: &str, event: NotifyEvent, message: &str, per_stack_channel: Option<&str>) {
-    let channel = match resolve_channel(per_stack_channel) {
+    let channel = match resolve_event_channel(event, per_stack_channel) {
         Some(c) => c,
         None    => return,
     };
 
+    if !event.severity().passes(channel.min_severity.as_ref()) {
+        return;
+    }
+
+    if !dedup_allows(stack, event.key(), event.severity() == Severity::Recovery) {
+        println!(
+            "[{}] Notify: '{}' for '{}' already sent within the dedup window — suppressed",
+            chrono::Utc::now().to_rfc3339(), event.label(), stack
+        );
+        return;
+    }
+
     let payload = WebhookPayload {
         source:    "rehearsa",
         severity:  event.severity().to_string(),
@@ -540,74 +933,224 @@ pub fn notify(stack: &str, event: NotifyEvent, message: &str, per_stack_channel:
         stack, event.label(), payload.severity, message, payload.timestamp,
     );
 
+    let mut delivered = false;
+
     // Webhook transport
-    if channel.url.is_some() {
-        if let Err(e) = send_webhook_sync(&channel, &payload) {
-            eprintln!(
+    if !channel.webhooks.is_empty() {
+        match send_webhook_sync(&channel, &payload) {
+            Ok(()) => delivered = true,
+            Err(e) => eprintln!(
                 "[{}] Notify: webhook delivery failed for '{}' on '{}': {}",
                 chrono::Utc::now().to_rfc3339(), event.label(), stack, e
-            );
+            ),
         }
     }
 
     // Email transport
     if channel.email.is_some() {
-        if let Err(e) = send_email_sync(&channel, &subject, &body) {
-            eprintln!(
+        match send_email_sync(&channel, &subject, &body) {
+            Ok(()) => delivered = true,
+            Err(e) => eprintln!(
                 "[{}] Notify: email delivery failed for '{}' on '{}': {}",
                 chrono::Utc::now().to_rfc3339(), event.label(), stack, e
-            );
+            ),
         }
     }
+
+    // Only a delivered alert starts the window, so a failed one is retried
+    if delivered && event.severity() != Severity::Recovery {
+        dedup_record(stack, event.key());
+    }
+}
+
+// ======================================================
+// DEDUPLICATION
+// ======================================================
+//
+// Last-sent time per (stack, event), kept in the daemon state dir so the
+// window survives restarts. The file watcher and the scheduler can both
+// fire for a broken stack; DEDUP_LOCK serialises the read-modify-write.
+// A recovery clears the stack's entries so the next failure alerts at once.
+// A send is recorded only after a transport delivered it.
+// State errors are logged and the notification goes out regardless.
+
+type DedupState = BTreeMap<String, BTreeMap<String, String>>;
+
+static DEDUP_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn load_dedup_state() -> DedupState {
+    fs::read_to_string(crate::daemon::state_path(DEDUP_STATE_FILE))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_dedup_state(state: &DedupState) {
+    let path = crate::daemon::state_path(DEDUP_STATE_FILE);
+    let result = serde_json::to_string_pretty(state)
+        .map_err(io::Error::other)
+        .and_then(|raw| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            crate::fsutil::write_atomic(&path, raw.as_bytes(), Some(0o600))
+        });
+    if let Err(e) = result {
+        eprintln!("Notify: failed to write dedup state to {}: {}", path.display(), e);
+    }
+}
+
+/// Whether a notification for (stack, event) should go out now.
+/// `recovery` always passes and resets the stack.
+fn dedup_allows(stack: &str, event: &str, recovery: bool) -> bool {
+    let window = crate::daemon::resolve_config().notify_dedup_minutes.value;
+    let _guard = DEDUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut state = load_dedup_state();
+    let now = chrono::Utc::now();
+
+    if recovery {
+        if state.remove(stack).is_some() {
+            save_dedup_state(&state);
+        }
+        return true;
+    }
+
+    if window == 0 {
+        return true;
+    }
+
+    let recent = state
+        .get(stack)
+        .and_then(|events| events.get(event))
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .is_some_and(|last| now.signed_duration_since(last) < chrono::Duration::minutes(window as i64));
+
+    !recent
+}
+
+/// Start the dedup window for (stack, event) after a successful delivery.
+fn dedup_record(stack: &str, event: &str) {
+    if crate::daemon::resolve_config().notify_dedup_minutes.value == 0 {
+        return;
+    }
+    let _guard = DEDUP_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut state = load_dedup_state();
+    state
+        .entry(stack.to_owned())
+        .or_default()
+        .insert(event.to_owned(), chrono::Utc::now().to_rfc3339());
+    save_dedup_state(&state);
 }
 
 // ======================================================
 // DELIVERY — WEBHOOK
 // ======================================================
 
+/// Deliver to every webhook target on the channel. All targets are attempted
+/// even if some fail; failures are collected into a single error.
 fn send_webhook_sync(channel: &NotifyChannel, payload: &WebhookPayload) -> io::Result<()> {
-    let url = match &channel.url {
-        Some(u) => u,
-        None    => return Ok(()),
-    };
+    let failures: Vec<String> = channel
+        .webhooks
+        .iter()
+        .filter_map(|target| {
+            send_webhook_target(channel, target, payload)
+                .err()
+                .map(|e| format!("{}: {}", target.url, e))
+        })
+        .collect();
+
+    if failures.is_empty() {
+        return Ok(());
+    }
 
-    let body = serde_json::to_string(payload)
-        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Err(io::Error::other(format!(
+        "{}/{} webhook(s) failed: {}",
+        failures.len(),
+        channel.webhooks.len(),
+        failures.join("; "),
+    )))
+}
 
-    let mut cmd = std::process::Command::new("curl");
-    cmd.arg("--silent")
-       .arg("--show-error")
-       .arg("--fail")
-       .arg("--max-time").arg("10")
-       .arg("-X").arg("POST")
-       .arg("-H").arg("Content-Type: application/json");
+fn send_webhook_target(
+    channel: &NotifyChannel,
+    target:  &WebhookTarget,
+    payload: &WebhookPayload,
+) -> io::Result<()> {
+    let url = &target.url;
 
-    if let Some(ref secret) = channel.secret {
-        cmd.arg("-H").arg(format!("X-Rehearsa-Secret: {}", secret));
+    let body = match target.format {
+        WebhookFormat::Json    => serde_json::to_value(payload)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        WebhookFormat::Slack   => slack_body(payload),
+        WebhookFormat::Discord => discord_body(payload),
     }
+    .to_string();
 
-    cmd.arg("-d").arg(&body).arg(url);
+    let mut headers = vec![];
+    if let Some(secret) = target.secret.as_ref().or(channel.secret.as_ref()) {
+        headers.push(("X-Rehearsa-Secret", secret.clone()));
+    }
 
-    let output = cmd.output().map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::NotFound,
-            format!("Failed to run curl (is it installed?): {}", e),
-        )
-    })?;
+    post_json(url, &headers, &body, 10)
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("curl error: {}", stderr.trim()),
-        ));
+/// Attachment/embed colour for a payload severity: red for critical, amber
+/// for warning, green for recovery, grey otherwise (e.g. test messages).
+fn severity_color(severity: &str) -> u32 {
+    match severity {
+        "CRITICAL" => 0xD93025,
+        "WARNING"  => 0xF2A900,
+        "RECOVERY" => 0x2EA44F,
+        _          => 0x8A8F98,
     }
+}
 
-    Ok(())
+/// Slack incoming-webhook body. `text` is the notification fallback; the
+/// attachment carries the colour bar and the detail blocks.
+fn slack_body(payload: &WebhookPayload) -> serde_json::Value {
+    serde_json::json!({
+        "text": format!("[Rehearsa {}] {} — {}", payload.severity, payload.event, payload.stack),
+        "attachments": [{
+            "color": format!("#{:06X}", severity_color(&payload.severity)),
+            "blocks": [
+                {
+                    "type": "section",
+                    "text": {
+                        "type": "mrkdwn",
+                        "text": format!("*{}* — `{}`\n{}", payload.event, payload.stack, payload.message),
+                    },
+                },
+                {
+                    "type": "context",
+                    "elements": [{
+                        "type": "mrkdwn",
+                        "text": format!("{} · {}", payload.severity, payload.timestamp),
+                    }],
+                },
+            ],
+        }],
+    })
+}
+
+/// Discord webhook body with a single embed.
+fn discord_body(payload: &WebhookPayload) -> serde_json::Value {
+    serde_json::json!({
+        "username": "Rehearsa",
+        "embeds": [{
+            "title": payload.event,
+            "description": payload.message,
+            "color": severity_color(&payload.severity),
+            "fields": [
+                { "name": "Stack",    "value": payload.stack,    "inline": true },
+                { "name": "Severity", "value": payload.severity, "inline": true },
+            ],
+            "timestamp": payload.timestamp,
+        }],
+    })
 }
 
 // ======================================================
-// DELIVERY — EMAIL (SMTP via lettre, Sendgrid scaffolded)
+// DELIVERY — EMAIL (SMTP via lettre, Sendgrid via HTTP)
 // ======================================================
 
 fn send_email_sync(channel: &NotifyChannel, subject: &str, body: &str) -> io::Result<()> {
@@ -649,7 +1192,7 @@ fn send_smtp(cfg: &EmailConfig, subject: &str, body: &str) -> io::Result<()> {
     }
 
     let email = message_builder.body(body.to_owned()).map_err(|e| {
-        io::Error::new(io::ErrorKind::Other, format!("Failed to build email: {}", e))
+        io::Error::other(format!("Failed to build email: {}", e))
     })?;
 
     // Resolve password
@@ -662,7 +1205,7 @@ fn send_smtp(cfg: &EmailConfig, subject: &str, body: &str) -> io::Result<()> {
     // Build transport
     let mut builder = if cfg.smtp_starttls {
         let tls_params = TlsParameters::new(host.to_owned()).map_err(|e| {
-            io::Error::new(io::ErrorKind::Other, format!("TLS configuration error: {}", e))
+            io::Error::other(format!("TLS configuration error: {}", e))
         })?;
         SmtpTransport::builder_dangerous(host)
             .port(port)
@@ -680,7 +1223,7 @@ fn send_smtp(cfg: &EmailConfig, subject: &str, body: &str) -> io::Result<()> {
     let transport = builder.build();
 
     transport.send(&email).map_err(|e| {
-        io::Error::new(io::ErrorKind::Other, format!("SMTP delivery failed: {}", e))
+        io::Error::other(format!("SMTP delivery failed: {}", e))
     })?;
 
     Ok(())
@@ -708,14 +1251,15 @@ fn send_sendgrid(cfg: &EmailConfig, subject: &str, body: &str) -> io::Result<()>
         ));
     }
 
-    // Build Sendgrid v3 /mail/send payload
-    let personalizations: Vec<serde_json::Value> = cfg.to.iter().map(|addr| {
-        serde_json::json!({ "to": [{ "email": addr }] })
-    }).collect();
+    // Build Sendgrid v3 /mail/send payload — one personalization carrying
+    // every recipient.
+    let recipients: Vec<serde_json::Value> = cfg.to.iter()
+        .map(|addr| sendgrid_address(addr))
+        .collect();
 
     let payload = serde_json::json!({
-        "personalizations": personalizations,
-        "from": { "email": cfg.from },
+        "personalizations": [{ "to": recipients }],
+        "from": sendgrid_address(&cfg.from),
         "subject": subject,
         "content": [{ "type": "text/plain", "value": body }]
     });
@@ -723,36 +1267,103 @@ fn send_sendgrid(cfg: &EmailConfig, subject: &str, body: &str) -> io::Result<()>
     let body_str = serde_json::to_string(&payload)
         .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
-    let mut cmd = std::process::Command::new("curl");
-    cmd.arg("--silent")
-       .arg("--show-error")
-       .arg("--fail")
-       .arg("--max-time").arg("15")
-       .arg("-X").arg("POST")
-       .arg("https://api.sendgrid.com/v3/mail/send")
-       .arg("-H").arg("Content-Type: application/json")
-       .arg("-H").arg(format!("Authorization: Bearer {}", api_key))
-       .arg("-d").arg(&body_str);
-
-    let output = cmd.output().map_err(|e| {
-        io::Error::new(
-            io::ErrorKind::NotFound,
-            format!("Failed to run curl (is it installed?): {}", e),
-        )
+    post_json(
+        SENDGRID_SEND_URL,
+        &[("Authorization", format!("Bearer {}", api_key))],
+        &body_str,
+        15,
+    )
+    .map_err(|e| io::Error::new(e.kind(), format!("Sendgrid delivery failed: {}", e)))
+}
+
+/// Sendgrid wants `{ "email", "name" }` rather than "Name <addr>".
+fn sendgrid_address(addr: &str) -> serde_json::Value {
+    match addr.split_once('<') {
+        Some((name, rest)) => {
+            let email = rest.trim_end().trim_end_matches('>').trim();
+            let name = name.trim().trim_matches('"');
+            if name.is_empty() {
+                serde_json::json!({ "email": email })
+            } else {
+                serde_json::json!({ "email": email, "name": name })
+            }
+        }
+        None => serde_json::json!({ "email": addr.trim() }),
+    }
+}
+
+// ======================================================
+// DELIVERY — HTTP
+// ======================================================
+
+const SENDGRID_SEND_URL: &str = "https://api.sendgrid.com/v3/mail/send";
+
+/// POST a JSON body. Any non-2xx response is an error carrying the status
+/// code and response body.
+///
+/// `notify` is called from the daemon's async tasks, where reqwest's
+/// blocking client must not run, so the request is made on its own thread.
+fn post_json(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &str,
+    timeout_secs: u64,
+) -> io::Result<()> {
+    std::thread::scope(|s| {
+        s.spawn(|| post_json_blocking(url, headers, body, timeout_secs))
+            .join()
+            .unwrap_or_else(|_| Err(io::Error::other("HTTP delivery thread panicked")))
+    })
+}
+
+fn post_json_blocking(
+    url: &str,
+    headers: &[(&str, String)],
+    body: &str,
+    timeout_secs: u64,
+) -> io::Result<()> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(timeout_secs))
+        .build()
+        .map_err(|e| io::Error::other(format!("HTTP client error: {}", error_chain(&e))))?;
+
+    let mut request = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body.to_owned());
+
+    for (name, value) in headers {
+        request = request.header(*name, value);
+    }
+
+    let response = request.send().map_err(|e| {
+        let kind = if e.is_timeout() { io::ErrorKind::TimedOut } else { io::ErrorKind::Other };
+        io::Error::new(kind, format!("request failed: {}", error_chain(&e)))
     })?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
-            format!("Sendgrid delivery failed: {} {}", stderr.trim(), stdout.trim()),
+    let status = response.status();
+    if !status.is_success() {
+        let text = response.text().unwrap_or_default();
+        return Err(io::Error::other(
+            format!("HTTP {}: {}", status.as_u16(), text.trim()),
         ));
     }
 
     Ok(())
 }
 
+/// reqwest's top-level messages are terse ("error sending request");
+/// the underlying cause (DNS, TLS, connection refused) is in the source chain.
+fn error_chain(e: &dyn std::error::Error) -> String {
+    let mut parts = vec![e.to_string()];
+    let mut source = e.source();
+    while let Some(cause) = source {
+        parts.push(cause.to_string());
+        source = cause.source();
+    }
+    parts.join(": ")
+}
+
 // ======================================================
 // CONSTANTS (internal)
 // ======================================================
@@ -0,0 +1,50 @@
+use serde::Serialize;
+use std::str::FromStr;
+
+// ======================================================
+// STRUCTURED OUTPUT
+// ======================================================
+//
+// --format selects how `status`, `coverage` and `stack test` print.
+// Text keeps each command's own table; json and yaml serialize the same
+// structures, so the two never disagree on field names. --json is
+// shorthand for --format json.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// JSON or YAML rather than the human table.
+    pub fn is_structured(self) -> bool {
+        self != OutputFormat::Text
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            other  => Err(format!("Unknown output format '{}'. Use text, json or yaml.", other)),
+        }
+    }
+}
+
+/// Serialize a value as YAML, or as pretty JSON for any other format.
+pub fn render<T: Serialize + ?Sized>(value: &T, format: OutputFormat) -> Result<String, String> {
+    match format {
+        OutputFormat::Yaml => serde_yaml::to_string(value)
+            .map(|s| s.trim_end().to_string())
+            .map_err(|e| format!("YAML error: {}", e)),
+        _ => serde_json::to_string_pretty(value)
+            .map_err(|e| format!("JSON error: {}", e)),
+    }
+}
@@ -1,12 +1,18 @@
 use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
 use std::fs;
+use std::fmt;
 use std::path::PathBuf;
 
+/// Spike threshold used when `fail_on_duration_spike` is set without
+/// an explicit `duration_spike_percent`.
+pub const DEFAULT_DURATION_SPIKE_PERCENT: u32 = 50;
+
 // ======================================================
 // POLICY STRUCT
 // ======================================================
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct StackPolicy {
     pub min_confidence: Option<u32>,
 
@@ -19,8 +25,267 @@ pub struct StackPolicy {
     // Duration-based enforcement
     pub fail_on_duration_spike: Option<bool>,
     pub duration_spike_percent: Option<u32>,
+    /// Fail on any drift from the baseline, however small.
     pub fail_on_baseline_drift: Option<bool>,
+    /// Fail only on Major drift (service set changed or a large score drop).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_on_major_drift: Option<bool>,
+    /// Absolute ceiling on run duration, regardless of history.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_duration_seconds: Option<u64>,
+    /// Per-service score floors. A listed service missing from the run fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_service_scores: Option<HashMap<String, u32>>,
+}
+
+impl StackPolicy {
+    /// True if no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.min_confidence.is_none()
+            && self.min_readiness.is_none()
+            && self.block_on_regression.is_none()
+            && self.fail_on_new_service_failure.is_none()
+            && self.fail_on_duration_spike.is_none()
+            && self.duration_spike_percent.is_none()
+            && self.fail_on_baseline_drift.is_none()
+            && self.fail_on_major_drift.is_none()
+            && self.max_duration_seconds.is_none()
+            && self.min_service_scores.is_none()
+    }
+
+    /// Fields set on both `self` and `other` with differing values.
+    pub fn conflicts_with(&self, other: &StackPolicy) -> Vec<&'static str> {
+        fn differs<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+            matches!((a, b), (Some(x), Some(y)) if x != y)
+        }
+
+        let mut fields = Vec::new();
+        if differs(&self.min_confidence, &other.min_confidence) { fields.push("min_confidence"); }
+        if differs(&self.min_readiness, &other.min_readiness) { fields.push("min_readiness"); }
+        if differs(&self.block_on_regression, &other.block_on_regression) { fields.push("block_on_regression"); }
+        if differs(&self.fail_on_new_service_failure, &other.fail_on_new_service_failure) { fields.push("fail_on_new_service_failure"); }
+        if differs(&self.fail_on_duration_spike, &other.fail_on_duration_spike) { fields.push("fail_on_duration_spike"); }
+        if differs(&self.duration_spike_percent, &other.duration_spike_percent) { fields.push("duration_spike_percent"); }
+        if differs(&self.fail_on_baseline_drift, &other.fail_on_baseline_drift) { fields.push("fail_on_baseline_drift"); }
+        if differs(&self.fail_on_major_drift, &other.fail_on_major_drift) { fields.push("fail_on_major_drift"); }
+        if differs(&self.max_duration_seconds, &other.max_duration_seconds) { fields.push("max_duration_seconds"); }
+        if let (Some(a), Some(b)) = (&self.min_service_scores, &other.min_service_scores) {
+            if a.iter().any(|(svc, v)| b.get(svc).is_some_and(|w| w != v)) {
+                fields.push("min_service_scores");
+            }
+        }
+        fields
+    }
+
+    /// Overlay the fields set on `other` onto a copy of `self`.
+    pub fn merged_with(&self, other: &StackPolicy) -> StackPolicy {
+        StackPolicy {
+            min_confidence:              other.min_confidence.or(self.min_confidence),
+            min_readiness:               other.min_readiness.or(self.min_readiness),
+            block_on_regression:         other.block_on_regression.or(self.block_on_regression),
+            fail_on_new_service_failure: other.fail_on_new_service_failure.or(self.fail_on_new_service_failure),
+            fail_on_duration_spike:      other.fail_on_duration_spike.or(self.fail_on_duration_spike),
+            duration_spike_percent:      other.duration_spike_percent.or(self.duration_spike_percent),
+            fail_on_baseline_drift:      other.fail_on_baseline_drift.or(self.fail_on_baseline_drift),
+            fail_on_major_drift:         other.fail_on_major_drift.or(self.fail_on_major_drift),
+            max_duration_seconds:        other.max_duration_seconds.or(self.max_duration_seconds),
+            // Per-service floors merge by service rather than replacing the map.
+            min_service_scores: match (&self.min_service_scores, &other.min_service_scores) {
+                (Some(a), Some(b)) => {
+                    let mut merged = a.clone();
+                    merged.extend(b.iter().map(|(k, v)| (k.clone(), *v)));
+                    Some(merged)
+                }
+                (a, b) => b.clone().or_else(|| a.clone()),
+            },
+        }
+    }
+}
+// ======================================================
+// EFFECTIVE POLICY
+// ======================================================
+//
+// The full rule set as the engine will enforce it — every field,
+// including ones left unset — with where each value came from.
+// Used by the compliance report so "PASS" can be read against the
+// standard that produced it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicySource {
+    /// Set explicitly in the stack's policy file.
+    Stack,
+    /// Not set by the stack; inherited from the fleet default policy.
+    FleetDefault,
+    /// Not set; the engine applies a built-in value.
+    Default,
+    /// Not set; the rule is not enforced.
+    Unset,
+}
+
+impl fmt::Display for PolicySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicySource::Stack   => write!(f, "stack policy"),
+            PolicySource::FleetDefault => write!(f, "default policy"),
+            PolicySource::Default => write!(f, "built-in default"),
+            PolicySource::Unset   => write!(f, "unset"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectiveRule {
+    pub rule:   &'static str,
+    pub value:  String,
+    pub source: PolicySource,
+}
+
+/// Resolve every policy rule for a stack from its own policy file and
+/// the fleet default. Stack settings win field by field; with neither
+/// present every rule is unset.
+pub fn effective_rules(own: Option<&StackPolicy>, default: Option<&StackPolicy>) -> Vec<EffectiveRule> {
+    let empty = StackPolicy::default();
+    let own = own.unwrap_or(&empty);
+    let default = default.unwrap_or(&empty);
+
+    let own_rules = rules_for(own);
+    let default_rules = rules_for(default);
+
+    rules_for(&default.merged_with(own))
+        .into_iter()
+        .zip(own_rules.iter().zip(&default_rules))
+        .map(|(mut rule, (from_own, from_default))| {
+            if from_own.source == PolicySource::Stack {
+                rule.source = PolicySource::Stack;
+            } else if from_default.source == PolicySource::Stack {
+                rule.source = PolicySource::FleetDefault;
+            }
+            rule
+        })
+        .collect()
+}
+
+/// Every rule of a single policy, each marked Stack where it is set.
+fn rules_for(p: &StackPolicy) -> Vec<EffectiveRule> {
+    fn threshold(rule: &'static str, v: Option<u32>) -> EffectiveRule {
+        match v {
+            Some(v) => EffectiveRule { rule, value: format!("{}%", v), source: PolicySource::Stack },
+            None    => EffectiveRule { rule, value: "not enforced".to_string(), source: PolicySource::Unset },
+        }
+    }
+
+    fn flag(rule: &'static str, v: Option<bool>) -> EffectiveRule {
+        match v {
+            Some(true)  => EffectiveRule { rule, value: "enforced".to_string(), source: PolicySource::Stack },
+            Some(false) => EffectiveRule { rule, value: "not enforced".to_string(), source: PolicySource::Stack },
+            None        => EffectiveRule { rule, value: "not enforced".to_string(), source: PolicySource::Unset },
+        }
+    }
+
+    let spike_percent = match p.duration_spike_percent {
+        Some(v) => EffectiveRule {
+            rule:   "duration_spike_percent",
+            value:  format!("{}%", v),
+            source: PolicySource::Stack,
+        },
+        None => EffectiveRule {
+            rule:   "duration_spike_percent",
+            value:  format!("{}%", DEFAULT_DURATION_SPIKE_PERCENT),
+            source: PolicySource::Default,
+        },
+    };
+
+    let max_duration = match p.max_duration_seconds {
+        Some(v) => EffectiveRule { rule: "max_duration_seconds", value: format!("{}s", v), source: PolicySource::Stack },
+        None    => EffectiveRule { rule: "max_duration_seconds", value: "not enforced".to_string(), source: PolicySource::Unset },
+    };
+
+    let service_floors = match &p.min_service_scores {
+        Some(floors) if !floors.is_empty() => {
+            let mut entries: Vec<String> = floors.iter().map(|(s, v)| format!("{} >= {}", s, v)).collect();
+            entries.sort();
+            EffectiveRule { rule: "min_service_scores", value: entries.join(", "), source: PolicySource::Stack }
+        }
+        _ => EffectiveRule { rule: "min_service_scores", value: "not enforced".to_string(), source: PolicySource::Unset },
+    };
+
+    vec![
+        threshold("min_confidence", p.min_confidence),
+        threshold("min_readiness", p.min_readiness),
+        flag("block_on_regression", p.block_on_regression),
+        flag("fail_on_new_service_failure", p.fail_on_new_service_failure),
+        flag("fail_on_duration_spike", p.fail_on_duration_spike),
+        spike_percent,
+        flag("fail_on_baseline_drift", p.fail_on_baseline_drift),
+        flag("fail_on_major_drift", p.fail_on_major_drift),
+        max_duration,
+        service_floors,
+    ]
 }
+
+// ======================================================
+// CHECK RESULTS
+// ======================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum CheckResult {
+    Pass,
+    Fail,
+    /// Rule not enforced by this policy.
+    Skip,
+}
+
+impl fmt::Display for CheckResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckResult::Pass => write!(f, "PASS"),
+            CheckResult::Fail => write!(f, "FAIL"),
+            CheckResult::Skip => write!(f, "SKIP"),
+        }
+    }
+}
+
+/// One policy rule evaluated against a rehearsal.
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyCheck {
+    pub rule:      &'static str,
+    pub threshold: String,
+    pub observed:  String,
+    pub result:    CheckResult,
+}
+
+impl PolicyCheck {
+    pub fn skip(rule: &'static str) -> Self {
+        PolicyCheck {
+            rule,
+            threshold: "not enforced".to_string(),
+            observed:  "-".to_string(),
+            result:    CheckResult::Skip,
+        }
+    }
+}
+
+/// Print checks as a table (used by `stack test --explain-policy`).
+pub fn print_policy_checks(stack: &str, checks: &[PolicyCheck]) {
+    println!();
+    println!("Policy Evaluation: {}", stack);
+    println!("{}", "─".repeat(72));
+
+    if checks.is_empty() {
+        println!("No policy configured — nothing enforced.");
+        return;
+    }
+
+    println!("{:<30} {:<14} {:<18} Result", "Rule", "Threshold", "Observed");
+    println!("{}", "─".repeat(72));
+
+    for c in checks {
+        println!("{:<30} {:<14} {:<18} {}", c.rule, c.threshold, c.observed, c.result);
+    }
+}
+
 // ======================================================
 // INTERNAL PATH HELPERS
 // ======================================================
@@ -47,8 +312,11 @@ fn policy_path(stack: &str) -> Result<PathBuf, String> {
 // LOAD
 // ======================================================
 
-pub fn load_policy(stack: &str) -> Option<StackPolicy> {
-    let path = policy_path(stack).ok()?;
+/// File name (without extension) of the fleet-wide default policy.
+pub const DEFAULT_POLICY_NAME: &str = "__default__";
+
+fn read_policy_file(name: &str) -> Option<StackPolicy> {
+    let path = policy_path(name).ok()?;
 
     if !path.exists() {
         return None;
@@ -58,6 +326,25 @@ pub fn load_policy(stack: &str) -> Option<StackPolicy> {
     serde_json::from_str(&content).ok()
 }
 
+/// The stack's own policy file only, without the fleet default.
+pub fn load_stack_policy(stack: &str) -> Option<StackPolicy> {
+    read_policy_file(stack)
+}
+
+/// The fleet default policy, applied under every stack's own settings.
+pub fn load_default_policy() -> Option<StackPolicy> {
+    read_policy_file(DEFAULT_POLICY_NAME)
+}
+
+/// The policy enforced for a stack: the fleet default overlaid with the
+/// stack's own settings, field by field. None when neither exists.
+pub fn load_policy(stack: &str) -> Option<StackPolicy> {
+    match (load_default_policy(), load_stack_policy(stack)) {
+        (Some(default), Some(own)) => Some(default.merged_with(&own)),
+        (default, own)             => own.or(default),
+    }
+}
+
 // ======================================================
 // SAVE (used by CLI Set)
 // ======================================================
@@ -74,19 +361,225 @@ pub fn save_policy(stack: &str, policy: &StackPolicy) -> Result<(), String> {
     Ok(())
 }
 
+// ======================================================
+// BULK APPLY (used by CLI Apply)
+// ======================================================
+
+/// Outcome of applying one set of policy fields across many stacks.
+#[derive(Debug, Default)]
+pub struct PolicyApplySummary {
+    pub applied: Vec<String>,
+    /// Stacks left untouched because an explicit policy conflicts,
+    /// with the conflicting field names.
+    pub skipped: Vec<(String, Vec<&'static str>)>,
+}
+
+/// Write `fields` to every stack in `stacks`, merging into any existing
+/// policy. Stacks whose existing policy sets a conflicting value are
+/// skipped unless `overwrite` is set.
+pub fn apply_policy(
+    stacks: &[String],
+    fields: &StackPolicy,
+    overwrite: bool,
+) -> Result<PolicyApplySummary, String> {
+    let mut summary = PolicyApplySummary::default();
+
+    for stack in stacks {
+        let policy = match load_stack_policy(stack) {
+            Some(existing) => {
+                let conflicts = existing.conflicts_with(fields);
+                if !conflicts.is_empty() && !overwrite {
+                    summary.skipped.push((stack.clone(), conflicts));
+                    continue;
+                }
+                existing.merged_with(fields)
+            }
+            None => fields.clone(),
+        };
+
+        save_policy(stack, &policy)?;
+        summary.applied.push(stack.clone());
+    }
+
+    Ok(summary)
+}
+
+/// Minimal glob match supporting `*` (any run) and `?` (any one char).
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+
+    let (mut pi, mut ni) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while ni < n.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == n[ni]) {
+            pi += 1;
+            ni += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ni));
+            pi += 1;
+        } else if let Some((sp, sn)) = star {
+            pi = sp + 1;
+            ni = sn + 1;
+            star = Some((sp, sn + 1));
+        } else {
+            return false;
+        }
+    }
+
+    p[pi..].iter().all(|c| *c == '*')
+}
+
 // ======================================================
 // DELETE
 // ======================================================
 
-pub fn delete_policy(stack: &str) -> Result<(), String> {
+/// Delete a stack's policy. Returns Ok(false) if no policy existed.
+/// Whether a policy file exists for the stack, whether or not it parses.
+pub fn policy_exists(stack: &str) -> Result<bool, String> {
+    Ok(policy_path(stack)?.exists())
+}
+
+pub fn delete_policy(stack: &str) -> Result<bool, String> {
     let path = policy_path(stack)?;
 
-    if path.exists() {
-        fs::remove_file(path)
-            .map_err(|e| format!("Failed to delete policy: {}", e))?;
+    if !path.exists() {
+        return Ok(false);
     }
 
-    Ok(())
+    fs::remove_file(path)
+        .map_err(|e| format!("Failed to delete policy: {}", e))?;
+
+    Ok(true)
+}
+
+// ======================================================
+// VALIDATE (used by CLI Validate)
+// ======================================================
+//
+// Hand-edited policy files can parse cleanly and still be nonsense.
+// Errors are settings the engine cannot honour as written; warnings
+// are settings that are merely suspicious.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueLevel {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for IssueLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueLevel::Error   => f.pad("ERROR"),
+            IssueLevel::Warning => f.pad("WARNING"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PolicyIssue {
+    pub level:   IssueLevel,
+    pub message: String,
+}
+
+/// Names of every policy file, sorted — stacks and the fleet default.
+pub fn policy_stacks() -> Vec<String> {
+    let dir = match policy_dir() {
+        Ok(d)  => d,
+        Err(_) => return vec![],
+    };
+
+    let mut stacks: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            if path.extension().and_then(|x| x.to_str()) != Some("json") {
+                return None;
+            }
+            path.file_stem().map(|s| s.to_string_lossy().to_string())
+        })
+        .collect();
+
+    stacks.sort();
+    stacks
+}
+
+/// Lint one stack's policy file. Err when there is no file to check.
+pub fn validate_policy(stack: &str) -> Result<Vec<PolicyIssue>, String> {
+    let path = policy_path(stack)?;
+
+    if !path.exists() {
+        return Err(format!("No policy found for '{}'", stack));
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+
+    let mut issues = Vec::new();
+    let mut error   = |message: String| issues.push(PolicyIssue { level: IssueLevel::Error, message });
+
+    // load_policy treats an unparseable file as no policy at all —
+    // the stack silently runs unchecked.
+    let policy: StackPolicy = match serde_json::from_str(&content) {
+        Ok(p)  => p,
+        Err(e) => {
+            error(format!("Policy file does not parse and is ignored by the engine: {}", e));
+            return Ok(issues);
+        }
+    };
+
+    for (field, value) in [("min_confidence", policy.min_confidence), ("min_readiness", policy.min_readiness)] {
+        if let Some(v) = value.filter(|v| *v > 100) {
+            error(format!("{} is {} — scores never exceed 100, so every run fails", field, v));
+        }
+    }
+
+    if policy.fail_on_duration_spike == Some(true) && policy.duration_spike_percent.is_none() {
+        error(format!(
+            "fail_on_duration_spike is enabled but duration_spike_percent is unset — \
+             the built-in {}% applies; set it explicitly",
+            DEFAULT_DURATION_SPIKE_PERCENT
+        ));
+    }
+
+    let mut floors: Vec<(&String, &u32)> = policy.min_service_scores.iter().flatten().collect();
+    floors.sort();
+
+    for (service, min) in &floors {
+        if **min > 100 {
+            error(format!("min_service_scores.{} is {} — scores never exceed 100", service, min));
+        }
+    }
+
+    if let Some(latest) = crate::history::load_latest(stack) {
+        for (service, _) in &floors {
+            if !latest.services.contains_key(*service) {
+                error(format!(
+                    "min_service_scores references '{}', which is not in the latest run — it will always fail",
+                    service
+                ));
+            }
+        }
+    }
+
+    let mut warning = |message: String| issues.push(PolicyIssue { level: IssueLevel::Warning, message });
+
+    if policy.is_empty() {
+        warning("No rules configured — nothing is enforced".to_string());
+    }
+
+    if policy.duration_spike_percent.is_some() && policy.fail_on_duration_spike != Some(true) {
+        warning("duration_spike_percent is set but fail_on_duration_spike is not enabled".to_string());
+    }
+
+    if policy.fail_on_baseline_drift == Some(true) && policy.fail_on_major_drift == Some(true) {
+        warning("fail_on_major_drift is redundant — fail_on_baseline_drift already fails on any drift".to_string());
+    }
+
+    Ok(issues)
 }
 
 // ======================================================
@@ -94,14 +587,38 @@ pub fn delete_policy(stack: &str) -> Result<(), String> {
 // ======================================================
 
 pub fn show_policy(stack: &str) -> Result<(), String> {
-    match load_policy(stack) {
+    let own = load_stack_policy(stack);
+    let default = load_default_policy();
+
+    if own.is_none() && default.is_none() {
+        println!("No policy found for '{}'", stack);
+        return Ok(());
+    }
+
+    println!("Policy for '{}':", stack);
+    print_rules(&effective_rules(own.as_ref(), default.as_ref()));
+
+    Ok(())
+}
+
+pub fn show_default_policy() -> Result<(), String> {
+    match load_default_policy() {
         Some(policy) => {
-            println!("{:#?}", policy);
-        }
-        None => {
-            println!("No policy found for '{}'", stack);
+            println!("Default policy (applies to every stack, overridden per field by stack policies):");
+            print_rules(&effective_rules(None, Some(&policy)));
         }
+        None => println!("No default policy set."),
     }
 
     Ok(())
 }
+
+fn print_rules(rules: &[EffectiveRule]) {
+    println!();
+    println!("{:<30} {:<24} Source", "Rule", "Value");
+    println!("{}", "─".repeat(72));
+
+    for r in rules {
+        println!("{:<30} {:<24} {}", r.rule, r.value, r.source);
+    }
+}
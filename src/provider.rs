@@ -2,8 +2,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::io;
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
 
 // ======================================================
 // CONSTANTS
@@ -11,6 +13,9 @@ use std::process::Command;
 
 const PROVIDERS_PATH: &str = "/etc/rehearsa/providers.json";
 
+/// Default limit for a test restore, per backup tool invocation.
+const DEFAULT_RESTORE_TIMEOUT_SECS: u64 = 300;
+
 // ======================================================
 // TYPES
 // ======================================================
@@ -20,13 +25,15 @@ const PROVIDERS_PATH: &str = "/etc/rehearsa/providers.json";
 pub enum ProviderKind {
     Restic,
     Borg,
+    Rclone,
 }
 
 impl std::fmt::Display for ProviderKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            ProviderKind::Restic => write!(f, "restic"),
-            ProviderKind::Borg   => write!(f, "borg"),
+            ProviderKind::Restic => f.pad("restic"),
+            ProviderKind::Borg   => f.pad("borg"),
+            ProviderKind::Rclone => f.pad("rclone"),
         }
     }
 }
@@ -52,9 +59,13 @@ pub struct VerifyOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_snapshot_age_hours: Option<u64>,
 
-    /// If true, attempt a dry-run restore from the latest snapshot before rehearsal.
+    /// If true, restore a single small file from the latest snapshot before rehearsal.
     #[serde(default)]
     pub test_restore: bool,
+
+    /// Time limit in seconds for each step of the test restore.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restore_timeout_secs: Option<u64>,
 }
 
 /// A named backup provider definition stored in /etc/rehearsa/providers.json.
@@ -124,10 +135,11 @@ pub fn add_provider(
     let kind = match kind_str {
         "restic" => ProviderKind::Restic,
         "borg"   => ProviderKind::Borg,
+        "rclone" => ProviderKind::Rclone,
         other => {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
-                format!("Unknown provider kind '{}'. Supported: restic, borg", other),
+                format!("Unknown provider kind '{}'. Supported: restic, borg, rclone", other),
             ))
         }
     };
@@ -165,6 +177,7 @@ pub fn set_provider_verify(
     name: &str,
     max_snapshot_age_hours: Option<u64>,
     test_restore: bool,
+    restore_timeout_secs: Option<u64>,
 ) -> io::Result<()> {
     let mut registry = load_registry()?;
     let provider = registry.get_mut(name).ok_or_else(|| {
@@ -179,6 +192,10 @@ pub fn set_provider_verify(
         provider.verify.test_restore = true;
         println!("Set test restore: true");
     }
+    if let Some(secs) = restore_timeout_secs {
+        provider.verify.restore_timeout_secs = Some(secs);
+        println!("Set restore timeout: {}s", secs);
+    }
 
     save_registry(&registry)?;
     println!("Provider '{}' verification options updated.", name);
@@ -206,6 +223,9 @@ pub fn show_provider(name: &str) -> io::Result<()> {
                     .map(|h| format!("{}h", h))
                     .unwrap_or_else(|| "not set".to_owned()));
             println!("  test restore     : {}", p.verify.test_restore);
+            if p.verify.test_restore {
+                println!("  restore timeout  : {}s", restore_timeout(p).as_secs());
+            }
         }
         None => {
             return Err(io::Error::new(
@@ -226,7 +246,7 @@ pub fn list_providers() -> io::Result<()> {
         return Ok(());
     }
 
-    println!("{:<20} {:<10} {}", "Name", "Kind", "Repository");
+    println!("{:<20} {:<10} Repository", "Name", "Kind");
     println!("{}", "─".repeat(60));
     let mut entries: Vec<&ProviderConfig> = registry.values().collect();
     entries.sort_by(|a, b| a.name.cmp(&b.name));
@@ -250,9 +270,9 @@ pub fn delete_provider(name: &str) -> io::Result<()> {
     Ok(())
 }
 
-/// Verify a provider's repository is reachable and (for Restic) contains at least one snapshot.
-/// Model A: checks repo accessibility via `restic snapshots`.
-/// Model B scaffold: snapshot age and test-restore enforcement live here when implemented.
+/// Verify a provider's repository is reachable and contains at least one snapshot.
+/// Model A: checks repo accessibility via `restic snapshots` / `borg info`.
+/// Model B: enforces snapshot age and, when enabled, a bounded test restore.
 pub fn verify_provider(name: &str) -> io::Result<()> {
     let registry = load_registry()?;
     let provider = match registry.get(name) {
@@ -271,6 +291,7 @@ pub fn verify_provider(name: &str) -> io::Result<()> {
     match provider.kind {
         ProviderKind::Restic => verify_restic(provider),
         ProviderKind::Borg   => verify_borg(provider),
+        ProviderKind::Rclone => verify_rclone(provider),
     }
 }
 
@@ -280,22 +301,7 @@ fn verify_restic(provider: &ProviderConfig) -> io::Result<()> {
     cmd.arg("--repo").arg(&provider.repository);
     cmd.arg("snapshots").arg("--last").arg("--json");
 
-    // Inject credentials
-    match (&provider.password.env, &provider.password.file) {
-        (Some(env_var), _) => {
-            cmd.env("RESTIC_PASSWORD_ENV", env_var);
-            // Pass the actual value if available in the current environment
-            if let Ok(val) = std::env::var(env_var) {
-                cmd.env("RESTIC_PASSWORD", val);
-            }
-        }
-        (_, Some(file)) => {
-            cmd.arg("--password-file").arg(file);
-        }
-        _ => {
-            // No credential config — restic will fall back to its own env lookup
-        }
-    }
+    inject_restic_credentials(&mut cmd, provider);
 
     println!("Repository : {}", provider.repository);
     print!("Reachable  : ");
@@ -310,8 +316,7 @@ fn verify_restic(provider: &ProviderConfig) -> io::Result<()> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         println!("✗ FAILED");
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
+        return Err(io::Error::other(
             format!("restic error: {}", stderr.trim()),
         ));
     }
@@ -351,8 +356,7 @@ fn verify_restic(provider: &ProviderConfig) -> io::Result<()> {
                             .num_hours();
                         println!("Snapshot age : {}h (max: {}h)", age_hours, max_age_hours);
                         if age_hours as u64 > max_age_hours {
-                            return Err(io::Error::new(
-                                io::ErrorKind::Other,
+                            return Err(io::Error::other(
                                 format!(
                                     "Snapshot is {}h old — exceeds max age of {}h. Run a backup.",
                                     age_hours, max_age_hours
@@ -369,6 +373,11 @@ fn verify_restic(provider: &ProviderConfig) -> io::Result<()> {
         }
     }
 
+    // Model B: test restore
+    if provider.verify.test_restore {
+        test_restore_restic(provider)?;
+    }
+
     println!();
     println!("Status: PROVIDER OK");
     Ok(())
@@ -400,8 +409,7 @@ fn verify_borg(provider: &ProviderConfig) -> io::Result<()> {
     if !info_out.status.success() {
         let stderr = String::from_utf8_lossy(&info_out.stderr);
         println!("✗ FAILED");
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
+        return Err(io::Error::other(
             format!("borg error: {}", stderr.trim()),
         ));
     }
@@ -426,8 +434,7 @@ fn verify_borg(provider: &ProviderConfig) -> io::Result<()> {
 
     if !list_out.status.success() {
         let stderr = String::from_utf8_lossy(&list_out.stderr);
-        return Err(io::Error::new(
-            io::ErrorKind::Other,
+        return Err(io::Error::other(
             format!("borg list error: {}", stderr.trim()),
         ));
     }
@@ -489,8 +496,7 @@ fn verify_borg(provider: &ProviderConfig) -> io::Result<()> {
                             .num_hours();
                         println!("Archive age  : {}h (max: {}h)", age_hours, max_age_hours);
                         if age_hours as u64 > max_age_hours {
-                            return Err(io::Error::new(
-                                io::ErrorKind::Other,
+                            return Err(io::Error::other(
                                 format!(
                                     "Archive is {}h old — exceeds max age of {}h. Run a backup.",
                                     age_hours, max_age_hours
@@ -507,11 +513,135 @@ fn verify_borg(provider: &ProviderConfig) -> io::Result<()> {
         }
     }
 
+    // Model B: test restore
+    if provider.verify.test_restore {
+        let archive = parsed
+            .get("archives")
+            .and_then(|a| a.as_array())
+            .and_then(|a| a.first())
+            .and_then(|a| a.get("name"))
+            .and_then(|n| n.as_str())
+            .ok_or_else(|| io::Error::other("Could not determine latest archive name for test restore"))?;
+        test_restore_borg(provider, archive)?;
+    }
+
+    println!();
+    println!("Status: PROVIDER OK");
+    Ok(())
+}
+
+fn verify_rclone(provider: &ProviderConfig) -> io::Result<()> {
+    // rclone lsjson -R --files-only <remote> — checks reachability and lists
+    // every object. There are no snapshots; objects stand in for them.
+    let mut cmd = Command::new("rclone");
+    cmd.arg("lsjson")
+       .arg("--recursive")
+       .arg("--files-only")
+       .arg(&provider.repository);
+
+    inject_rclone_credentials(&mut cmd, provider);
+
+    println!("Repository : {}", provider.repository);
+    print!("Reachable  : ");
+
+    let output = cmd.output().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Failed to run rclone (is it installed?): {}", e),
+        )
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        println!("✗ FAILED");
+        return Err(io::Error::other(
+            format!("rclone error: {}", stderr.trim()),
+        ));
+    }
+
+    println!("✓ OK");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let objects: Vec<serde_json::Value> =
+        serde_json::from_str(&stdout).unwrap_or_default();
+
+    println!("Objects    : {}", objects.len());
+
+    if objects.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "No objects found at remote. Run a backup first, then re-verify.",
+        ));
+    }
+
+    // Most recently modified object, e.g. "2026-02-23T03:00:01.123456789Z"
+    let newest = objects
+        .iter()
+        .filter_map(|o| o.get("ModTime").and_then(|t| t.as_str()))
+        .filter_map(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .max();
+
+    if let Some(time) = newest {
+        println!("Latest     : {}", time.with_timezone(&chrono::Utc).format("%Y-%m-%d %H:%M:%S"));
+    }
+
+    // Model B: age enforcement against the newest object
+    if let Some(max_age_hours) = provider.verify.max_snapshot_age_hours {
+        match newest {
+            Some(time) => {
+                let age_hours = chrono::Utc::now()
+                    .signed_duration_since(time.with_timezone(&chrono::Utc))
+                    .num_hours();
+                println!("Object age   : {}h (max: {}h)", age_hours, max_age_hours);
+                if age_hours as u64 > max_age_hours {
+                    return Err(io::Error::other(
+                        format!(
+                            "Newest object is {}h old — exceeds max age of {}h. Run a backup.",
+                            age_hours, max_age_hours
+                        ),
+                    ));
+                }
+                println!("Age check    : ✓ OK");
+            }
+            None => {
+                println!("Age check    : ⚠ Could not parse object timestamps");
+            }
+        }
+    }
+
+    // Model B: test restore
+    if provider.verify.test_restore {
+        let files = objects.iter().filter_map(|o| Some((
+            o.get("Path")?.as_str()?.to_owned(),
+            o.get("Size").and_then(|s| s.as_u64()).unwrap_or(0),
+        )));
+        test_restore_rclone(provider, files)?;
+    }
+
     println!();
     println!("Status: PROVIDER OK");
     Ok(())
 }
 
+/// Inject Restic password credentials into a Command.
+fn inject_restic_credentials(cmd: &mut Command, provider: &ProviderConfig) {
+    match (&provider.password.env, &provider.password.file) {
+        (Some(env_var), _) => {
+            cmd.env("RESTIC_PASSWORD_ENV", env_var);
+            // Pass the actual value if available in the current environment
+            if let Ok(val) = std::env::var(env_var) {
+                cmd.env("RESTIC_PASSWORD", val);
+            }
+        }
+        (_, Some(file)) => {
+            cmd.arg("--password-file").arg(file);
+        }
+        _ => {
+            // No credential config — restic will fall back to its own env lookup
+        }
+    }
+}
+
 /// Inject Borg passphrase credentials into a Command.
 /// Borg uses BORG_PASSPHRASE (env var) or BORG_PASSPHRASE_FD / --passphrase-file.
 /// We map Rehearsa's PasswordSource onto Borg's native env vars to keep it
@@ -537,6 +667,290 @@ fn inject_borg_credentials(cmd: &mut Command, provider: &ProviderConfig) {
     }
 }
 
+/// Inject rclone credentials into a Command using rclone's own env vars.
+/// The env source names a variable holding the config password
+/// (RCLONE_CONFIG_PASS); the file source is the rclone config file to use.
+fn inject_rclone_credentials(cmd: &mut Command, provider: &ProviderConfig) {
+    match (&provider.password.env, &provider.password.file) {
+        (Some(env_var), _) => {
+            if let Ok(val) = std::env::var(env_var) {
+                cmd.env("RCLONE_CONFIG_PASS", val);
+            }
+        }
+        (_, Some(file)) => {
+            cmd.env("RCLONE_CONFIG", file);
+        }
+        _ => {
+            // No credentials configured — rclone uses its default config.
+        }
+    }
+}
+
+// ======================================================
+// TEST RESTORE (Model B)
+// ======================================================
+//
+// A reachable repository is not proof that it restores. With
+// `test_restore` set, the smallest non-empty file in the latest snapshot
+// is restored (Restic and rclone, into a temp dir that is removed
+// afterwards) or dry-run extracted (Borg). Restoring one file keeps the cost bounded
+// while still exercising index, decryption and pack reads.
+
+fn restore_timeout(provider: &ProviderConfig) -> Duration {
+    Duration::from_secs(
+        provider.verify.restore_timeout_secs.unwrap_or(DEFAULT_RESTORE_TIMEOUT_SECS),
+    )
+}
+
+/// Run a command to completion, killing it if it exceeds `timeout`.
+/// Output is drained on background threads so a chatty child cannot block.
+fn run_bounded(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {}s", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(200));
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut p) = pipe {
+            let _ = p.read_to_end(&mut buf);
+        }
+        buf
+    })
+}
+
+/// Pick the smallest regular file, preferring non-empty ones so data is
+/// actually read back. `files` is (path, size).
+fn smallest_file(files: impl Iterator<Item = (String, u64)>) -> Option<(String, u64)> {
+    files.min_by_key(|(_, size)| (*size == 0, *size))
+}
+
+fn restore_failed(tool: &str, step: &str, output: &Output) -> io::Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let detail = match stderr.trim() {
+        "" => output.status.to_string(),
+        s  => s.to_string(),
+    };
+    io::Error::other(format!("Test restore failed — {} {}: {}", tool, step, detail))
+}
+
+fn test_restore_restic(provider: &ProviderConfig) -> io::Result<()> {
+    let timeout = restore_timeout(provider);
+    let started = Instant::now();
+    print!("Test restore : ");
+
+    // ── Step 1: choose a file from the latest snapshot ───────────────────
+    let mut ls_cmd = Command::new("restic");
+    ls_cmd.arg("--repo").arg(&provider.repository)
+          .arg("ls").arg("latest").arg("--json");
+    inject_restic_credentials(&mut ls_cmd, provider);
+
+    let ls_out = run_bounded(&mut ls_cmd, timeout).map_err(|e| {
+        println!("✗ FAILED");
+        io::Error::other(format!("Test restore failed — restic ls: {}", e))
+    })?;
+    if !ls_out.status.success() {
+        println!("✗ FAILED");
+        return Err(restore_failed("restic", "ls", &ls_out));
+    }
+
+    // restic ls --json emits one JSON object per line: the snapshot, then nodes.
+    let listing = String::from_utf8_lossy(&ls_out.stdout);
+    let files = listing
+        .lines()
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .filter(|n| n.get("type").and_then(|t| t.as_str()) == Some("file"))
+        .filter_map(|n| Some((
+            n.get("path")?.as_str()?.to_owned(),
+            n.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+        )));
+
+    let (path, size) = smallest_file(files).ok_or_else(|| {
+        println!("✗ FAILED");
+        io::Error::other("Test restore failed — latest snapshot contains no files")
+    })?;
+
+    // ── Step 2: restore just that file into a temp dir ───────────────────
+    let target = std::env::temp_dir().join(format!(
+        "rehearsa-restore-{}-{}",
+        provider.name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&target)?;
+
+    let mut restore_cmd = Command::new("restic");
+    restore_cmd.arg("--repo").arg(&provider.repository)
+               .arg("restore").arg("latest")
+               .arg("--target").arg(&target)
+               .arg("--include").arg(&path)
+               .arg("--quiet");
+    inject_restic_credentials(&mut restore_cmd, provider);
+
+    let result = run_bounded(&mut restore_cmd, timeout);
+    let restored = target.join(path.trim_start_matches('/'));
+    let restored_ok = fs::metadata(&restored).map(|m| m.len() == size).unwrap_or(false);
+    let _ = fs::remove_dir_all(&target);
+
+    match result {
+        Err(e) => {
+            println!("✗ FAILED");
+            Err(io::Error::other(format!("Test restore failed — restic restore: {}", e)))
+        }
+        Ok(out) if !out.status.success() => {
+            println!("✗ FAILED");
+            Err(restore_failed("restic", "restore", &out))
+        }
+        Ok(_) if !restored_ok => {
+            println!("✗ FAILED");
+            Err(io::Error::other(format!(
+                "Test restore failed — {} was not restored intact", path
+            )))
+        }
+        Ok(_) => {
+            println!("✓ OK ({}, {} bytes, {}s)", path, size, started.elapsed().as_secs());
+            Ok(())
+        }
+    }
+}
+
+fn test_restore_borg(provider: &ProviderConfig, archive: &str) -> io::Result<()> {
+    let timeout = restore_timeout(provider);
+    let started = Instant::now();
+    let archive_ref = format!("{}::{}", provider.repository, archive);
+    print!("Test restore : ");
+
+    // ── Step 1: choose a file from the latest archive ────────────────────
+    let mut list_cmd = Command::new("borg");
+    list_cmd.arg("list").arg("--json-lines").arg(&archive_ref);
+    inject_borg_credentials(&mut list_cmd, provider);
+
+    let list_out = run_bounded(&mut list_cmd, timeout).map_err(|e| {
+        println!("✗ FAILED");
+        io::Error::other(format!("Test restore failed — borg list: {}", e))
+    })?;
+    if !list_out.status.success() {
+        println!("✗ FAILED");
+        return Err(restore_failed("borg", "list", &list_out));
+    }
+
+    // Regular files have type "-" in borg's listing.
+    let listing = String::from_utf8_lossy(&list_out.stdout);
+    let files = listing
+        .lines()
+        .filter_map(|l| serde_json::from_str::<serde_json::Value>(l).ok())
+        .filter(|n| n.get("type").and_then(|t| t.as_str()) == Some("-"))
+        .filter_map(|n| Some((
+            n.get("path")?.as_str()?.to_owned(),
+            n.get("size").and_then(|s| s.as_u64()).unwrap_or(0),
+        )));
+
+    let (path, size) = smallest_file(files).ok_or_else(|| {
+        println!("✗ FAILED");
+        io::Error::other("Test restore failed — latest archive contains no files")
+    })?;
+
+    // ── Step 2: dry-run extract just that file ───────────────────────────
+    let mut extract_cmd = Command::new("borg");
+    extract_cmd.arg("extract").arg("--dry-run").arg(&archive_ref).arg(&path);
+    inject_borg_credentials(&mut extract_cmd, provider);
+
+    match run_bounded(&mut extract_cmd, timeout) {
+        Err(e) => {
+            println!("✗ FAILED");
+            Err(io::Error::other(format!("Test restore failed — borg extract: {}", e)))
+        }
+        Ok(out) if !out.status.success() => {
+            println!("✗ FAILED");
+            Err(restore_failed("borg", "extract", &out))
+        }
+        Ok(_) => {
+            println!("✓ OK ({}, {} bytes, {}s)", path, size, started.elapsed().as_secs());
+            Ok(())
+        }
+    }
+}
+
+fn test_restore_rclone(
+    provider: &ProviderConfig,
+    files: impl Iterator<Item = (String, u64)>,
+) -> io::Result<()> {
+    let timeout = restore_timeout(provider);
+    let started = Instant::now();
+    print!("Test restore : ");
+
+    let (path, size) = smallest_file(files).ok_or_else(|| {
+        println!("✗ FAILED");
+        io::Error::other("Test restore failed — remote contains no files")
+    })?;
+
+    let target = std::env::temp_dir().join(format!(
+        "rehearsa-restore-{}-{}",
+        provider.name,
+        std::process::id()
+    ));
+    fs::create_dir_all(&target)?;
+    let restored = target.join("object");
+
+    let mut copy_cmd = Command::new("rclone");
+    copy_cmd.arg("copyto")
+            .arg(format!("{}/{}", provider.repository.trim_end_matches('/'), path))
+            .arg(&restored);
+    inject_rclone_credentials(&mut copy_cmd, provider);
+
+    let result = run_bounded(&mut copy_cmd, timeout);
+    let restored_ok = fs::metadata(&restored).map(|m| m.len() == size).unwrap_or(false);
+    let _ = fs::remove_dir_all(&target);
+
+    match result {
+        Err(e) => {
+            println!("✗ FAILED");
+            Err(io::Error::other(format!("Test restore failed — rclone copyto: {}", e)))
+        }
+        Ok(out) if !out.status.success() => {
+            println!("✗ FAILED");
+            Err(restore_failed("rclone", "copyto", &out))
+        }
+        Ok(_) if !restored_ok => {
+            println!("✗ FAILED");
+            Err(io::Error::other(format!(
+                "Test restore failed — {} was not restored intact", path
+            )))
+        }
+        Ok(_) => {
+            println!("✓ OK ({}, {} bytes, {}s)", path, size, started.elapsed().as_secs());
+            Ok(())
+        }
+    }
+}
+
 // ======================================================
 // PUBLIC LOADER (used by daemon + engine)
 // ======================================================
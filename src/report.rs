@@ -1,12 +1,15 @@
+use colored::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
 
-use crate::baseline::load_baseline;
-use crate::history::{calculate_stability, load_latest, RunRecord};
-use crate::policy::load_policy;
+use crate::baseline::{load_baseline, DriftSeverity};
+use crate::history::{calculate_stability, load_latest, load_recent, RunRecord};
+use crate::policy::{load_policy, load_stack_policy, load_default_policy, effective_rules, PolicySource};
 use crate::provider::load_provider;
+use crate::exitcode::PASS_SCORE;
+use crate::scoring::load_scoring;
 
 // ======================================================
 // REPORT DATA MODEL
@@ -39,6 +42,16 @@ pub struct ReportMeta {
     pub generated_at:     String,
     pub rehearsa_version: String,
     pub report_id:        String,
+    /// Host that produced the latest rehearsal, if recorded.
+    #[serde(default)]
+    pub host:             Option<String>,
+    /// Docker engine version of the latest rehearsal, if recorded.
+    #[serde(default)]
+    pub docker_version:   Option<String>,
+    /// SHA-256 over the canonical JSON of the rest of the report.
+    /// Only present when generated with `--sign`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash:     Option<String>,
 }
 
 // ──────────────────────────────────────────────────────
@@ -71,6 +84,9 @@ pub struct RehearsalSection {
     pub risk:             String,
     pub exit_code:        i32,
     pub services:         HashMap<String, u32>,
+    /// Log tail of each service that failed in this run.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub service_logs:     HashMap<String, String>,
 }
 
 // ──────────────────────────────────────────────────────
@@ -95,6 +111,10 @@ pub struct HistoryRun {
     pub duration_seconds: u64,
     pub risk:             String,
     pub exit_code:        i32,
+    #[serde(default)]
+    pub host:             Option<String>,
+    #[serde(default)]
+    pub docker_version:   Option<String>,
 }
 
 // ──────────────────────────────────────────────────────
@@ -107,6 +127,9 @@ pub struct BaselineSection {
     pub pinned:              bool,
     /// "CONTRACT_HONOURED" | "DRIFT_DETECTED" | "NO_BASELINE"
     pub status:              String,
+    /// Drift severity when a baseline is pinned.
+    #[serde(default)]
+    pub drift_severity:      Option<DriftSeverity>,
     pub expected_confidence: Option<u32>,
     pub expected_readiness:  Option<u32>,
     pub expected_duration:   Option<u64>,
@@ -128,6 +151,17 @@ pub struct PolicySection {
     /// "PASS" | "FAIL" | "NOT_CONFIGURED"
     pub verdict:             String,
     pub checks:              Vec<PolicyCheck>,
+    /// Every rule as enforced, including unset ones, with its source.
+    #[serde(default)]
+    pub effective:           Vec<EffectivePolicyRule>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EffectivePolicyRule {
+    pub rule:   String,
+    /// e.g. "80%", "enforced", "not enforced"
+    pub value:  String,
+    pub source: PolicySource,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,6 +188,12 @@ pub struct PreflightFinding {
     /// "CRITICAL" | "WARNING" | "INFO"
     pub severity: String,
     pub message:  String,
+    /// Stable finding ID; None for the placeholder on runs without stored findings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id:       Option<String>,
+    /// Suppressed by the operator — listed as an accepted risk.
+    #[serde(default)]
+    pub accepted: bool,
 }
 
 // ──────────────────────────────────────────────────────
@@ -175,37 +215,7 @@ pub struct ProviderSection {
 // ======================================================
 
 fn load_history(stack: &str, window: usize) -> Vec<RunRecord> {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => return vec![],
-    };
-
-    let stack_dir = home
-        .join(".rehearsa")
-        .join("history")
-        .join(stack);
-
-    if !stack_dir.exists() {
-        return vec![];
-    }
-
-    let mut entries: Vec<PathBuf> = match fs::read_dir(&stack_dir) {
-        Ok(e) => e.filter_map(|e| e.ok().map(|e| e.path())).collect(),
-        Err(_) => return vec![],
-    };
-
-    entries.sort();
-
-    entries
-        .into_iter()
-        .rev()
-        .take(window)
-        .rev()
-        .filter_map(|p| {
-            let content = fs::read_to_string(p).ok()?;
-            serde_json::from_str(&content).ok()
-        })
-        .collect()
+    load_recent(stack, window)
 }
 
 // ======================================================
@@ -241,6 +251,9 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
         generated_at:     chrono::Utc::now().to_rfc3339(),
         rehearsa_version: env!("CARGO_PKG_VERSION").to_string(),
         report_id,
+        host:             latest.host.clone(),
+        docker_version:   latest.docker_version.clone(),
+        content_hash:     None,
     };
 
     // ──────────────────────────────────────────────
@@ -254,6 +267,7 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
         risk:             latest.risk.clone(),
         exit_code:        latest.exit_code,
         services:         latest.services.clone(),
+        service_logs:     latest.service_logs.clone(),
     };
 
     // ──────────────────────────────────────────────
@@ -283,6 +297,8 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
             duration_seconds: r.duration_seconds,
             risk:             r.risk.clone(),
             exit_code:        r.exit_code,
+            host:             r.host.clone(),
+            docker_version:   r.docker_version.clone(),
         })
         .collect();
 
@@ -307,11 +323,8 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
             latest.duration_seconds,
         );
 
-        let has_drift = !drift.new_services.is_empty()
-            || !drift.missing_services.is_empty()
-            || drift.confidence_delta != 0
-            || drift.readiness_delta.unwrap_or(0) != 0
-            || drift.duration_delta_percent.unwrap_or(0) != 0;
+        let severity = drift.severity();
+        let has_drift = severity != DriftSeverity::None;
 
         BaselineSection {
             pinned:              true,
@@ -320,6 +333,7 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
             } else {
                 "CONTRACT_HONOURED".to_string()
             },
+            drift_severity:      Some(severity),
             expected_confidence: Some(baseline.expected_confidence),
             expected_readiness:  baseline.expected_readiness,
             expected_duration:   Some(baseline.expected_duration),
@@ -334,6 +348,7 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
         BaselineSection {
             pinned:              false,
             status:              "NO_BASELINE".to_string(),
+            drift_severity:      None,
             expected_confidence: None,
             expected_readiness:  None,
             expected_duration:   None,
@@ -351,7 +366,21 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
     // ──────────────────────────────────────────────
     // Policy section
     // ──────────────────────────────────────────────
-    let policy_section = if let Some(policy) = load_policy(stack) {
+    let loaded_policy = load_policy(stack);
+
+    let effective: Vec<EffectivePolicyRule> = effective_rules(
+        load_stack_policy(stack).as_ref(),
+        load_default_policy().as_ref(),
+    )
+        .into_iter()
+        .map(|r| EffectivePolicyRule {
+            rule:   r.rule.to_string(),
+            value:  r.value,
+            source: r.source,
+        })
+        .collect();
+
+    let policy_section = if let Some(policy) = loaded_policy {
         let mut checks: Vec<PolicyCheck> = vec![];
         let mut any_fail = false;
 
@@ -403,11 +432,52 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
             });
         }
 
+        // fail_on_major_drift
+        if policy.fail_on_major_drift.unwrap_or(false) {
+            let major = baseline_section.drift_severity == Some(DriftSeverity::Major);
+            if major { any_fail = true; }
+            checks.push(PolicyCheck {
+                rule:    "fail_on_major_drift".to_string(),
+                setting: "true".to_string(),
+                result:  if major { "FAIL" } else { "PASS" }.to_string(),
+                detail:  baseline_section.drift_severity
+                    .map_or("no baseline".to_string(), |s| format!("drift {}", s)),
+            });
+        }
+
+        // max_duration_seconds
+        if let Some(max) = policy.max_duration_seconds {
+            let pass = latest.duration_seconds <= max;
+            if !pass { any_fail = true; }
+            checks.push(PolicyCheck {
+                rule:    "max_duration_seconds".to_string(),
+                setting: format!("{}s", max),
+                result:  if pass { "PASS" } else { "FAIL" }.to_string(),
+                detail:  format!("actual {}s", latest.duration_seconds),
+            });
+        }
+
+        // min_service_scores
+        let mut floors: Vec<(&String, &u32)> = policy.min_service_scores.iter().flatten().collect();
+        floors.sort();
+        for (service, &min) in floors {
+            let score = latest.services.get(service).copied();
+            let pass = score.is_some_and(|s| s >= min);
+            if !pass { any_fail = true; }
+            checks.push(PolicyCheck {
+                rule:    format!("min_service_score:{}", service),
+                setting: min.to_string(),
+                result:  if pass { "PASS" } else { "FAIL" }.to_string(),
+                detail:  score.map_or("missing from run".to_string(), |s| format!("actual {}", s)),
+            });
+        }
+
         // fail_on_new_service_failure
         if policy.fail_on_new_service_failure.unwrap_or(false) {
+            let bands = load_scoring(stack).bands();
             let failed: Vec<&str> = latest.services
                 .iter()
-                .filter(|(_, &s)| s == 0)
+                .filter(|(_, &s)| bands.is_failed(s))
                 .map(|(n, _)| n.as_str())
                 .collect();
             let pass = failed.is_empty();
@@ -428,55 +498,67 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
             configured: true,
             verdict:    if any_fail { "FAIL" } else { "PASS" }.to_string(),
             checks,
+            effective,
         }
     } else {
         PolicySection {
             configured: false,
             verdict:    "NOT_CONFIGURED".to_string(),
             checks:     vec![],
+            effective,
         }
     };
 
     let policy_violated = policy_section.verdict == "FAIL";
 
     // ──────────────────────────────────────────────
-    // Preflight section — reconstructed from latest run metadata.
-    // Full preflight findings are not stored in RunRecord (by design —
-    // they are ephemeral output). We surface the restore readiness score
-    // that was captured and note that detailed findings are live-only.
+    // Preflight section — findings stored with the latest run.
+    // Records written before findings were persisted carry none; for
+    // those we surface only the readiness score and a note.
     // ──────────────────────────────────────────────
-    let preflight_section = PreflightSection {
-        restore_readiness_score: latest.readiness.unwrap_or(0),
-        findings: vec![PreflightFinding {
+    let findings = if latest.preflight_findings.is_empty() {
+        vec![PreflightFinding {
             severity: "INFO".to_string(),
-            message:  "Detailed preflight findings are captured at rehearsal runtime. \
-                       Re-run `rehearsa run` to view live findings."
+            message:  "No preflight findings were stored with this run. \
+                       Re-run `rehearsa stack test` to capture them."
                 .to_string(),
-        }],
+            id:       None,
+            accepted: false,
+        }]
+    } else {
+        latest.preflight_findings.iter().map(|f| PreflightFinding {
+            severity: f.severity.clone(),
+            message:  f.message.clone(),
+            id:       Some(f.id.clone()),
+            accepted: f.acknowledged,
+        }).collect()
+    };
+
+    let preflight_section = PreflightSection {
+        restore_readiness_score: latest.readiness.unwrap_or(0),
+        findings,
     };
 
     // ──────────────────────────────────────────────
     // Provider section
     // ──────────────────────────────────────────────
     let provider_section = if let Some(ref pname) = opts.provider_name {
-        if let Some(provider) = load_provider(pname) {
-            ProviderSection {
-                attached:   true,
-                name:       Some(provider.name.clone()),
-                kind:       Some(provider.kind.to_string()),
-                repository: Some(provider.repository.clone()),
-                // Verification status is not stored persistently — we report
-                // "not checked at report time" so users know to run provider verify.
-                verified:   None,
-            }
-        } else {
-            ProviderSection {
-                attached:   true,
-                name:       Some(pname.clone()),
-                kind:       None,
-                repository: None,
-                verified:   Some(false),
-            }
+        // A named-but-unknown provider is a usage error (usually a typo in
+        // --provider), not a failed verification — refuse rather than print
+        // a misleading provider failure into a compliance document.
+        let provider = load_provider(pname).ok_or_else(|| format!(
+            "Provider '{}' not found. Run `rehearsa provider list` to see registered providers.",
+            pname
+        ))?;
+
+        ProviderSection {
+            attached:   true,
+            name:       Some(provider.name.clone()),
+            kind:       Some(provider.kind.to_string()),
+            repository: Some(provider.repository.clone()),
+            // Verification status is not stored persistently — we report
+            // "not checked at report time" so users know to run provider verify.
+            verified:   None,
         }
     } else {
         ProviderSection {
@@ -491,11 +573,13 @@ pub fn build_report(opts: &ReportOptions) -> Result<ComplianceReport, String> {
     // ──────────────────────────────────────────────
     // Summary verdict
     // ──────────────────────────────────────────────
-    let verdict = if policy_violated || baseline_drift {
-        "FAIL"
-    } else if latest.confidence < 70 {
+    // Minor drift only warns — a policy can still escalate it via
+    // fail_on_baseline_drift.
+    let major_drift = baseline_section.drift_severity == Some(DriftSeverity::Major);
+
+    let verdict = if policy_violated || major_drift || latest.confidence < PASS_SCORE {
         "FAIL"
-    } else if latest.confidence < 90 || history.trend == "DOWN" {
+    } else if baseline_drift || latest.confidence < 90 || history.trend == "DOWN" {
         "WARN"
     } else {
         "PASS"
@@ -584,6 +668,266 @@ pub fn render_json_fleet(reports: &[ComplianceReport]) -> Result<String, String>
         .map_err(|e| format!("JSON serialisation failed: {}", e))
 }
 
+// ======================================================
+// SIGNING
+// ======================================================
+//
+// The hash covers the report serialised as a serde_json::Value, whose object
+// keys are always sorted, so the digest does not depend on field order or
+// whitespace in the file. meta.content_hash itself is excluded.
+
+fn content_hash(value: &serde_json::Value) -> Result<String, String> {
+    let mut temp = value.clone();
+    if let Some(meta) = temp.get_mut("meta").and_then(|m| m.as_object_mut()) {
+        meta.remove("content_hash");
+    }
+
+    let json = serde_json::to_string(&temp)
+        .map_err(|e| format!("Hash serialization error: {}", e))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(json.as_bytes());
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Compute and embed `meta.content_hash`.
+pub fn sign_report(report: &mut ComplianceReport) -> Result<(), String> {
+    let value = serde_json::to_value(&*report)
+        .map_err(|e| format!("Hash serialization error: {}", e))?;
+    report.meta.content_hash = Some(content_hash(&value)?);
+    Ok(())
+}
+
+/// Recompute the content hash of every report in a JSON report file (single
+/// or fleet) and print match / mismatch. Returns true only if every report
+/// is signed and matches.
+pub fn verify_report_file(path: &str) -> Result<bool, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+    let reports = match value {
+        serde_json::Value::Array(items) => items,
+        single => vec![single],
+    };
+
+    if reports.is_empty() {
+        return Err(format!("No reports found in {}.", path));
+    }
+
+    let mut all_ok = true;
+
+    for report in &reports {
+        let meta = report.get("meta")
+            .ok_or_else(|| format!("{} is not a Rehearsa report (no meta section).", path))?;
+        let target    = meta.get("target").and_then(|v| v.as_str()).unwrap_or("?");
+        let report_id = meta.get("report_id").and_then(|v| v.as_str()).unwrap_or("?");
+        let short_id  = report_id.get(..8).unwrap_or(report_id);
+
+        match meta.get("content_hash").and_then(|v| v.as_str()) {
+            None => {
+                all_ok = false;
+                println!("UNSIGNED  {} ({}) — no content_hash; generate with --sign", target, short_id);
+            }
+            Some(stored) => {
+                if content_hash(report)? == stored {
+                    println!("MATCH     {} ({}) sha256:{}", target, short_id, stored);
+                } else {
+                    all_ok = false;
+                    println!("MISMATCH  {} ({}) — report was modified after signing", target, short_id);
+                }
+            }
+        }
+    }
+
+    Ok(all_ok)
+}
+
+// ======================================================
+// COMPARE
+// ======================================================
+//
+// Analysis over two saved JSON reports for the same stack — typically one
+// per release. Nothing is recomputed; values are read as recorded.
+
+#[derive(Serialize, Debug)]
+pub struct Transition {
+    pub from: String,
+    pub to:   String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PolicyFlip {
+    pub rule: String,
+    pub from: String,
+    pub to:   String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ReportDiff {
+    pub stack:            String,
+    /// report_id of the older report
+    pub from_report:      String,
+    pub from_generated:   String,
+    pub to_report:        String,
+    pub to_generated:     String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verdict:          Option<Transition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub risk:             Option<Transition>,
+    pub confidence_delta: i32,
+    pub readiness_delta:  i32,
+    /// Policy checks present in both reports whose result changed.
+    pub policy_flips:     Vec<PolicyFlip>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub baseline_status:  Option<Transition>,
+    pub added_services:   Vec<String>,
+    pub removed_services: Vec<String>,
+}
+
+fn transition(from: &str, to: &str) -> Option<Transition> {
+    (from != to).then(|| Transition { from: from.to_string(), to: to.to_string() })
+}
+
+/// Load the report for `stack` from a JSON file written by `rehearsa report`.
+/// Fleet files are searched by target; single-stack files must match it.
+fn load_saved_report(path: &str, stack: &str) -> Result<ComplianceReport, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+
+    let value: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path, e))?;
+
+    let target_of = |v: &serde_json::Value| {
+        v.pointer("/meta/target").and_then(|t| t.as_str()).map(str::to_string)
+    };
+
+    let value = match value {
+        serde_json::Value::Array(items) => items
+            .into_iter()
+            .find(|v| target_of(v).as_deref() == Some(stack))
+            .ok_or_else(|| format!("{} has no report for stack '{}'.", path, stack))?,
+        single => {
+            let target = target_of(&single).unwrap_or_default();
+            if target != stack {
+                return Err(format!(
+                    "{} is a report for '{}', not '{}'.",
+                    path, target, stack
+                ));
+            }
+            single
+        }
+    };
+
+    serde_json::from_value(value)
+        .map_err(|e| format!("{} is not a Rehearsa compliance report: {}", path, e))
+}
+
+pub fn compare_reports(stack: &str, from_path: &str, to_path: &str) -> Result<ReportDiff, String> {
+    let a = load_saved_report(from_path, stack)?;
+    let b = load_saved_report(to_path, stack)?;
+
+    let policy_flips = b.policy.checks
+        .iter()
+        .filter_map(|to| {
+            let from = a.policy.checks.iter().find(|c| c.rule == to.rule)?;
+            (from.result != to.result).then(|| PolicyFlip {
+                rule: to.rule.clone(),
+                from: from.result.clone(),
+                to:   to.result.clone(),
+            })
+        })
+        .collect();
+
+    let mut added_services: Vec<String> = b.rehearsal.services.keys()
+        .filter(|s| !a.rehearsal.services.contains_key(*s))
+        .cloned()
+        .collect();
+    added_services.sort();
+
+    let mut removed_services: Vec<String> = a.rehearsal.services.keys()
+        .filter(|s| !b.rehearsal.services.contains_key(*s))
+        .cloned()
+        .collect();
+    removed_services.sort();
+
+    Ok(ReportDiff {
+        stack:            stack.to_string(),
+        verdict:          transition(&a.summary.verdict, &b.summary.verdict),
+        risk:             transition(&a.summary.risk, &b.summary.risk),
+        confidence_delta: b.summary.confidence as i32 - a.summary.confidence as i32,
+        readiness_delta:  b.summary.readiness as i32 - a.summary.readiness as i32,
+        policy_flips,
+        baseline_status:  transition(&a.baseline.status, &b.baseline.status),
+        added_services,
+        removed_services,
+        from_report:      a.meta.report_id,
+        from_generated:   a.meta.generated_at,
+        to_report:        b.meta.report_id,
+        to_generated:     b.meta.generated_at,
+    })
+}
+
+fn signed(delta: i32) -> String {
+    if delta > 0 { format!("+{}", delta) } else { delta.to_string() }
+}
+
+pub fn print_report_diff(diff: &ReportDiff) {
+    println!("Stack: {}", diff.stack);
+    println!(
+        "  {} ({}) → {} ({})\n",
+        short_ts(&diff.from_generated),
+        diff.from_report.get(..8).unwrap_or(&diff.from_report),
+        short_ts(&diff.to_generated),
+        diff.to_report.get(..8).unwrap_or(&diff.to_report),
+    );
+
+    match &diff.verdict {
+        Some(t) => {
+            let line = format!("Verdict:    {} → {}", t.from, t.to);
+            if ReportVerdict::from_label(&t.to) > ReportVerdict::from_label(&t.from) {
+                println!("{}", line.red());
+            } else {
+                println!("{}", line.green());
+            }
+        }
+        None => println!("Verdict:    unchanged"),
+    }
+    println!("Confidence: {}%", signed(diff.confidence_delta));
+    println!("Readiness:  {}%", signed(diff.readiness_delta));
+    if let Some(t) = &diff.risk {
+        println!("Risk:       {} → {}", t.from, t.to);
+    }
+    if let Some(t) = &diff.baseline_status {
+        println!("Baseline:   {} → {}", t.from, t.to);
+    }
+
+    if !diff.policy_flips.is_empty() {
+        println!("\nPolicy checks:");
+        for flip in &diff.policy_flips {
+            let line = format!("  {:<32} {} → {}", flip.rule, flip.from, flip.to);
+            match flip.to.as_str() {
+                "FAIL" => println!("{}", line.red()),
+                "PASS" => println!("{}", line.green()),
+                _      => println!("{}", line),
+            }
+        }
+    }
+
+    if !diff.added_services.is_empty() || !diff.removed_services.is_empty() {
+        println!("\nServices:");
+        for name in &diff.added_services {
+            println!("  {}", format!("+ {} (added)", name).green());
+        }
+        for name in &diff.removed_services {
+            println!("  {}", format!("- {} (removed)", name).red());
+        }
+    }
+}
+
 // ======================================================
 // PDF OUTPUT
 // ======================================================
@@ -681,8 +1025,9 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
         y -= line_height_md;
 
         layer.use_text(
-            &format!("Stack: {}   |   Generated: {}   |   ID: {}",
+            format!("Stack: {}   |   Host: {}   |   Generated: {}   |   ID: {}",
                 report.meta.target,
+                report.meta.host.as_deref().unwrap_or("unknown"),
                 &report.meta.generated_at[..19].replace('T', " "),
                 &report.meta.report_id[..8],
             ),
@@ -721,7 +1066,7 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
 
         layer.set_fill_color(verdict_color.clone());
         layer.use_text(
-            &format!("▐  {}  — Confidence: {}%   Readiness: {}%   Risk: {}   Stability: {}%",
+            format!("▐  {}  — Confidence: {}%   Readiness: {}%   Risk: {}   Stability: {}%",
                 report.summary.verdict,
                 report.summary.confidence,
                 report.summary.readiness,
@@ -785,6 +1130,7 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
     kv_line!("Readiness",        &format!("{}%", report.rehearsal.readiness));
     kv_line!("Risk Band",        &report.rehearsal.risk);
     kv_line!("Exit Code",        &report.rehearsal.exit_code.to_string());
+    kv_line!("Docker Engine",    report.meta.docker_version.as_deref().unwrap_or("not recorded"));
 
     y -= line_height_sm;
     {
@@ -801,6 +1147,24 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
         mono_line!(&format!("  {:<24} {:>3}%  {}", name, score, bar));
     }
 
+    // Failure logs, clipped to the page width
+    let mut failed_logs: Vec<(&String, &String)> = report.rehearsal.service_logs.iter().collect();
+    failed_logs.sort_by_key(|(k, _)| k.as_str());
+    for (name, logs) in failed_logs {
+        y -= line_height_sm;
+        {
+            new_page_if_needed!(line_height_sm * 2.0);
+            let layer = current_layer!();
+            layer.set_fill_color(Color::Rgb(Rgb::new(0.35, 0.35, 0.35, None)));
+            layer.use_text(format!("Logs: {} (failed)", name), 8.5, left_margin + Mm(2.0), y, &font);
+            y -= line_height_sm;
+        }
+        for line in logs.lines() {
+            let clipped: String = line.chars().take(100).collect();
+            mono_line!(&format!("  {}", clipped));
+        }
+    }
+
     // ══════════════════════════════════════════════
     // 2. HISTORY & TREND
     // ══════════════════════════════════════════════
@@ -808,14 +1172,106 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
     kv_line!("Window",    &format!("{} runs", report.history.window));
     kv_line!("Stability", &format!("{}%", report.history.stability));
     kv_line!("Trend",     &report.history.trend);
+
+    // An engine upgrade inside the window is a likely explanation for a shift.
+    let mut engines: Vec<&str> = report.history.runs
+        .iter()
+        .filter_map(|r| r.docker_version.as_deref())
+        .collect();
+    engines.dedup();
+    if engines.len() > 1 {
+        kv_line!("Docker Engine Changes", &engines.join(" -> "));
+    }
     y -= line_height_sm;
 
+    // ── Confidence chart (oldest → newest, 0–100) ─────────────────────────
+    if report.history.runs.len() >= 2 {
+        let chart_h = Mm(35.0);
+        new_page_if_needed!(chart_h + Mm(10.0));
+        let layer = current_layer!();
+
+        let x0 = left_margin.0 + 10.0;
+        let x1 = right_margin.0;
+        let y1 = y.0 - 2.0;
+        let y0 = y1 - chart_h.0;
+        let y_at = |value: u32| Mm(y0 + chart_h.0 * value.min(100) as f32 / 100.0);
+        let grey = Color::Rgb(Rgb::new(0.55, 0.55, 0.55, None));
+
+        // Axes, with labels at 0 / 50 / 100
+        layer.set_outline_color(grey.clone());
+        layer.set_outline_thickness(0.5);
+        layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(x0), Mm(y1)), false),
+                (Point::new(Mm(x0), Mm(y0)), false),
+                (Point::new(Mm(x1), Mm(y0)), false),
+            ],
+            is_closed: false,
+        });
+        layer.set_fill_color(grey);
+        for tick in [0, 50, 100] {
+            layer.use_text(
+                format!("{:>3}", tick),
+                6.5,
+                left_margin + Mm(2.0),
+                y_at(tick) - Mm(1.0),
+                &font_mono,
+            );
+        }
+
+        // Policy floor, dashed
+        let min_confidence = report.policy.checks
+            .iter()
+            .find(|c| c.rule == "min_confidence")
+            .and_then(|c| c.setting.trim_end_matches('%').parse::<u32>().ok());
+        if let Some(min) = min_confidence {
+            layer.set_line_dash_pattern(LineDashPattern {
+                dash_1: Some(3),
+                gap_1:  Some(2),
+                ..LineDashPattern::default()
+            });
+            layer.add_line(Line {
+                points: vec![
+                    (Point::new(Mm(x0), y_at(min)), false),
+                    (Point::new(Mm(x1), y_at(min)), false),
+                ],
+                is_closed: false,
+            });
+            layer.set_line_dash_pattern(LineDashPattern::default());
+            layer.use_text(
+                format!("min {}%", min),
+                6.5,
+                Mm(x1 - 14.0),
+                y_at(min) + Mm(1.0),
+                &font_regular,
+            );
+        }
+
+        // Confidence series
+        let step = (x1 - x0) / (report.history.runs.len() - 1) as f32;
+        layer.set_outline_color(Color::Rgb(Rgb::new(0.10, 0.10, 0.10, None)));
+        layer.set_outline_thickness(1.0);
+        layer.add_line(Line {
+            points: report.history.runs
+                .iter()
+                .enumerate()
+                .map(|(i, run)| (Point::new(Mm(x0 + step * i as f32), y_at(run.confidence)), false))
+                .collect(),
+            is_closed: false,
+        });
+
+        // Restore defaults for later rules
+        layer.set_outline_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+
+        y = Mm(y0) - line_height_md;
+    }
+
     {
         let layer = current_layer!();
         layer.set_fill_color(Color::Rgb(Rgb::new(0.35, 0.35, 0.35, None)));
         layer.use_text(
-            &format!("{:<22} {:>12} {:>10} {:>10} {:>8}",
-                "Timestamp", "Confidence", "Readiness", "Duration", "Risk"),
+            format!("{:<22} {:>12} {:>10} {:>10} {:>8}  {}",
+                "Timestamp", "Confidence", "Readiness", "Duration", "Risk", "Host"),
             7.5,
             left_margin + Mm(2.0),
             y,
@@ -831,12 +1287,13 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
             &run.timestamp
         };
         mono_line!(&format!(
-            "{:<22} {:>11}% {:>9}% {:>9}s {:>8}",
+            "{:<22} {:>11}% {:>9}% {:>9}s {:>8}  {}",
             ts.replace('T', " "),
             run.confidence,
             run.readiness.unwrap_or(0),
             run.duration_seconds,
             run.risk,
+            run.host.as_deref().unwrap_or("-"),
         ));
     }
 
@@ -846,6 +1303,9 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
     section_heading!("3. Baseline Contract");
     kv_line!("Pinned",  if report.baseline.pinned { "Yes" } else { "No" });
     kv_line!("Status",  &report.baseline.status);
+    if let Some(severity) = report.baseline.drift_severity {
+        kv_line!("Drift Severity", &severity.to_string());
+    }
 
     if report.baseline.pinned {
         if let Some(c) = report.baseline.expected_confidence {
@@ -887,7 +1347,7 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
             let layer = current_layer!();
             layer.set_fill_color(Color::Rgb(Rgb::new(0.35, 0.35, 0.35, None)));
             layer.use_text(
-                &format!("{:<30} {:>8} {:>8}  {}", "Rule", "Setting", "Result", "Detail"),
+                format!("{:<30} {:>8} {:>8}  {}", "Rule", "Setting", "Result", "Detail"),
                 7.5,
                 left_margin + Mm(2.0),
                 y,
@@ -904,6 +1364,29 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
         }
     }
 
+    if !report.policy.effective.is_empty() {
+        y -= line_height_sm;
+        {
+            let layer = current_layer!();
+            layer.set_fill_color(Color::Rgb(Rgb::new(0.35, 0.35, 0.35, None)));
+            layer.use_text(
+                format!("{:<30} {:<14} {}", "Effective Policy", "Value", "Source"),
+                7.5,
+                left_margin + Mm(2.0),
+                y,
+                &font,
+            );
+            y -= line_height_sm;
+        }
+
+        for rule in &report.policy.effective {
+            mono_line!(&format!(
+                "{:<30} {:<14} {}",
+                rule.rule, rule.value, rule.source
+            ));
+        }
+    }
+
     // ══════════════════════════════════════════════
     // 5. PREFLIGHT
     // ══════════════════════════════════════════════
@@ -911,7 +1394,12 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
     kv_line!("Restore Readiness Score", &format!("{}%", report.preflight.restore_readiness_score));
 
     for f in &report.preflight.findings {
-        mono_line!(&format!("[{}] {}", f.severity, f.message));
+        let suffix = match (&f.id, f.accepted) {
+            (Some(id), true)  => format!(" ({}, ACCEPTED)", id),
+            (Some(id), false) => format!(" ({})", id),
+            (None, _)         => String::new(),
+        };
+        mono_line!(&format!("[{}] {}{}", f.severity, f.message, suffix));
     }
 
     // ══════════════════════════════════════════════
@@ -946,7 +1434,7 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
         draw_rule(&layer, footer_y);
         layer.set_fill_color(Color::Rgb(Rgb::new(0.55, 0.55, 0.55, None)));
         layer.use_text(
-            &format!(
+            format!(
                 "Rehearsa v{}  |  Report ID: {}  |  {}",
                 report.meta.rehearsa_version,
                 report.meta.report_id,
@@ -957,6 +1445,15 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
             Mm(8.0),
             &font_regular,
         );
+        if let Some(ref hash) = report.meta.content_hash {
+            layer.use_text(
+                format!("SHA-256: {}", hash),
+                7.0,
+                left_margin,
+                Mm(5.0),
+                &font_regular,
+            );
+        }
     }
 
     // ──────────────────────────────────────────────
@@ -969,10 +1466,535 @@ pub fn render_pdf(report: &ComplianceReport) -> Result<Vec<u8>, String> {
     Ok(cursor.into_inner())
 }
 
+// ======================================================
+// HTML OUTPUT
+// ======================================================
+//
+// Self-contained documents (inline CSS, no scripts or external assets) so
+// they can be embedded in a wiki or archived as-is. Sections mirror the PDF.
+
+const HTML_STYLE: &str = "\
+body{font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;color:#1a1a1a;max-width:960px;margin:2em auto;padding:0 1em;}\
+h1{font-size:1.6em;margin-bottom:.2em;}h1 small{font-weight:normal;color:#555;font-size:.6em;}\
+h2{font-size:1.25em;border-bottom:1px solid #ccc;padding-bottom:.2em;margin-top:1.6em;}\
+h3{font-size:1em;margin:1.2em 0 .4em;color:#444;}\
+.meta{color:#555;font-size:.85em;}\
+.banner{padding:.7em 1em;border-radius:4px;font-weight:bold;margin:1em 0;}\
+.pass{background:#e6f7eb;color:#21994f;}.warn{background:#fff7e0;color:#d98c0d;}.fail{background:#fce8e8;color:#cc2626;}\
+table{border-collapse:collapse;width:100%;font-size:.85em;margin:.4em 0;}\
+th,td{text-align:left;padding:.3em .6em;border-bottom:1px solid #eee;vertical-align:top;}\
+th{background:#f5f5f5;}td.num{text-align:right;font-variant-numeric:tabular-nums;}\
+dl{display:grid;grid-template-columns:14em 1fr;gap:.2em .8em;font-size:.9em;}dt{font-weight:bold;color:#555;}dd{margin:0;}\
+.bar{background:#eee;width:10em;height:.8em;display:inline-block;vertical-align:middle;}\
+.bar span{display:block;height:100%;background:#21994f;}\
+.accepted{color:#777;}\
+footer{margin-top:2em;border-top:1px solid #ccc;padding-top:.5em;color:#888;font-size:.8em;}";
+
+/// Render a single-stack report as a standalone HTML document.
+pub fn render_html(report: &ComplianceReport) -> Result<String, String> {
+    Ok(html_document(
+        &format!("Rehearsa Compliance Report — {}", report.meta.target),
+        &html_stack_section(report, None),
+    ))
+}
+
+/// Render a fleet report as one page: a summary table linking to each
+/// stack's section.
+pub fn render_html_fleet(reports: &[ComplianceReport]) -> Result<String, String> {
+    let mut body = String::new();
+
+    body.push_str("<h1>Rehearsa <small>Fleet Compliance Report</small></h1>\n");
+    body.push_str(&format!(
+        "<p class=\"meta\">{} stacks · Generated {}</p>\n",
+        reports.len(),
+        esc(&short_ts(&chrono::Utc::now().to_rfc3339())),
+    ));
+
+    body.push_str("<table>\n<tr><th>Stack</th><th>Verdict</th><th>Confidence</th><th>Readiness</th><th>Risk</th><th>Stability</th></tr>\n");
+    for r in reports {
+        body.push_str(&format!(
+            "<tr><td><a href=\"#{}\">{}</a></td><td class=\"{}\">{}</td><td class=\"num\">{}%</td><td class=\"num\">{}%</td><td>{}</td><td class=\"num\">{}%</td></tr>\n",
+            stack_anchor(&r.meta.target),
+            esc(&r.meta.target),
+            verdict_class(&r.summary.verdict),
+            esc(&r.summary.verdict),
+            r.summary.confidence,
+            r.summary.readiness,
+            esc(&r.summary.risk),
+            r.summary.stability,
+        ));
+    }
+    body.push_str("</table>\n");
+
+    for r in reports {
+        body.push_str("<hr>\n");
+        body.push_str(&html_stack_section(r, Some(&stack_anchor(&r.meta.target))));
+    }
+
+    Ok(html_document("Rehearsa Fleet Compliance Report", &body))
+}
+
+fn html_document(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        esc(title),
+        HTML_STYLE,
+        body,
+    )
+}
+
+/// One stack's header, verdict banner, six sections and footer.
+/// `anchor` sets the id used by the fleet summary links.
+fn html_stack_section(report: &ComplianceReport, anchor: Option<&str>) -> String {
+    let mut h = String::new();
+
+    let dl = |rows: &[(&str, String)]| -> String {
+        let mut s = String::from("<dl>\n");
+        for (k, v) in rows {
+            s.push_str(&format!("<dt>{}</dt><dd>{}</dd>\n", esc(k), esc(v)));
+        }
+        s.push_str("</dl>\n");
+        s
+    };
+
+    // ── Header & verdict banner ──────────────────────────────────────────
+    h.push_str(&format!(
+        "<section{}>\n<h1>Rehearsa <small>Compliance Report — {}</small></h1>\n",
+        anchor.map(|a| format!(" id=\"{}\"", a)).unwrap_or_default(),
+        esc(&report.meta.target),
+    ));
+    h.push_str(&format!(
+        "<p class=\"meta\">Stack: {} · Host: {} · Generated: {} · ID: {}</p>\n",
+        esc(&report.meta.target),
+        esc(report.meta.host.as_deref().unwrap_or("unknown")),
+        esc(&short_ts(&report.meta.generated_at)),
+        esc(&report.meta.report_id),
+    ));
+    h.push_str(&format!(
+        "<div class=\"banner {}\">{} — Confidence: {}% · Readiness: {}% · Risk: {} · Stability: {}%</div>\n",
+        verdict_class(&report.summary.verdict),
+        esc(&report.summary.verdict),
+        report.summary.confidence,
+        report.summary.readiness,
+        esc(&report.summary.risk),
+        report.summary.stability,
+    ));
+
+    // ── 1. Latest rehearsal ──────────────────────────────────────────────
+    h.push_str("<h2>1. Latest Rehearsal</h2>\n");
+    h.push_str(&dl(&[
+        ("Timestamp",     short_ts(&report.rehearsal.timestamp)),
+        ("Duration",      format!("{}s", report.rehearsal.duration_seconds)),
+        ("Confidence",    format!("{}%", report.rehearsal.confidence)),
+        ("Readiness",     format!("{}%", report.rehearsal.readiness)),
+        ("Risk Band",     report.rehearsal.risk.clone()),
+        ("Exit Code",     report.rehearsal.exit_code.to_string()),
+        ("Docker Engine", report.meta.docker_version.clone().unwrap_or_else(|| "not recorded".to_string())),
+    ]));
+
+    h.push_str("<h3>Service Scores</h3>\n<table>\n<tr><th>Service</th><th>Score</th><th></th></tr>\n");
+    let mut services: Vec<(&String, &u32)> = report.rehearsal.services.iter().collect();
+    services.sort_by_key(|(k, _)| k.as_str());
+    for (name, score) in services {
+        h.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}%</td><td><span class=\"bar\"><span style=\"width:{}%\"></span></span></td></tr>\n",
+            esc(name), score, (*score).min(100),
+        ));
+    }
+    h.push_str("</table>\n");
+
+    let mut failed_logs: Vec<(&String, &String)> = report.rehearsal.service_logs.iter().collect();
+    failed_logs.sort_by_key(|(k, _)| k.as_str());
+    for (name, logs) in failed_logs {
+        h.push_str(&format!(
+            "<details><summary>Logs: {} (failed)</summary>\n<pre>{}</pre>\n</details>\n",
+            esc(name), esc(logs),
+        ));
+    }
+
+    // ── 2. History & trend ───────────────────────────────────────────────
+    h.push_str("<h2>2. History &amp; Trend</h2>\n");
+    let mut rows = vec![
+        ("Window",    format!("{} runs", report.history.window)),
+        ("Stability", format!("{}%", report.history.stability)),
+        ("Trend",     report.history.trend.clone()),
+    ];
+    let mut engines: Vec<&str> = report.history.runs
+        .iter()
+        .filter_map(|r| r.docker_version.as_deref())
+        .collect();
+    engines.dedup();
+    if engines.len() > 1 {
+        rows.push(("Docker Engine Changes", engines.join(" → ")));
+    }
+    h.push_str(&dl(&rows));
+
+    h.push_str("<table>\n<tr><th>Timestamp</th><th>Confidence</th><th>Readiness</th><th>Duration</th><th>Risk</th><th>Host</th></tr>\n");
+    for run in &report.history.runs {
+        h.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}%</td><td class=\"num\">{}%</td><td class=\"num\">{}s</td><td>{}</td><td>{}</td></tr>\n",
+            esc(&short_ts(&run.timestamp)),
+            run.confidence,
+            run.readiness.unwrap_or(0),
+            run.duration_seconds,
+            esc(&run.risk),
+            esc(run.host.as_deref().unwrap_or("-")),
+        ));
+    }
+    h.push_str("</table>\n");
+
+    // ── 3. Baseline contract ─────────────────────────────────────────────
+    h.push_str("<h2>3. Baseline Contract</h2>\n");
+    let b = &report.baseline;
+    let mut rows = vec![
+        ("Pinned", if b.pinned { "Yes" } else { "No" }.to_string()),
+        ("Status", b.status.clone()),
+    ];
+    if let Some(severity) = b.drift_severity {
+        rows.push(("Drift Severity", severity.to_string()));
+    }
+    if b.pinned {
+        if let Some(c) = b.expected_confidence { rows.push(("Expected Confidence", format!("{}%", c))); }
+        if let Some(r) = b.expected_readiness  { rows.push(("Expected Readiness", format!("{}%", r))); }
+        if let Some(d) = b.expected_duration   { rows.push(("Expected Duration", format!("{}s", d))); }
+        if let Some(d) = b.confidence_delta    { rows.push(("Confidence Δ", format!("{:+}%", d))); }
+        if let Some(d) = b.readiness_delta     { rows.push(("Readiness Δ", format!("{:+}%", d))); }
+        if let Some(d) = b.duration_delta_pct  { rows.push(("Duration Δ", format!("{:+}%", d))); }
+        if !b.new_services.is_empty()     { rows.push(("New Services", b.new_services.join(", "))); }
+        if !b.missing_services.is_empty() { rows.push(("Missing Services", b.missing_services.join(", "))); }
+    }
+    h.push_str(&dl(&rows));
+
+    // ── 4. Policy compliance ─────────────────────────────────────────────
+    h.push_str("<h2>4. Policy Compliance</h2>\n");
+    h.push_str(&dl(&[
+        ("Configured", if report.policy.configured { "Yes" } else { "No" }.to_string()),
+        ("Verdict",    report.policy.verdict.clone()),
+    ]));
+
+    if !report.policy.checks.is_empty() {
+        h.push_str("<table>\n<tr><th>Rule</th><th>Setting</th><th>Result</th><th>Detail</th></tr>\n");
+        for check in &report.policy.checks {
+            h.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td>{}</td></tr>\n",
+                esc(&check.rule),
+                esc(&check.setting),
+                verdict_class(&check.result),
+                esc(&check.result),
+                esc(&check.detail),
+            ));
+        }
+        h.push_str("</table>\n");
+    }
+
+    if !report.policy.effective.is_empty() {
+        h.push_str("<h3>Effective Policy</h3>\n<table>\n<tr><th>Rule</th><th>Value</th><th>Source</th></tr>\n");
+        for rule in &report.policy.effective {
+            h.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                esc(&rule.rule), esc(&rule.value), esc(&rule.source.to_string()),
+            ));
+        }
+        h.push_str("</table>\n");
+    }
+
+    // ── 5. Preflight ─────────────────────────────────────────────────────
+    h.push_str("<h2>5. Preflight</h2>\n");
+    h.push_str(&dl(&[(
+        "Restore Readiness Score",
+        format!("{}%", report.preflight.restore_readiness_score),
+    )]));
+
+    if !report.preflight.findings.is_empty() {
+        h.push_str("<table>\n<tr><th>Severity</th><th>Finding</th><th>ID</th></tr>\n");
+        for f in &report.preflight.findings {
+            let id = match (&f.id, f.accepted) {
+                (Some(id), true)  => format!("{} (accepted)", id),
+                (Some(id), false) => id.clone(),
+                (None, _)         => String::new(),
+            };
+            h.push_str(&format!(
+                "<tr{}><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                if f.accepted { " class=\"accepted\"" } else { "" },
+                esc(&f.severity), esc(&f.message), esc(&id),
+            ));
+        }
+        h.push_str("</table>\n");
+    }
+
+    // ── 6. Backup provider ───────────────────────────────────────────────
+    h.push_str("<h2>6. Backup Provider</h2>\n");
+    let p = &report.provider;
+    let mut rows = vec![("Attached", if p.attached { "Yes" } else { "No" }.to_string())];
+    if p.attached {
+        if let Some(ref n) = p.name       { rows.push(("Name", n.clone())); }
+        if let Some(ref k) = p.kind       { rows.push(("Kind", k.clone())); }
+        if let Some(ref r) = p.repository { rows.push(("Repository", r.clone())); }
+        rows.push(("Verified", match p.verified {
+            Some(true)  => "YES — OK",
+            Some(false) => "NO — FAILED",
+            None        => "Not checked at report time — run `rehearsa provider verify`",
+        }.to_string()));
+    }
+    h.push_str(&dl(&rows));
+
+    // ── Footer ───────────────────────────────────────────────────────────
+    h.push_str(&format!(
+        "<footer>Rehearsa v{} · Report ID: {} · {}{}</footer>\n</section>\n",
+        esc(&report.meta.rehearsa_version),
+        esc(&report.meta.report_id),
+        esc(report.meta.generated_at.get(..10).unwrap_or(&report.meta.generated_at)),
+        report.meta.content_hash.as_deref()
+            .map(|hash| format!("<br>SHA-256: {}", esc(hash)))
+            .unwrap_or_default(),
+    ));
+
+    h
+}
+
+/// CSS class for a PASS / WARN / FAIL style verdict.
+fn verdict_class(verdict: &str) -> &'static str {
+    match verdict {
+        "PASS" => "pass",
+        "WARN" => "warn",
+        "FAIL" => "fail",
+        _      => "",
+    }
+}
+
+fn stack_anchor(stack: &str) -> String {
+    let slug: String = stack
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    format!("stack-{}", slug)
+}
+
+/// Escape text for HTML element content and attribute values.
+fn esc(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&'  => out.push_str("&amp;"),
+            '<'  => out.push_str("&lt;"),
+            '>'  => out.push_str("&gt;"),
+            '"'  => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _    => out.push(c),
+        }
+    }
+    out
+}
+
+// ======================================================
+// MARKDOWN OUTPUT
+// ======================================================
+//
+// GitHub-flavoured Markdown for PR comments. Every number comes straight
+// from the assembled ComplianceReport, same as the JSON.
+
+/// Render a single-stack report as a Markdown document.
+pub fn render_markdown(report: &ComplianceReport) -> Result<String, String> {
+    Ok(md_stack_section(report, "#"))
+}
+
+/// Render a fleet report as one Markdown document: a summary table linking
+/// to each stack's section.
+pub fn render_markdown_fleet(reports: &[ComplianceReport]) -> Result<String, String> {
+    let mut m = String::new();
+
+    m.push_str("# Rehearsa Fleet Compliance Report\n\n");
+    m.push_str(&format!(
+        "{} stacks · Generated {}\n\n",
+        reports.len(),
+        short_ts(&chrono::Utc::now().to_rfc3339()),
+    ));
+
+    m.push_str("| Stack | Verdict | Confidence | Readiness | Risk | Stability |\n");
+    m.push_str("|---|---|--:|--:|---|--:|\n");
+    for r in reports {
+        m.push_str(&format!(
+            "| [{}](#{}) | {} {} | {}% | {}% | {} | {}% |\n",
+            md_cell(&r.meta.target),
+            stack_anchor(&r.meta.target),
+            verdict_emoji(&r.summary.verdict),
+            r.summary.verdict,
+            r.summary.confidence,
+            r.summary.readiness,
+            md_cell(&r.summary.risk),
+            r.summary.stability,
+        ));
+    }
+
+    for r in reports {
+        m.push_str("\n---\n\n");
+        m.push_str(&format!("<a id=\"{}\"></a>\n\n", stack_anchor(&r.meta.target)));
+        m.push_str(&md_stack_section(r, "##"));
+    }
+
+    Ok(m)
+}
+
+/// One stack's title, verdict line and sections. `level` is the heading
+/// marker for the title; sections sit one level below it.
+fn md_stack_section(report: &ComplianceReport, level: &str) -> String {
+    let mut m = String::new();
+    let sub = format!("{}#", level);
+
+    // ── Title & verdict line ─────────────────────────────────────────────
+    m.push_str(&format!("{} Rehearsa Compliance Report — {}\n\n", level, md_cell(&report.meta.target)));
+    m.push_str(&format!(
+        "**{} {}** — Confidence {}% · Readiness {}% · Risk {} · Stability {}%\n\n",
+        verdict_emoji(&report.summary.verdict),
+        report.summary.verdict,
+        report.summary.confidence,
+        report.summary.readiness,
+        report.summary.risk,
+        report.summary.stability,
+    ));
+    m.push_str(&format!(
+        "Host `{}` · Generated {} · Report ID `{}`\n\n",
+        report.meta.host.as_deref().unwrap_or("unknown"),
+        short_ts(&report.meta.generated_at),
+        report.meta.report_id,
+    ));
+    if let Some(ref hash) = report.meta.content_hash {
+        m.push_str(&format!("SHA-256 `{}`\n\n", hash));
+    }
+
+    // ── Latest rehearsal ─────────────────────────────────────────────────
+    m.push_str(&format!("{} Latest Rehearsal\n\n", sub));
+    m.push_str(&format!(
+        "{} · {}s · exit code {} · Docker {}\n\n",
+        short_ts(&report.rehearsal.timestamp),
+        report.rehearsal.duration_seconds,
+        report.rehearsal.exit_code,
+        report.meta.docker_version.as_deref().unwrap_or("not recorded"),
+    ));
+
+    if !report.rehearsal.services.is_empty() {
+        m.push_str("| Service | Score |\n|---|--:|\n");
+        let mut services: Vec<(&String, &u32)> = report.rehearsal.services.iter().collect();
+        services.sort_by_key(|(k, _)| k.as_str());
+        for (name, score) in services {
+            m.push_str(&format!("| {} | {}% |\n", md_cell(name), score));
+        }
+        m.push('\n');
+    }
+
+    let mut failed_logs: Vec<(&String, &String)> = report.rehearsal.service_logs.iter().collect();
+    failed_logs.sort_by_key(|(k, _)| k.as_str());
+    for (name, logs) in failed_logs {
+        m.push_str(&format!(
+            "<details>\n<summary>Logs: {} (failed)</summary>\n\n~~~~\n{}\n~~~~\n\n</details>\n\n",
+            name, logs,
+        ));
+    }
+
+    // ── History ──────────────────────────────────────────────────────────
+    m.push_str(&format!("{} History\n\n", sub));
+    m.push_str(&format!(
+        "Last {} runs · Stability {}% · Trend {}\n\n",
+        report.history.window, report.history.stability, report.history.trend,
+    ));
+
+    if !report.history.runs.is_empty() {
+        m.push_str("| Timestamp | Confidence | Readiness | Duration | Risk | Host |\n");
+        m.push_str("|---|--:|--:|--:|---|---|\n");
+        for run in &report.history.runs {
+            m.push_str(&format!(
+                "| {} | {}% | {}% | {}s | {} | {} |\n",
+                short_ts(&run.timestamp),
+                run.confidence,
+                run.readiness.unwrap_or(0),
+                run.duration_seconds,
+                md_cell(&run.risk),
+                md_cell(run.host.as_deref().unwrap_or("-")),
+            ));
+        }
+        m.push('\n');
+    }
+
+    // ── Policy ───────────────────────────────────────────────────────────
+    m.push_str(&format!("{} Policy — {}\n\n", sub, report.policy.verdict));
+    if !report.policy.configured {
+        m.push_str("No policy configured.\n\n");
+    }
+    if !report.policy.checks.is_empty() {
+        m.push_str("| Rule | Setting | Result | Detail |\n|---|---|---|---|\n");
+        for check in &report.policy.checks {
+            m.push_str(&format!(
+                "| {} | {} | {} {} | {} |\n",
+                md_cell(&check.rule),
+                md_cell(&check.setting),
+                verdict_emoji(&check.result),
+                check.result,
+                md_cell(&check.detail),
+            ));
+        }
+        m.push('\n');
+    }
+
+    // ── Baseline (collapsed) ─────────────────────────────────────────────
+    let b = &report.baseline;
+    m.push_str(&format!("<details>\n<summary>Baseline — {}</summary>\n\n", b.status));
+    if b.pinned {
+        m.push_str("| | Expected | Δ |\n|---|--:|--:|\n");
+        m.push_str(&format!(
+            "| Confidence | {} | {} |\n",
+            b.expected_confidence.map(|c| format!("{}%", c)).unwrap_or_else(|| "-".into()),
+            b.confidence_delta.map(|d| format!("{:+}%", d)).unwrap_or_else(|| "-".into()),
+        ));
+        m.push_str(&format!(
+            "| Readiness | {} | {} |\n",
+            b.expected_readiness.map(|r| format!("{}%", r)).unwrap_or_else(|| "-".into()),
+            b.readiness_delta.map(|d| format!("{:+}%", d)).unwrap_or_else(|| "-".into()),
+        ));
+        m.push_str(&format!(
+            "| Duration | {} | {} |\n\n",
+            b.expected_duration.map(|d| format!("{}s", d)).unwrap_or_else(|| "-".into()),
+            b.duration_delta_pct.map(|d| format!("{:+}%", d)).unwrap_or_else(|| "-".into()),
+        ));
+        if let Some(severity) = b.drift_severity {
+            m.push_str(&format!("Drift severity: {}\n\n", severity));
+        }
+        if !b.new_services.is_empty() {
+            m.push_str(&format!("New services: {}\n\n", b.new_services.join(", ")));
+        }
+        if !b.missing_services.is_empty() {
+            m.push_str(&format!("Missing services: {}\n\n", b.missing_services.join(", ")));
+        }
+    } else {
+        m.push_str("No baseline pinned.\n\n");
+    }
+    m.push_str("</details>\n");
+
+    m
+}
+
+fn verdict_emoji(verdict: &str) -> &'static str {
+    match verdict {
+        "PASS" => "✅",
+        "WARN" => "⚠️",
+        "FAIL" => "❌",
+        _      => "➖",
+    }
+}
+
+/// Make text safe inside a Markdown table cell.
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
 // ======================================================
 // HELPERS
 // ======================================================
 
+/// "2026-02-23T03:00:01.123+00:00" → "2026-02-23 03:00:01".
+fn short_ts(ts: &str) -> String {
+    ts.get(..19).unwrap_or(ts).replace('T', " ")
+}
+
 /// ASCII mini bar for service score column.
 fn score_bar(score: u32) -> String {
     let filled = (score / 10) as usize;
@@ -1011,30 +2033,94 @@ pub struct ReportArgs {
     pub output:   Option<String>,   // None = stdout / current dir
     pub provider: Option<String>,
     pub window:   usize,
+    /// Embed a content hash in each report (`--sign`).
+    pub sign:     bool,
 }
 
 #[derive(Clone, PartialEq)]
 pub enum ReportFormat {
     Json,
     Pdf,
+    Html,
+    Markdown,
+    /// JSON and PDF.
     Both,
 }
 
-pub fn run_report(args: &ReportArgs) -> Result<(), String> {
+/// Headline verdict, ordered by severity so a fleet aggregates to the worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReportVerdict {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl ReportVerdict {
+    fn from_summary(summary: &ReportSummary) -> Self {
+        Self::from_label(&summary.verdict)
+    }
+
+    /// "PASS" | "WARN" | "FAIL" as stored in the report.
+    fn from_label(verdict: &str) -> Self {
+        match verdict {
+            "FAIL" => ReportVerdict::Fail,
+            "WARN" => ReportVerdict::Warn,
+            _      => ReportVerdict::Pass,
+        }
+    }
+}
+
+impl std::fmt::Display for ReportVerdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportVerdict::Pass => write!(f, "PASS"),
+            ReportVerdict::Warn => write!(f, "WARN"),
+            ReportVerdict::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReportVerdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "fail" => Ok(ReportVerdict::Fail),
+            "warn" => Ok(ReportVerdict::Warn),
+            other  => Err(format!("Unknown verdict '{}'. Use fail or warn.", other)),
+        }
+    }
+}
+
+/// Writes the report(s) and returns the aggregate verdict.
+pub fn run_report(args: &ReportArgs) -> Result<ReportVerdict, String> {
     match args.stack {
         Some(ref stack) => run_single_report(stack, args),
         None            => run_fleet_report(args),
     }
 }
 
-fn run_single_report(stack: &str, args: &ReportArgs) -> Result<(), String> {
+/// Print the verdict line. Goes to stderr when JSON is being written to
+/// stdout so the JSON stays parseable.
+fn print_verdict(args: &ReportArgs, line: &str) {
+    if args.output.is_none() && args.format != ReportFormat::Pdf {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+fn run_single_report(stack: &str, args: &ReportArgs) -> Result<ReportVerdict, String> {
     let opts = ReportOptions {
         stack:          stack.to_string(),
         history_window: args.window,
         provider_name:  args.provider.clone(),
     };
 
-    let report = build_report(&opts)?;
+    let mut report = build_report(&opts)?;
+    if args.sign {
+        sign_report(&mut report)?;
+    }
 
     if args.format == ReportFormat::Json || args.format == ReportFormat::Both {
         let json = render_json(&report)?;
@@ -1048,16 +2134,37 @@ fn run_single_report(stack: &str, args: &ReportArgs) -> Result<(), String> {
         write_or_print(&pdf, &path, "pdf")?;
     }
 
-    Ok(())
+    if args.format == ReportFormat::Html {
+        let html = render_html(&report)?;
+        let path = resolve_output_path(&args.output, stack, "html");
+        write_or_print(&html.into_bytes(), &path, "html")?;
+    }
+
+    if args.format == ReportFormat::Markdown {
+        let md = render_markdown(&report)?;
+        let path = resolve_output_path(&args.output, stack, "md");
+        write_or_print(&md.into_bytes(), &path, "md")?;
+    }
+
+    let verdict = ReportVerdict::from_summary(&report.summary);
+    print_verdict(args, &format!("Verdict: {}", verdict));
+
+    Ok(verdict)
 }
 
-fn run_fleet_report(args: &ReportArgs) -> Result<(), String> {
-    let reports = build_fleet_report();
+fn run_fleet_report(args: &ReportArgs) -> Result<ReportVerdict, String> {
+    let mut reports = build_fleet_report();
 
     if reports.is_empty() {
         return Err("No stacks with rehearsal history found.".to_string());
     }
 
+    if args.sign {
+        for report in &mut reports {
+            sign_report(report)?;
+        }
+    }
+
     // JSON fleet: one file, array of all reports
     if args.format == ReportFormat::Json || args.format == ReportFormat::Both {
         let json = render_json_fleet(&reports)?;
@@ -1074,7 +2181,37 @@ fn run_fleet_report(args: &ReportArgs) -> Result<(), String> {
         }
     }
 
-    Ok(())
+    // HTML fleet: one navigable page with a section per stack
+    if args.format == ReportFormat::Html {
+        let html = render_html_fleet(&reports)?;
+        let path = resolve_output_path(&args.output, "fleet", "html");
+        write_or_print(&html.into_bytes(), &path, "html")?;
+    }
+
+    // Markdown fleet: one document with a section per stack
+    if args.format == ReportFormat::Markdown {
+        let md = render_markdown_fleet(&reports)?;
+        let path = resolve_output_path(&args.output, "fleet", "md");
+        write_or_print(&md.into_bytes(), &path, "md")?;
+    }
+
+    let verdicts: Vec<ReportVerdict> = reports
+        .iter()
+        .map(|r| ReportVerdict::from_summary(&r.summary))
+        .collect();
+
+    let count = |v: ReportVerdict| verdicts.iter().filter(|x| **x == v).count();
+    let fleet = verdicts.iter().copied().max().unwrap_or(ReportVerdict::Pass);
+
+    print_verdict(args, &format!(
+        "Fleet verdict: {}  ({} FAIL, {} WARN, {} PASS)",
+        fleet,
+        count(ReportVerdict::Fail),
+        count(ReportVerdict::Warn),
+        count(ReportVerdict::Pass),
+    ));
+
+    Ok(fleet)
 }
 
 fn resolve_output_path(
@@ -1091,13 +2228,13 @@ fn resolve_output_path(
         }
         Some(path) => path.clone(),
         None if ext == "pdf" => format!("{}-report.pdf", stem),
-        None => "-".to_string(), // stdout for JSON
+        None => "-".to_string(), // stdout for JSON, HTML and Markdown
     }
 }
 
 fn write_or_print(bytes: &[u8], path: &str, kind: &str) -> Result<(), String> {
     if path == "-" {
-        // stdout — only valid for JSON
+        // stdout — only valid for text formats (JSON, HTML, Markdown)
         let s = std::str::from_utf8(bytes)
             .map_err(|e| format!("UTF-8 error: {}", e))?;
         print!("{}", s);
@@ -1115,6 +2252,7 @@ fn write_or_print(bytes: &[u8], path: &str, kind: &str) -> Result<(), String> {
     fs::write(path, bytes)
         .map_err(|e| format!("Failed to write {}: {}", path, e))?;
 
-    println!("Report written: {} ({})", path, kind.to_uppercase());
+    let label = if kind == "md" { "MARKDOWN".to_string() } else { kind.to_uppercase() };
+    println!("Report written: {} ({})", path, label);
     Ok(())
 }
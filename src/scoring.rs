@@ -0,0 +1,236 @@
+use serde::{Serialize, Deserialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+
+// ======================================================
+// SCORING CONFIG
+// ======================================================
+//
+// Per-service weights, score-band overrides and start retries. A stack's file at
+// ~/.rehearsa/scoring/<stack>.json is overlaid on the global defaults
+// at ~/.rehearsa/scoring.json. With neither present every service
+// weighs 1 and the built-in bands apply — confidence is unchanged.
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ScoringConfig {
+    /// Weight multiplier per service name. Unlisted services weigh 1;
+    /// 0 excludes a service from confidence.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub weights: BTreeMap<String, u32>,
+
+    /// Score awarded for a healthy service (or a successful oneshot).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub healthy: Option<u32>,
+    /// Score for a running service with no healthcheck.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub running: Option<u32>,
+    /// Score for a service whose healthcheck reports unhealthy.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unhealthy: Option<u32>,
+    /// Score for a service that exited, timed out or was not started.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed: Option<u32>,
+
+    /// Times a container that exits before the timeout is removed and
+    /// recreated before its score is recorded. Unset means no retries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_retries: Option<u32>,
+}
+
+impl ScoringConfig {
+    /// Overlay the fields set on `other` onto a copy of `self`.
+    fn merged_with(&self, other: &ScoringConfig) -> ScoringConfig {
+        let mut weights = self.weights.clone();
+        weights.extend(other.weights.iter().map(|(k, v)| (k.clone(), *v)));
+
+        ScoringConfig {
+            weights,
+            healthy:   other.healthy.or(self.healthy),
+            running:   other.running.or(self.running),
+            unhealthy: other.unhealthy.or(self.unhealthy),
+            failed:    other.failed.or(self.failed),
+            start_retries: other.start_retries.or(self.start_retries),
+        }
+    }
+
+    pub fn bands(&self) -> ScoreBands {
+        let d = ScoreBands::default();
+        ScoreBands {
+            healthy:   self.healthy.unwrap_or(d.healthy),
+            running:   self.running.unwrap_or(d.running),
+            unhealthy: self.unhealthy.unwrap_or(d.unhealthy),
+            failed:    self.failed.unwrap_or(d.failed),
+        }
+    }
+
+    pub fn start_retries(&self) -> u32 {
+        self.start_retries.unwrap_or(0)
+    }
+
+    pub fn weight(&self, service: &str) -> u32 {
+        self.weights.get(service).copied().unwrap_or(1)
+    }
+
+    /// Weighted average of `scores`. Falls back to the plain average when
+    /// every weight is 0; returns None when there are no scores.
+    pub fn confidence(&self, scores: &HashMap<String, u32>) -> Option<u32> {
+        let total_weight: u32 = scores.keys().map(|s| self.weight(s)).sum();
+
+        if total_weight == 0 {
+            let total: u32 = scores.values().sum();
+            return total.checked_div(scores.len() as u32);
+        }
+
+        let weighted: u32 = scores.iter().map(|(s, v)| v * self.weight(s)).sum();
+        Some(weighted / total_weight)
+    }
+}
+
+/// Per-outcome service scores, fully resolved.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreBands {
+    pub healthy:   u32,
+    pub running:   u32,
+    pub unhealthy: u32,
+    pub failed:    u32,
+}
+
+impl Default for ScoreBands {
+    fn default() -> Self {
+        ScoreBands { healthy: 100, running: 85, unhealthy: 40, failed: 0 }
+    }
+}
+
+impl ScoreBands {
+    /// Whether a service score counts as a failure: at or below the
+    /// failed band. Log capture, --keep-on-failure and
+    /// fail_on_new_service_failure all use this.
+    pub fn is_failed(&self, score: u32) -> bool {
+        score <= self.failed
+    }
+}
+
+// ======================================================
+// PATHS
+// ======================================================
+
+fn rehearsa_dir() -> Result<PathBuf, String> {
+    let home = dirs::home_dir()
+        .ok_or("Could not determine home directory")?;
+    Ok(home.join(".rehearsa"))
+}
+
+fn global_path() -> Result<PathBuf, String> {
+    Ok(rehearsa_dir()?.join("scoring.json"))
+}
+
+fn stack_path(stack: &str) -> Result<PathBuf, String> {
+    let dir = rehearsa_dir()?.join("scoring");
+
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create scoring dir: {}", e))?;
+    }
+
+    Ok(dir.join(format!("{}.json", stack)))
+}
+
+fn read_config(path: Result<PathBuf, String>) -> Option<ScoringConfig> {
+    let content = fs::read_to_string(path.ok()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+// ======================================================
+// LOAD / SAVE
+// ======================================================
+
+/// Global defaults overlaid with the stack's own settings.
+pub fn load_scoring(stack: &str) -> ScoringConfig {
+    let global = read_config(global_path()).unwrap_or_default();
+
+    match read_config(stack_path(stack)) {
+        Some(own) => global.merged_with(&own),
+        None      => global,
+    }
+}
+
+/// Update the stack's own scoring file with a weight, band overrides
+/// and/or start retries.
+pub fn set_scoring(
+    stack: &str,
+    service: Option<(&str, u32)>,
+    bands: &ScoringConfig,
+) -> Result<(), String> {
+    for value in [bands.healthy, bands.running, bands.unhealthy, bands.failed].into_iter().flatten() {
+        if value > 100 {
+            return Err(format!("Score band values must be 0-100 (got {})", value));
+        }
+    }
+
+    let mut config = read_config(stack_path(stack)).unwrap_or_default();
+
+    if let Some((name, weight)) = service {
+        config.weights.insert(name.to_string(), weight);
+    }
+    config = config.merged_with(bands);
+
+    let path = stack_path(stack)?;
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize scoring config: {}", e))?;
+
+    fs::write(path, json)
+        .map_err(|e| format!("Failed to write scoring config: {}", e))
+}
+
+// ======================================================
+// DISPLAY
+// ======================================================
+
+/// Print the effective weights and bands. `services` lists the stack's
+/// services (from its latest run) so default weights are shown too.
+pub fn show_scoring(stack: &str, services: &[String]) {
+    let config = load_scoring(stack);
+    let bands = config.bands();
+
+    println!("Scoring for '{}':", stack);
+    println!();
+    println!("Score bands:");
+    println!("  {:<12} {}", "healthy", bands.healthy);
+    println!("  {:<12} {}", "running", bands.running);
+    println!("  {:<12} {}", "unhealthy", bands.unhealthy);
+    println!("  {:<12} {}", "failed", bands.failed);
+    println!();
+    println!("Start retries: {}", config.start_retries());
+    println!();
+
+    let mut names: Vec<&String> = services.iter().chain(config.weights.keys()).collect();
+    names.sort();
+    names.dedup();
+
+    if names.is_empty() {
+        println!("No services known yet — every service weighs 1.");
+        return;
+    }
+
+    println!("Service weights:");
+    for name in names {
+        let marker = if config.weights.contains_key(name) { "" } else { "  (default)" };
+        println!("  {:<24} {}{}", name, config.weight(name), marker);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scores_at_or_below_the_failed_band_are_failures() {
+        let bands = ScoreBands { failed: 10, ..ScoreBands::default() };
+
+        assert!(bands.is_failed(0));
+        assert!(bands.is_failed(10));
+        assert!(!bands.is_failed(11));
+        assert!(!ScoreBands::default().is_failed(1));
+    }
+}